@@ -0,0 +1,90 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ferrux_engine::geometry::vector::Point3;
+use ferrux_engine::geometry::{Aabb, BoundingSphere, Mesh, Rotation};
+use ferrux_engine::math::builders::{RotationAxis, RotationMatrixBuilder};
+use ferrux_engine::math::Matrix4;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Grid mesh of two triangles per cell, with the given number of points per side
+fn grid(side: usize) -> Mesh {
+    let mut content = String::new();
+    for i in 0..side * side {
+        content += &format!("v {} {} 0\n", i % side, i / side);
+    }
+    for row in 0..side - 1 {
+        for column in 0..side - 1 {
+            let a = row * side + column + 1;
+            content += &format!("f {} {} {}\nf {} {} {}\n", a, a + 1, a + side, a + 1, a + side + 1, a + side);
+        }
+    }
+    Mesh::try_from(content).unwrap()
+}
+
+/// Points and normals shared one by one, as meshes kept them before the [VertexBuffer]s. The
+/// rotation repeats the one of those meshes, since they can't be built anymore.
+///
+/// [VertexBuffer]: ferrux_engine::geometry::VertexBuffer
+struct SharedPoints {
+    points: Vec<Rc<RefCell<Point3>>>,
+    normals: Vec<Rc<RefCell<Point3>>>,
+    orientation: Matrix4,
+    aabb: Aabb,
+    bounding_sphere: BoundingSphere,
+}
+
+impl SharedPoints {
+    fn of(mesh: &Mesh) -> Self {
+        let share = |point: Point3| Rc::new(RefCell::new(point));
+        Self {
+            points: mesh.points().iter().map(share).collect(),
+            normals: mesh.normals().iter().map(share).collect(),
+            orientation: Matrix4::identity(),
+            aabb: Aabb::default(),
+            bounding_sphere: BoundingSphere::default(),
+        }
+    }
+}
+
+impl Rotation for SharedPoints {
+    fn rotate(&mut self, rotation: &Matrix4) {
+        for point in &self.points {
+            point.borrow_mut().rotate(rotation);
+        }
+        for normal in &self.normals {
+            normal.borrow_mut().rotate(rotation);
+        }
+        self.orientation = &self.orientation * rotation;
+        let points: Vec<Point3> = self.points.iter().map(|point| point.borrow().clone()).collect();
+        self.aabb = Aabb::from_points(&points);
+        self.bounding_sphere = BoundingSphere::from_points(&points);
+    }
+}
+
+fn rotation() -> Matrix4 {
+    RotationMatrixBuilder::new().in_axis(RotationAxis::Y).with_theta(0.01).build()
+}
+
+/// Rotates whole meshes, with their points and normals, against rotating their shared points one
+/// by one
+fn mesh_rotation(c: &mut Criterion) {
+    let matrix = rotation();
+    let mut group = c.benchmark_group("mesh_rotation");
+    // Around a thousand and a hundred thousand points
+    for side in [32, 317] {
+        let mut mesh = grid(side);
+        let count = mesh.points().len();
+
+        let mut shared = SharedPoints::of(&mesh);
+        group.bench_function(BenchmarkId::new("shared_points", count), |b| {
+            b.iter(|| shared.rotate(black_box(&matrix)))
+        });
+        group.bench_function(BenchmarkId::new("mesh", count), |b| {
+            b.iter(|| mesh.rotate(black_box(&matrix)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mesh_rotation);
+criterion_main!(benches);
@@ -1,17 +1,73 @@
-use crate::FerruxViewport;
-use crate::engine::EngineCamera;
+use crate::collision::{Collider, Contact};
+use crate::geometry::vector::Point3;
+use crate::geometry::{Aabb, BoundingSphere, Ray};
+use crate::physics::RigidBody;
+use crate::render::{DrawMode, Rasterizer, RenderContext};
 
 /// Entities than can be drawn in the canvas
 pub trait Drawable {
-    /// Draws the entity in the given [EngineCanvas]
+    /// Draws the entity with the given [Rasterizer]
     ///
     /// # Arguments
-    /// * `viewport` - Viewport to draw the Drawable
-    fn draw(&self, viewport: &mut FerruxViewport, camera: &EngineCamera);
+    /// * `rasterizer` - Rasterizer to draw the Drawable
+    /// * `context` - State of the scene, like the camera and the lights
+    fn draw(&self, rasterizer: &mut Rasterizer, context: &RenderContext);
+
+    /// Adds the triangles of the entity casting shadows, in world space. By default, none.
+    ///
+    /// # Arguments
+    /// * `casters` - Triangles casting shadows in the scene
+    /// * `context` - State of the scene, like the camera and the lights
+    fn cast_shadows(&self, _casters: &mut Vec<[Point3; 3]>, _context: &RenderContext) {}
+
+    /// Returns the axis-aligned box enclosing the entity, in world space. Entities without one
+    /// are never culled.
+    fn aabb(&self) -> Option<&Aabb> {
+        None
+    }
+
+    /// Returns the sphere enclosing the entity, in world space. Entities without one are never
+    /// culled.
+    fn bounding_sphere(&self) -> Option<&BoundingSphere> {
+        None
+    }
+
+    /// Returns the index of the closest triangle of the entity crossed by the ray, in world space,
+    /// and the distance to it. By default, entities can't be hit.
+    fn intersect(&self, _ray: &Ray) -> Option<(usize, f32)> {
+        None
+    }
 }
 
 /// Updatable entities
 pub trait Actor: Drawable {
     /// Updates the entity state
     fn update(&mut self, delta: u128);
+
+    /// Changes the global [DrawMode]. Actors without a drawing mode can just ignore it.
+    fn set_draw_mode(&mut self, _mode: DrawMode) {}
+
+    /// Returns the volume of the entity tested against the others to find its collisions, in
+    /// world space. By default, entities don't collide.
+    fn collider(&self) -> Option<Collider<'_>> {
+        None
+    }
+
+    /// Reacts to a collision with another actor, found after the update of all of them
+    ///
+    /// # Arguments
+    /// * `contact` - Contact with the normal pointing from this actor to the other one
+    fn on_collision(&mut self, _contact: &Contact) {}
+
+    /// Returns the body moving the entity in the fixed step of the physics. By default, entities
+    /// are only moved by themselves.
+    fn rigid_body(&mut self) -> Option<&mut RigidBody> {
+        None
+    }
+
+    /// Moves the entity, as requested by the physics for the ones with a [RigidBody]
+    ///
+    /// # Arguments
+    /// * `offset` - Distance to move in each axis, in world space
+    fn translate(&mut self, _offset: &Point3) {}
 }
@@ -1,54 +1,154 @@
-use crate::FerruxViewport;
 use crate::actors::actor::Drawable;
 use crate::actors::Actor;
-use crate::engine::EngineCamera;
-use crate::geometry::Mesh;
-use crate::geometry::Projectable;
+use crate::collision::{Collider, ColliderShape};
+use crate::geometry::{Aabb, BoundingSphere, Mesh, Ray};
+use crate::geometry::vector::Point3;
 use crate::geometry::Rotation;
 use crate::math::builders::{RotationAxis, RotationMatrixBuilder};
-use crate::geometry::vector::ops::{Dot, Normalizable};
+use crate::geometry::Winding;
+use crate::lighting::Material;
+use crate::math::Float;
+use crate::physics::RigidBody;
+use crate::render::{CullMode, DrawMode, DrawOptions, Rasterizer, RenderContext, RenderPipeline, Shading};
 
-/// Implementation of an actor with a mesh
-pub struct MeshActor {
-    mesh: Mesh,
+/// Implementation of an actor with a mesh, of `f32` or `f64` precision
+pub struct MeshActor<T: Float = f32> {
+    mesh: Mesh<T>,
+    /// Bounds of the mesh in `f32`, updated when the mesh moves
+    aabb: Aabb,
+    bounding_sphere: BoundingSphere,
+    options: DrawOptions,
+    fixed_mode: bool,
+    material: Material,
+    pipeline: RenderPipeline,
+    collider: Option<ColliderShape>,
+    body: Option<RigidBody>,
 }
 
-impl MeshActor {
-    /// Creates a new actor with the given mesh
+impl<T: Float> MeshActor<T> {
+    /// Creates a new actor with the given mesh, drawn with the global [DrawMode]
     ///
     /// # Arguments
     /// * `mesh` - Mesh of the actor
     ///
-    pub fn new(mesh: Mesh) -> Self {
-        Self { mesh }
+    pub fn new(mesh: Mesh<T>) -> Self {
+        let (options, material): (DrawOptions, Material) = Default::default();
+        Self {
+            aabb: mesh.aabb().cast(),
+            bounding_sphere: mesh.bounding_sphere().cast(),
+            mesh,
+            options,
+            fixed_mode: false,
+            pipeline: RenderPipeline::new(&options, material.clone()),
+            material,
+            collider: None,
+            body: None,
+        }
+    }
+
+    /// Sets a [DrawMode] for this actor, ignoring the changes of the global one
+    ///
+    /// # Arguments
+    /// * `mode` - Mode to draw the actor
+    ///
+    pub fn with_draw_mode(mut self, mode: DrawMode) -> Self {
+        self.options.mode = mode;
+        self.fixed_mode = true;
+        self.rebuild_pipeline();
+        self
+    }
+
+    /// Sets the faces of the mesh to discard. By default, the back ones.
+    pub fn with_cull_mode(mut self, cull_mode: CullMode) -> Self {
+        self.options.cull_mode = cull_mode;
+        self.rebuild_pipeline();
+        self
+    }
+
+    /// Sets the vertex order of the front faces of the mesh. By default, clockwise.
+    ///
+    /// The vertex normals computed for meshes without them in the file assume clockwise order,
+    /// call [Mesh::compute_normals] with the winding to fix them.
+    pub fn with_winding(mut self, winding: Winding) -> Self {
+        self.options.winding = winding;
+        self.rebuild_pipeline();
+        self
+    }
+
+    /// Sets the way to light the faces of the mesh. By default, flat.
+    pub fn with_shading(mut self, shading: Shading) -> Self {
+        self.options.shading = shading;
+        self.rebuild_pipeline();
+        self
+    }
+
+    /// Sets the material of the mesh. Its diffuse map is only drawn if the mesh has texture
+    /// coordinates.
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self.rebuild_pipeline();
+        self
+    }
+
+    /// Makes the actor collide with the others through the given volume of its mesh. By default,
+    /// it doesn't collide.
+    pub fn with_collider(mut self, shape: ColliderShape) -> Self {
+        self.collider = Some(shape);
+        self
+    }
+
+    /// Moves the actor with the given body in the fixed step of the physics, instead of spinning
+    /// it around the origin
+    pub fn with_rigid_body(mut self, body: RigidBody) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Returns the mesh of the actor
+    pub fn mesh(&self) -> &Mesh<T> {
+        &self.mesh
+    }
+
+    fn rebuild_pipeline(&mut self) {
+        self.pipeline = RenderPipeline::new(&self.options, self.material.clone());
+    }
+
+    fn update_bounds(&mut self) {
+        self.aabb = self.mesh.aabb().cast();
+        self.bounding_sphere = self.mesh.bounding_sphere().cast();
     }
 }
 
-impl Drawable for MeshActor {
-    fn draw(&self, viewport: &mut FerruxViewport, camera: &EngineCamera) {
-        let offset = camera.offset();
-        let light = camera.light().normal();
-		
-        for triangle in &self.mesh.triangles {
-            let plain = triangle.plain_component().apply_offset(offset);
-            let normal = triangle.normal();
-            if normal.dot(&(&plain - camera.position())) < 0.0 {
-                let brightness = (light.dot(&normal) * (u8::MAX as f32)) as u8;
-                let color = [brightness, brightness, brightness, u8::MAX];
-
-                let projection = triangle.get_projection(camera.projection_matrix(), offset);
-				viewport.fill_triangle(
-				(projection.0.x, projection.0.y, projection.0.z),
-				(projection.1.x, projection.1.y, projection.1.z),
-				(projection.2.x, projection.2.y, projection.2.z), 
-					&color);
-            }
-        }
+impl<T: Float> Drawable for MeshActor<T> {
+    fn draw(&self, rasterizer: &mut Rasterizer, context: &RenderContext) {
+        self.pipeline.draw(rasterizer, &self.mesh, context);
+    }
+
+    fn cast_shadows(&self, casters: &mut Vec<[Point3; 3]>, context: &RenderContext) {
+        let offset = context.camera.offset();
+        casters.extend(self.mesh.triangles().map(|triangle| {
+            [triangle.0, triangle.1, triangle.2].map(|point| point.cast().apply_offset(offset))
+        }));
+    }
+
+    fn aabb(&self) -> Option<&Aabb> {
+        Some(&self.aabb)
+    }
+
+    fn bounding_sphere(&self) -> Option<&BoundingSphere> {
+        Some(&self.bounding_sphere)
+    }
+
+    fn intersect(&self, ray: &Ray) -> Option<(usize, f32)> {
+        ray.cast().intersect_mesh(&self.mesh).map(|(index, distance)| (index, distance.to_f32()))
     }
 }
 
-impl Actor for MeshActor {
+impl<T: Float> Actor for MeshActor<T> {
     fn update(&mut self, delta: u128) {
+        if self.body.is_some() {
+            return;
+        }
         let matrix_x = RotationMatrixBuilder::new()
             .in_axis(RotationAxis::X)
             .with_speed(0.005)
@@ -64,5 +164,26 @@ impl Actor for MeshActor {
 
 		self.mesh.rotate(&matrix_x);
 		self.mesh.rotate(&matrix_z);
+		self.update_bounds();
+    }
+
+    fn set_draw_mode(&mut self, mode: DrawMode) {
+        if !self.fixed_mode {
+            self.options.mode = mode;
+            self.rebuild_pipeline();
+        }
+    }
+
+    fn collider(&self) -> Option<Collider<'_>> {
+        self.collider.map(|shape| Collider::of_mesh(&self.mesh, shape))
+    }
+
+    fn rigid_body(&mut self) -> Option<&mut RigidBody> {
+        self.body.as_mut()
+    }
+
+    fn translate(&mut self, offset: &Point3) {
+        self.mesh.translate(&offset.cast());
+        self.update_bounds();
     }
 }
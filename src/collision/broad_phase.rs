@@ -0,0 +1,51 @@
+use crate::geometry::Aabb;
+
+/// Returns the pairs of boxes that overlap, as their positions in the list with the lowest first.
+/// The boxes are sorted along the X-axis and only the ones sharing part of it are compared.
+///
+/// # Arguments
+/// * `aabbs` - Boxes to test
+///
+pub fn sweep_and_prune(aabbs: &[Aabb]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..aabbs.len()).collect();
+    order.sort_by(|a, b| aabbs[*a].min.x.total_cmp(&aabbs[*b].min.x));
+
+    let mut pairs = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    for i in order {
+        active.retain(|j| aabbs[*j].max.x >= aabbs[i].min.x);
+        for j in &active {
+            if aabbs[i].intersects(&aabbs[*j]) {
+                pairs.push((i.min(*j), i.max(*j)));
+            }
+        }
+        active.push(i);
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sweep_and_prune;
+    use crate::geometry::vector::Point3;
+    use crate::geometry::Aabb;
+
+    fn aabb(x: f32, y: f32, size: f32) -> Aabb {
+        Aabb { min: Point3 { x, y, z: 0.0 }, max: Point3 { x: x + size, y: y + size, z: size } }
+    }
+
+    #[test]
+    fn overlapping_pairs() {
+        let aabbs = [
+            aabb(0.0, 0.0, 2.0),
+            aabb(5.0, 0.0, 1.0),
+            aabb(1.0, 1.0, 2.0),
+            // Shares the X range with the first one, but not the Y one
+            aabb(0.5, 5.0, 1.0),
+            aabb(-3.0, 0.0, 10.0),
+        ];
+        assert_eq!(sweep_and_prune(&aabbs), vec![(0, 2), (0, 4), (1, 4), (2, 4), (3, 4)]);
+        assert!(sweep_and_prune(&aabbs[..2]).is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Debug;
+
+use crate::geometry::triangle::Triangle;
+use crate::geometry::vector::Point3;
+use crate::geometry::{Aabb, BoundingSphere, Mesh, Obb};
+use crate::math::Float;
+
+/// Kind of volume used to find the collisions of an actor, from the cheapest to the most precise
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColliderShape {
+    /// Sphere enclosing the mesh
+    Sphere,
+    /// Box enclosing the mesh, aligned with the world axes
+    #[default]
+    Aabb,
+    /// Box enclosing the mesh, turned with it
+    Obb,
+    /// Triangles of the mesh
+    Mesh,
+}
+
+/// Volume of an actor in world space, tested against the others to find their contacts
+#[derive(Clone, Debug)]
+pub enum Collider<'a> {
+    Sphere(BoundingSphere),
+    Aabb(Aabb),
+    Obb(Obb),
+    Mesh(&'a dyn MeshShape),
+}
+
+/// Triangles of a mesh of any precision, tested in `f32` like the rest of the colliders
+pub trait MeshShape: Debug {
+    /// Returns the axis-aligned box enclosing the mesh
+    fn aabb(&self) -> Aabb;
+
+    /// Returns the triangles of the mesh
+    fn triangles(&self) -> Box<dyn Iterator<Item = Triangle> + '_>;
+}
+
+impl<T: Float> MeshShape for Mesh<T> {
+    fn aabb(&self) -> Aabb {
+        Mesh::aabb(self).cast()
+    }
+
+    fn triangles(&self) -> Box<dyn Iterator<Item = Triangle> + '_> {
+        Box::new(Mesh::triangles(self).map(|triangle| triangle.cast()))
+    }
+}
+
+impl<'a> Collider<'a> {
+    /// Returns the collider of the mesh with the given shape
+    ///
+    /// # Arguments
+    /// * `mesh` - Mesh to enclose
+    /// * `shape` - Kind of volume of the collider
+    ///
+    pub fn of_mesh<T: Float>(mesh: &'a Mesh<T>, shape: ColliderShape) -> Self {
+        match shape {
+            ColliderShape::Sphere => Self::Sphere(mesh.bounding_sphere().cast()),
+            ColliderShape::Aabb => Self::Aabb(mesh.aabb().cast()),
+            ColliderShape::Obb => Self::Obb(mesh.obb().cast()),
+            ColliderShape::Mesh => Self::Mesh(mesh),
+        }
+    }
+
+    /// Returns the axis-aligned box enclosing the collider
+    pub fn aabb(&self) -> Aabb {
+        match self {
+            Self::Sphere(sphere) => {
+                let (center, radius) = (&sphere.center, sphere.radius);
+                Aabb {
+                    min: Point3 { x: center.x - radius, y: center.y - radius, z: center.z - radius },
+                    max: Point3 { x: center.x + radius, y: center.y + radius, z: center.z + radius },
+                }
+            }
+            Self::Aabb(aabb) => aabb.clone(),
+            Self::Obb(obb) => Aabb::from_points(obb.corners()),
+            Self::Mesh(mesh) => mesh.aabb(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::environment::ActorId;
+use crate::geometry::vector::Point3;
+
+/// Collision between two actors, as seen by the first one
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contact {
+    /// Actor receiving the contact
+    pub actor: ActorId,
+    /// Actor colliding with it
+    pub other: ActorId,
+    /// Point where the actors touch, in world space
+    pub point: Point3,
+    /// Normalized direction from the actor to the other one
+    pub normal: Point3,
+    /// Distance to move the actors apart along the normal to separate them
+    pub depth: f32,
+}
+
+impl Contact {
+    /// Returns the same contact as seen by the other actor
+    pub fn flipped(&self) -> Self {
+        Self {
+            actor: self.other,
+            other: self.actor,
+            point: self.point.clone(),
+            normal: -&self.normal,
+            depth: self.depth,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::collision::{collide, sweep_and_prune, Collider, Contact};
+use crate::environment::ActorId;
+use crate::geometry::Aabb;
+
+/// Returns the contacts between the colliders, seen by the actor with the lowest identifier of
+/// each pair. The pairs whose boxes overlap are found first and then tested with their shapes.
+///
+/// # Arguments
+/// * `colliders` - Actors and their colliders in world space
+///
+pub fn detect_contacts(colliders: &[(ActorId, Collider)]) -> Vec<Contact> {
+    let aabbs: Vec<Aabb> = colliders.iter().map(|(_, collider)| collider.aabb()).collect();
+    sweep_and_prune(&aabbs).into_iter()
+        .filter_map(|(i, j)| {
+            let ((a, first), (b, second)) = (&colliders[i], &colliders[j]);
+            let intersection = collide(first, second)?;
+            let contact = Contact {
+                actor: *a,
+                other: *b,
+                point: intersection.point,
+                normal: intersection.normal,
+                depth: intersection.depth,
+            };
+            Some(if a < b { contact } else { contact.flipped() })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_contacts;
+    use crate::collision::Collider;
+    use crate::geometry::vector::Point3;
+    use crate::geometry::BoundingSphere;
+
+    fn sphere(x: f32) -> Collider<'static> {
+        Collider::Sphere(BoundingSphere { center: Point3 { x, y: 0.0, z: 0.0 }, radius: 1.0 })
+    }
+
+    #[test]
+    fn contacts_between_actors() {
+        let colliders = [(7, sphere(1.5)), (3, sphere(0.0)), (9, sphere(10.0))];
+        let contacts = detect_contacts(&colliders);
+        assert_eq!(contacts.len(), 1);
+        let contact = &contacts[0];
+        assert_eq!((contact.actor, contact.other), (3, 7));
+        assert_eq!(contact.normal, Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(contact.depth, 0.5);
+        assert_eq!(contact.point, Point3 { x: 0.75, y: 0.0, z: 0.0 });
+    }
+}
@@ -0,0 +1,11 @@
+pub use broad_phase::sweep_and_prune;
+pub use collider::{Collider, ColliderShape, MeshShape};
+pub use contact::Contact;
+pub use detection::detect_contacts;
+pub use narrow_phase::{collide, Intersection};
+
+mod broad_phase;
+mod collider;
+mod contact;
+mod detection;
+mod narrow_phase;
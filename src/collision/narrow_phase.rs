@@ -0,0 +1,328 @@
+use crate::collision::{Collider, MeshShape};
+use crate::geometry::triangle::Triangle;
+use crate::geometry::vector::ops::{Cross, Dot, Module};
+use crate::geometry::vector::Point3;
+use crate::geometry::{Aabb, BoundingSphere, Obb};
+
+/// Distance under which two points or projections are considered the same
+const EPSILON: f32 = 1e-5;
+
+/// Overlap between two colliders
+#[derive(Clone, Debug, PartialEq)]
+pub struct Intersection {
+    /// Point where the colliders touch, in the middle of the overlap
+    pub point: Point3,
+    /// Normalized direction from the first collider to the second one
+    pub normal: Point3,
+    /// Distance to move the colliders apart along the normal to separate them
+    pub depth: f32,
+}
+
+impl Intersection {
+    fn flipped(self) -> Self {
+        Self { normal: -self.normal, ..self }
+    }
+}
+
+/// Returns the overlap between two colliders, if they touch
+///
+/// # Arguments
+/// * `a` - First collider, the normal of the intersection goes out of it
+/// * `b` - Second collider
+///
+pub fn collide(a: &Collider, b: &Collider) -> Option<Intersection> {
+    match (Shape::of(a), Shape::of(b)) {
+        (Shape::Sphere(a), Shape::Sphere(b)) => spheres(a, b),
+        (Shape::Sphere(sphere), Shape::Box(obb)) => sphere_box(sphere, &obb),
+        (Shape::Box(obb), Shape::Sphere(sphere)) => sphere_box(sphere, &obb).map(Intersection::flipped),
+        (Shape::Sphere(sphere), Shape::Mesh(mesh)) => sphere_mesh(sphere, mesh),
+        (Shape::Mesh(mesh), Shape::Sphere(sphere)) => sphere_mesh(sphere, mesh).map(Intersection::flipped),
+        (Shape::Box(a), Shape::Box(b)) => separating_axes(&Convex::of_box(&a), &Convex::of_box(&b)),
+        (Shape::Box(obb), Shape::Mesh(mesh)) => box_mesh(&obb, mesh),
+        (Shape::Mesh(mesh), Shape::Box(obb)) => box_mesh(&obb, mesh).map(Intersection::flipped),
+        (Shape::Mesh(a), Shape::Mesh(b)) => meshes(a, b),
+    }
+}
+
+/// Colliders grouped by the test they need, the boxes aligned with the axes are just oriented ones
+enum Shape<'a> {
+    Sphere(&'a BoundingSphere),
+    Box(Obb),
+    Mesh(&'a dyn MeshShape),
+}
+
+impl<'a> Shape<'a> {
+    fn of(collider: &'a Collider) -> Self {
+        match collider {
+            Collider::Sphere(sphere) => Self::Sphere(sphere),
+            Collider::Aabb(aabb) => Self::Box(Obb::from(aabb)),
+            Collider::Obb(obb) => Self::Box(obb.clone()),
+            Collider::Mesh(mesh) => Self::Mesh(*mesh),
+        }
+    }
+}
+
+fn spheres(a: &BoundingSphere, b: &BoundingSphere) -> Option<Intersection> {
+    let offset = &b.center - &a.center;
+    let distance = (&offset).module();
+    let depth = a.radius + b.radius - distance;
+    if depth < 0.0 {
+        return None;
+    }
+    // Concentric spheres are pushed apart upwards
+    let normal = if distance > EPSILON {
+        offset / distance
+    } else {
+        Point3 { x: 0.0, y: 1.0, z: 0.0 }
+    };
+    Some(Intersection {
+        point: &a.center + &normal * (a.radius - depth * 0.5),
+        normal,
+        depth,
+    })
+}
+
+fn sphere_box(sphere: &BoundingSphere, obb: &Obb) -> Option<Intersection> {
+    let closest = obb.closest_point(&sphere.center);
+    if let Some(intersection) = sphere_point(sphere, closest) {
+        return intersection;
+    }
+    // The center is inside the box, so the sphere leaves it through the nearest face
+    let offset = &sphere.center - &obb.center;
+    let (normal, depth) = obb.axes.iter().zip(obb.half_extents)
+        .map(|(axis, half_extent)| {
+            let distance = (&offset).dot(axis);
+            (axis * -distance.signum(), half_extent - distance.abs())
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    Some(Intersection { point: sphere.center.clone(), normal, depth: sphere.radius + depth })
+}
+
+fn sphere_mesh(sphere: &BoundingSphere, mesh: &dyn MeshShape) -> Option<Intersection> {
+    deepest(mesh.triangles().filter_map(|triangle| {
+        let closest = triangle.closest_point(&sphere.center);
+        sphere_point(sphere, closest).unwrap_or_else(|| {
+            // The center is on the triangle, so the sphere leaves it through either side
+            Some(Intersection { point: sphere.center.clone(), normal: triangle.normal(), depth: sphere.radius })
+        })
+    }))
+}
+
+/// Returns the intersection of a sphere with the closest point of a shape to its center, or
+/// [None] if the center is the point itself and the normal can't be found from it
+fn sphere_point(sphere: &BoundingSphere, closest: Point3) -> Option<Option<Intersection>> {
+    let offset = &closest - &sphere.center;
+    let distance = (&offset).module();
+    if distance > sphere.radius {
+        return Some(None);
+    }
+    if distance < EPSILON {
+        return None;
+    }
+    Some(Some(Intersection {
+        normal: offset / distance,
+        depth: sphere.radius - distance,
+        point: closest,
+    }))
+}
+
+fn box_mesh(obb: &Obb, mesh: &dyn MeshShape) -> Option<Intersection> {
+    let aabb = Aabb::from_points(obb.corners());
+    let convex = Convex::of_box(obb);
+    deepest(mesh.triangles()
+        .filter_map(Convex::of_triangle)
+        .filter(|triangle| aabb.intersects(&Aabb::from_points(&triangle.points)))
+        .filter_map(|triangle| separating_axes(&convex, &triangle)))
+}
+
+fn meshes(a: &dyn MeshShape, b: &dyn MeshShape) -> Option<Intersection> {
+    let triangles: Vec<(Aabb, Convex)> = b.triangles()
+        .filter_map(Convex::of_triangle)
+        .map(|triangle| (Aabb::from_points(&triangle.points), triangle))
+        .filter(|(aabb, _)| aabb.intersects(&a.aabb()))
+        .collect();
+    deepest(a.triangles()
+        .filter_map(Convex::of_triangle)
+        .flat_map(|first| {
+            let aabb = Aabb::from_points(&first.points);
+            triangles.iter()
+                .filter(move |(other, _)| other.intersects(&aabb))
+                .filter_map(move |(_, second)| separating_axes(&first, second))
+        }))
+}
+
+fn deepest(intersections: impl Iterator<Item = Intersection>) -> Option<Intersection> {
+    intersections.max_by(|a, b| a.depth.total_cmp(&b.depth))
+}
+
+/// Convex shape described by its corners, the normals of its faces and the directions of its edges
+struct Convex {
+    points: Vec<Point3>,
+    axes: Vec<Point3>,
+    edges: Vec<Point3>,
+}
+
+impl Convex {
+    fn of_box(obb: &Obb) -> Self {
+        Self { points: obb.corners().to_vec(), axes: obb.axes.to_vec(), edges: obb.axes.to_vec() }
+    }
+
+    /// Returns the shape of the triangle, or [None] if it has no area
+    fn of_triangle(triangle: Triangle) -> Option<Self> {
+        let points = vec![triangle.0, triangle.1, triangle.2];
+        let edges = vec![&points[1] - &points[0], &points[2] - &points[1], &points[0] - &points[2]];
+        let normal = (&edges[0]).cross(&edges[1]);
+        if (&normal).module() < EPSILON {
+            return None;
+        }
+        let mut axes: Vec<Point3> = edges.iter().map(|edge| (&normal).cross(edge)).collect();
+        axes.push(normal);
+        Some(Self { points, axes, edges })
+    }
+
+    fn project(&self, axis: &Point3) -> (f32, f32) {
+        self.points.iter()
+            .map(|point| point.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(min, max), distance| (min.min(distance), max.max(distance)))
+    }
+}
+
+/// Returns the overlap of two convex shapes by testing the axes that could separate them: the
+/// normals of their faces and the crosses of their edges. The axis with the least overlap is
+/// the normal of the intersection.
+fn separating_axes(a: &Convex, b: &Convex) -> Option<Intersection> {
+    let crosses = a.edges.iter().flat_map(|first| b.edges.iter().map(move |second| first.cross(second)));
+    let axes = a.axes.iter().chain(&b.axes).cloned().chain(crosses);
+
+    let mut best: Option<(Point3, f32)> = None;
+    for axis in axes {
+        let length = (&axis).module();
+        if length < EPSILON {
+            continue;
+        }
+        let axis = axis / length;
+        let ((a_min, a_max), (b_min, b_max)) = (a.project(&axis), b.project(&axis));
+        // Distances to push the second shape forwards or backwards along the axis to separate them
+        let (forwards, backwards) = (a_max - b_min, b_max - a_min);
+        if forwards < 0.0 || backwards < 0.0 {
+            return None;
+        }
+        let (axis, overlap) = if forwards <= backwards { (axis, forwards) } else { (-axis, backwards) };
+        if best.as_ref().is_none_or(|(_, depth)| overlap < *depth) {
+            best = Some((axis, overlap));
+        }
+    }
+
+    let (normal, depth) = best?;
+    // Middle of the overlap over the deepest points of the second shape
+    let (support, _) = b.project(&normal);
+    let deepest: Vec<&Point3> = b.points.iter()
+        .filter(|point| point.dot(&normal) - support < EPSILON * 10.0)
+        .collect();
+    let sum = deepest.iter().fold(Point3::default(), |sum, point| sum + *point);
+    let point = sum / deepest.len() as f32 + &normal * (depth * 0.5);
+    Some(Intersection { point, normal, depth })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collide;
+    use crate::collision::{Collider, ColliderShape};
+    use crate::geometry::vector::Point3;
+    use crate::geometry::{Aabb, BoundingSphere, Mesh, Obb};
+
+    fn point(x: f32, y: f32, z: f32) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    fn sphere(x: f32, y: f32, z: f32, radius: f32) -> Collider<'static> {
+        Collider::Sphere(BoundingSphere { center: point(x, y, z), radius })
+    }
+
+    fn cube(x: f32, y: f32, z: f32) -> Collider<'static> {
+        Collider::Aabb(Aabb { min: point(x - 1.0, y - 1.0, z - 1.0), max: point(x + 1.0, y + 1.0, z + 1.0) })
+    }
+
+    fn assert_close(a: &Point3, b: &Point3) {
+        assert!((a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4 && (a.z - b.z).abs() < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn sphere_sphere() {
+        let intersection = collide(&sphere(0.0, 0.0, 0.0, 1.0), &sphere(0.0, 1.5, 0.0, 1.0)).unwrap();
+        assert_close(&intersection.normal, &point(0.0, 1.0, 0.0));
+        assert_close(&intersection.point, &point(0.0, 0.75, 0.0));
+        assert_eq!(intersection.depth, 0.5);
+        assert!(collide(&sphere(0.0, 0.0, 0.0, 1.0), &sphere(0.0, 2.5, 0.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn sphere_box() {
+        let intersection = collide(&sphere(1.5, 0.0, 0.0, 1.0), &cube(0.0, 0.0, 0.0)).unwrap();
+        assert_close(&intersection.normal, &point(-1.0, 0.0, 0.0));
+        assert_close(&intersection.point, &point(1.0, 0.0, 0.0));
+        assert!((intersection.depth - 0.5).abs() < 1e-5);
+
+        // Center inside the box
+        let intersection = collide(&cube(0.0, 0.0, 0.0), &sphere(0.0, 0.0, 0.75, 0.5)).unwrap();
+        assert_close(&intersection.normal, &point(0.0, 0.0, 1.0));
+        assert!((intersection.depth - 0.75).abs() < 1e-5);
+
+        assert!(collide(&sphere(2.0, 2.0, 0.0, 1.0), &cube(0.0, 0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn box_box() {
+        let intersection = collide(&cube(0.0, 0.0, 0.0), &cube(1.5, 0.5, 0.0)).unwrap();
+        assert_close(&intersection.normal, &point(1.0, 0.0, 0.0));
+        assert!((intersection.depth - 0.5).abs() < 1e-5);
+        assert!((intersection.point.x - 0.75).abs() < 1e-5);
+        assert!(collide(&cube(0.0, 0.0, 0.0), &cube(2.5, 0.0, 0.0)).is_none());
+
+        // A box turned 45 degrees around Z only touches the other with its edge
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let turned = Collider::Obb(Obb {
+            center: point(2.3, 0.0, 0.0),
+            axes: [point(half, half, 0.0), point(-half, half, 0.0), point(0.0, 0.0, 1.0)],
+            half_extents: [1.0, 1.0, 1.0],
+        });
+        let intersection = collide(&cube(0.0, 0.0, 0.0), &turned).unwrap();
+        assert_close(&intersection.normal, &point(1.0, 0.0, 0.0));
+        assert!((intersection.depth - (1.0 + 2.0_f32.sqrt() - 2.3)).abs() < 1e-4);
+        let turned = Collider::Obb(Obb { center: point(2.5, 0.0, 0.0), ..match turned {
+            Collider::Obb(obb) => obb,
+            _ => unreachable!(),
+        } });
+        assert!(collide(&cube(0.0, 0.0, 0.0), &turned).is_none());
+    }
+
+    #[test]
+    fn mesh_collisions() {
+        let mesh = Mesh::try_from("v -2 0 -2\nv 2 0 -2\nv 2 0 2\nv -2 0 2\nf 1 2 3\nf 1 3 4".to_string()).unwrap();
+        let floor = Collider::Mesh(&mesh);
+
+        let intersection = collide(&sphere(0.5, 0.5, 0.5, 1.0), &floor).unwrap();
+        assert_close(&intersection.normal, &point(0.0, -1.0, 0.0));
+        assert_close(&intersection.point, &point(0.5, 0.0, 0.5));
+        assert!((intersection.depth - 0.5).abs() < 1e-5);
+        assert!(collide(&floor, &sphere(0.0, 1.5, 0.0, 1.0)).is_none());
+
+        let intersection = collide(&cube(1.0, 0.75, 0.0), &floor).unwrap();
+        assert_close(&intersection.normal, &point(0.0, -1.0, 0.0));
+        assert!((intersection.depth - 0.25).abs() < 1e-5, "{intersection:?}");
+        assert!(collide(&cube(1.0, 1.25, 0.0), &floor).is_none());
+
+        // A wall standing on the floor
+        let wall = Mesh::try_from("v 1 -1 -1\nv 1 1 -1\nv 1 1 1\nf 1 2 3".to_string()).unwrap();
+        let intersection = collide(&Collider::Mesh(&wall), &floor).unwrap();
+        assert!((intersection.depth - 1.0).abs() < 1e-5);
+        let far_wall = Mesh::try_from("v 1 1 -1\nv 1 3 -1\nv 1 3 1\nf 1 2 3".to_string()).unwrap();
+        assert!(collide(&Collider::Mesh(&far_wall), &floor).is_none());
+
+        // Meshes of other precisions collide through their triangles too
+        let precise = Mesh::<f64>::parse("v 1 -1 -1\nv 1 1 -1\nv 1 1 1\nf 1 2 3").unwrap();
+        let precise = Collider::of_mesh(&precise, ColliderShape::Mesh);
+        assert!(matches!(precise, Collider::Mesh(_)));
+        assert_eq!(collide(&precise, &floor), Some(intersection));
+    }
+}
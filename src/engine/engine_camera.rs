@@ -1,13 +1,24 @@
 use crate::engine::EngineConfig;
 use crate::geometry::vector::Point3;
-use crate::math::Matrix4;
+use crate::geometry::Ray;
+use crate::math::builders::{RotationAxis, RotationMatrixBuilder};
+use crate::math::{Float, Matrix4};
 use ferrux_projection_matrix::ProjectionMatrixBuilder;
 
 pub struct EngineCamera {
-	position: Point3,
+	/// Position kept in `f64`, so the camera can follow meshes far from the origin
+	position: Point3<f64>,
+	yaw: f32,
+	pitch: f32,
+	/// View matrix in `f64`, for the meshes of any precision
+	precise_view_matrix: Matrix4<f64>,
+	/// View matrix narrowed to `f32`
+	view_matrix: Matrix4,
 	projection_matrix: Matrix4,
 	z_offset: f32,
-	light: Point3
+	near: f32,
+	far: f32,
+	screen_size: (u32, u32),
 }
 
 impl EngineCamera {
@@ -22,30 +33,177 @@ impl EngineCamera {
 
 		Self {
 			position: Point3 { x: 0.0, y: 0.0, z: 0.0},
+			yaw: 0.0,
+			pitch: 0.0,
+			precise_view_matrix: Matrix4::identity(),
+			view_matrix: Matrix4::identity(),
 			projection_matrix: Matrix4::new(matrix),
 			z_offset: config.z_offset,
-			light: config.light_direction.clone(),
+			near: config.screen_position,
+			far: config.view_limit,
+			screen_size: (config.width, config.height),
 		}
 	}
 
 	/// Returns the current camera position
-	pub fn position(&self) -> &Point3 {
+	pub fn position(&self) -> Point3 {
+		self.position.cast()
+	}
+
+	/// Returns the current camera position in `f64`
+	pub fn precise_position(&self) -> &Point3<f64> {
 		&self.position
 	}
 
+	/// Moves the camera to the given position, of any precision
+	pub fn set_position<T: Float>(&mut self, position: Point3<T>) {
+		self.position = position.cast();
+		self.update_view_matrix();
+	}
+
+	/// Orients the camera
+	///
+	/// # Arguments
+	/// * `yaw` - Rotation around the Y-axis, in radians
+	/// * `pitch` - Rotation around the X-axis, in radians
+	///
+	pub fn set_rotation(&mut self, yaw: f32, pitch: f32) {
+		self.yaw = yaw;
+		self.pitch = pitch;
+		self.update_view_matrix();
+	}
+
 	/// Returns the offset in the Z-axis
 	pub fn offset(&self) -> f32 {
 		self.z_offset
 	}
 
+	/// Returns the distance to the nearest plane drawn
+	pub fn near(&self) -> f32 {
+		self.near
+	}
+
+	/// Returns the distance to the farthest plane drawn
+	pub fn far(&self) -> f32 {
+		self.far
+	}
+
+	/// Returns the matrix transforming the world coordinates to the camera space
+	pub fn view_matrix(&self) -> &Matrix4 {
+		&self.view_matrix
+	}
+
+	/// Returns the matrix transforming the world coordinates to the camera space in `f64`. Far
+	/// from the origin, the translation doesn't fit in `f32` without losing detail.
+	pub fn precise_view_matrix(&self) -> &Matrix4<f64> {
+		&self.precise_view_matrix
+	}
+
 	/// Returns the projection matrix of the current rendering
 	pub fn projection_matrix(&self) -> &Matrix4 {
 		&self.projection_matrix
 	}
 
-	/// Returns the light vector
-	pub fn light(&self) -> &Point3 {
-		&self.light
+	/// Returns the direction in world space of the ray going from the camera through a point of
+	/// the screen. It isn't normalized, its length in the view direction is one.
+	///
+	/// # Arguments
+	/// * `x`, `y` - Coordinates of the point in pixels
+	///
+	pub fn ray_direction(&self, x: f32, y: f32) -> Point3 {
+		let (width, height) = self.screen_size;
+		let projection = &self.projection_matrix;
+		let ndc_x = 2.0 * x / width.max(1) as f32 - 1.0;
+		let ndc_y = 2.0 * y / height.max(1) as f32 - 1.0;
+		let view = Point3 {
+			x: ndc_x * projection[2][3] / projection[0][0],
+			y: ndc_y * projection[2][3] / projection[1][1],
+			z: 1.0,
+		};
+		let pitch = RotationMatrixBuilder::new()
+			.in_axis(RotationAxis::X)
+			.with_theta(self.pitch)
+			.build();
+		let yaw = RotationMatrixBuilder::new()
+			.in_axis(RotationAxis::Y)
+			.with_theta(self.yaw)
+			.build();
+		view.transform(&(&pitch * &yaw))
+	}
+
+	/// Returns the ray in world space going from the camera through a pixel of the window
+	///
+	/// # Arguments
+	/// * `x`, `y` - Coordinates of the pixel, from the upper-left corner of the window
+	///
+	pub fn screen_ray(&self, x: f32, y: f32) -> Ray {
+		let position = self.position();
+		let origin = Point3 { x: position.x, y: position.y, z: position.z - self.z_offset };
+		Ray::new(origin, &self.ray_direction(x, y))
 	}
 
-}
\ No newline at end of file
+	/// Returns the width and height of the screen in pixels
+	pub fn screen_size(&self) -> (u32, u32) {
+		self.screen_size
+	}
+
+	/// Updates the size of the screen
+	pub(crate) fn set_screen_size(&mut self, width: u32, height: u32) {
+		self.screen_size = (width, height);
+	}
+
+	/// Rebuilds the view matrices, the inverse of the camera position and rotation
+	fn update_view_matrix(&mut self) {
+		let translation = Matrix4::translation(-self.position.x, -self.position.y, -self.position.z);
+		let yaw = RotationMatrixBuilder::new()
+			.in_axis(RotationAxis::Y)
+			.with_theta(-self.yaw)
+			.build();
+		let pitch = RotationMatrixBuilder::new()
+			.in_axis(RotationAxis::X)
+			.with_theta(-self.pitch)
+			.build();
+		self.precise_view_matrix = &translation * &(&yaw * &pitch).cast();
+		self.view_matrix = self.precise_view_matrix.cast();
+	}
+
+}
+
+#[cfg(test)]
+mod tests {
+	use super::EngineCamera;
+	use crate::engine::EngineConfig;
+	use crate::geometry::vector::Point3;
+	use crate::geometry::Projectable;
+
+	fn assert_close(point: &Point3, expected: &Point3) {
+		assert!((point.x - expected.x).abs() < 1e-4, "{:?} != {:?}", point, expected);
+		assert!((point.y - expected.y).abs() < 1e-4, "{:?} != {:?}", point, expected);
+		assert!((point.z - expected.z).abs() < 1e-4, "{:?} != {:?}", point, expected);
+	}
+
+	#[test]
+	fn ray_direction_projects_back() {
+		let config = EngineConfig::default().with_width(64).with_height(32);
+		let mut camera = EngineCamera::new(&config);
+		camera.set_rotation(0.3, -0.2);
+		let direction = camera.ray_direction(10.0, 20.0);
+
+		// A point along the ray projects to the same pixel
+		let point = Point3 { x: direction.x * 5.0, y: direction.y * 5.0, z: direction.z * 5.0 };
+		let view = point.transform(camera.view_matrix());
+		let projected = view.get_projection(camera.projection_matrix(), 0.0);
+		assert_close(
+			&Point3 { x: (projected.x + 1.0) * 32.0, y: (projected.y + 1.0) * 16.0, z: 0.0 },
+			&Point3 { x: 10.0, y: 20.0, z: 0.0 },
+		);
+	}
+
+	#[test]
+	fn ray_direction_at_center() {
+		let camera = EngineCamera::new(&EngineConfig::default());
+		let (width, height) = camera.screen_size();
+		let direction = camera.ray_direction(width as f32 / 2.0, height as f32 / 2.0);
+		assert_close(&direction, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+	}
+}
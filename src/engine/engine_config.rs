@@ -1,4 +1,8 @@
 use crate::geometry::vector::Point3;
+use crate::geometry::MeshProcessing;
+use crate::post_process::Fog;
+use crate::render::{AntiAliasing, Background, DrawMode};
+use crate::texture::Filter;
 
 const DEFAULT_TITLE: &str = "Rust 3D Engine";
 const DEFAULT_WIDTH: u32 = 960;
@@ -7,8 +11,13 @@ const DEFAULT_FOV: f32 = 90.0;
 const DEFAULT_SCREEN_POSITION: f32 = 0.1;
 const DEFAULT_VIEW_LIMIT: f32 = 1000.0;
 const DEFAULT_Z_OFFSET: f32 = 3.0;
+const DEFAULT_AMBIENT_LIGHT: f32 = 0.1;
 const DEFAULT_LIGHT: Point3 = Point3 { x: 0.0, y: 0.0, z: -1.0 };
+const DEFAULT_SHADOW_MAP_RESOLUTION: u32 = 1024;
+const DEFAULT_SHADOW_BIAS: f32 = 0.01;
 const DEFAULT_FILE: &str = "resources/spaceship.obj";
+const DEFAULT_GRAVITY: Point3 = Point3 { x: 0.0, y: -9.81, z: 0.0 };
+const DEFAULT_FIXED_STEP: f32 = 1.0 / 60.0;
 
 // TODO check invalid values
 /// Entity holding all the configurable options of the engine
@@ -21,7 +30,19 @@ pub struct EngineConfig<'a> {
     pub view_limit: f32,
     pub z_offset: f32,
     pub light_direction: Point3,
+    pub ambient_light: f32,
     pub file: &'a str,
+    pub mesh_processing: MeshProcessing,
+    pub draw_mode: DrawMode,
+    pub texture_filter: Filter,
+    pub shadows: bool,
+    pub shadow_map_resolution: u32,
+    pub shadow_bias: f32,
+    pub anti_aliasing: AntiAliasing,
+    pub fog: Option<Fog>,
+    pub background: Background,
+    pub gravity: Point3,
+    pub fixed_step: f32,
 }
 
 impl<'a> EngineConfig<'a> {
@@ -68,18 +89,92 @@ impl<'a> EngineConfig<'a> {
         self
     }
 
-    /// Specifies the direction of the global illumination
+    /// Specifies the direction pointing to the source of the global illumination
     pub fn with_light_direction(mut self, light_direction: Point3) -> Self {
         self.light_direction = light_direction;
         self
     }
 
+    /// Specifies the intensity of the ambient light
+    pub fn with_ambient_light(mut self, ambient_light: f32) -> Self {
+        self.ambient_light = ambient_light;
+        self
+    }
+
     /// Specifies the path of the file to render
     pub fn using_file(mut self, file: &'a str) -> Self {
         self.file = file;
         self
     }
 
+    /// Specifies the operations applied to the mesh of the file when it's loaded, like centering
+    /// and normalizing it to fit any model in the same view
+    pub fn with_mesh_processing(mut self, mesh_processing: MeshProcessing) -> Self {
+        self.mesh_processing = mesh_processing;
+        self
+    }
+
+    /// Specifies the initial global mode to draw the meshes
+    pub fn with_draw_mode(mut self, draw_mode: DrawMode) -> Self {
+        self.draw_mode = draw_mode;
+        self
+    }
+
+    /// Specifies the filter to sample the textures of the meshes
+    pub fn with_texture_filter(mut self, texture_filter: Filter) -> Self {
+        self.texture_filter = texture_filter;
+        self
+    }
+
+    /// Enables or disables the shadows of the global directional light
+    pub fn with_shadows(mut self, shadows: bool) -> Self {
+        self.shadows = shadows;
+        self
+    }
+
+    /// Specifies the width and height in texels of the shadow map
+    pub fn with_shadow_map_resolution(mut self, shadow_map_resolution: u32) -> Self {
+        self.shadow_map_resolution = shadow_map_resolution;
+        self
+    }
+
+    /// Specifies the distance tolerated between a surface and its shadow caster, to avoid the
+    /// surfaces shadowing themselves
+    pub fn with_shadow_bias(mut self, shadow_bias: f32) -> Self {
+        self.shadow_bias = shadow_bias;
+        self
+    }
+
+    /// Specifies the technique to smooth the edges of the rendered triangles
+    pub fn with_anti_aliasing(mut self, anti_aliasing: AntiAliasing) -> Self {
+        self.anti_aliasing = anti_aliasing;
+        self
+    }
+
+    /// Specifies the fog fading the far surfaces
+    pub fn with_fog(mut self, fog: Fog) -> Self {
+        self.fog = Some(fog);
+        self
+    }
+
+    /// Specifies the color, gradient or skybox drawn behind the meshes
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Specifies the acceleration pulling the rigid bodies, in units per second squared
+    pub fn with_gravity(mut self, gravity: Point3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Specifies the seconds simulated by each step of the physics, independent of the frame rate
+    pub fn with_fixed_step(mut self, fixed_step: f32) -> Self {
+        self.fixed_step = fixed_step;
+        self
+    }
+
 }
 
 impl<'a> Default for EngineConfig<'a> {
@@ -93,7 +188,19 @@ impl<'a> Default for EngineConfig<'a> {
             view_limit: DEFAULT_VIEW_LIMIT,
             z_offset: DEFAULT_Z_OFFSET,
             light_direction: DEFAULT_LIGHT,
-            file: DEFAULT_FILE
+            ambient_light: DEFAULT_AMBIENT_LIGHT,
+            file: DEFAULT_FILE,
+            mesh_processing: MeshProcessing::default(),
+            draw_mode: DrawMode::default(),
+            texture_filter: Filter::default(),
+            shadows: false,
+            shadow_map_resolution: DEFAULT_SHADOW_MAP_RESOLUTION,
+            shadow_bias: DEFAULT_SHADOW_BIAS,
+            anti_aliasing: AntiAliasing::default(),
+            fog: None,
+            background: Background::default(),
+            gravity: DEFAULT_GRAVITY,
+            fixed_step: DEFAULT_FIXED_STEP,
         }
     }
 }
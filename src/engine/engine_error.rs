@@ -8,6 +8,8 @@ pub enum EngineError<'a> {
     AdapterNotFound,
     /// A file to be rendered is missing or invalid
     BadFile(&'a str),
+    /// The material of a file to be rendered is missing or invalid
+    BadMaterial(String),
     /// Closing of the engine invoked
     CloseInvocation,
     /// Error triggered during a render
@@ -21,6 +23,7 @@ impl<'a> EngineError<'a> {
             Self::BadFile(file) => {
                 format!("The specified file {file} is missing or invalid")
             },
+            Self::BadMaterial(message) => format!("Invalid material: {message}"),
             Self::CloseInvocation => "Close invoked".to_string(),
             Self::Rendering => "Rendering has failed".to_string(),
         }
@@ -45,6 +48,7 @@ impl<'a> From<EnvironmentError<'a>> for EngineError<'a> {
     fn from(e: EnvironmentError<'a>) -> Self {
         match e {
             EnvironmentError::BadFile(file) => Self::BadFile(file),
+            EnvironmentError::BadMaterial(message) => Self::BadMaterial(message),
         }
     }
 }
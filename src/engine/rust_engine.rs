@@ -1,10 +1,13 @@
 use crate::engine::engine_camera::EngineCamera;
 use crate::engine::engine_error::EngineError;
 use crate::engine::EngineConfig;
-use crate::environment::Environment;
+use crate::environment::{Environment, Pick};
+use crate::overlay::{Overlay, OverlayCanvas};
+use crate::post_process::PostProcessChain;
+use crate::render::{DrawMode, Renderer, WindowFramebuffer};
 use crate::FerruxViewport;
 use ferrux_viewport::viewport::ViewportFactory;
-use log::{error, info};
+use log::{debug, error, info};
 use std::time::SystemTime;
 use winit::dpi::LogicalSize;
 use winit::event::{Event, VirtualKeyCode};
@@ -14,14 +17,20 @@ use winit_input_helper::WinitInputHelper;
 
 type Error<'a> = EngineError<'a>;
 
-/// Graphics engine. It holds the displayed window, the canvas to print in the window and the
-/// environment with the meshes to display.
+/// Key to cycle through the global [DrawMode]
+const DRAW_MODE_KEY: VirtualKeyCode = VirtualKeyCode::Tab;
+
+/// Graphics engine. It holds the displayed window, the canvas to print in the window, the
+/// environment with the meshes to display and the overlay drawn on top of them.
 pub struct Rust3DEngine {
     input: WinitInputHelper,
     window: Window,
     viewport: FerruxViewport,
+    renderer: Renderer,
     camera: EngineCamera,
     environment: Environment,
+    overlay: Overlay,
+    draw_mode: DrawMode,
     time: SystemTime,
 }
 
@@ -67,25 +76,76 @@ impl Rust3DEngine {
             EngineError::AdapterNotFound
         })?;
 
-        let environment = Environment::new(config.file)?;
+        let environment = Environment::new(&config)?;
+
+        let mut renderer = Renderer::new()
+            .with_anti_aliasing(config.anti_aliasing)
+            .with_background(config.background.clone());
+        if config.shadows {
+            renderer = renderer.with_shadows(config.shadow_map_resolution, config.shadow_bias);
+        }
+        if let Some(fog) = config.fog {
+            renderer = renderer.with_fog(fog);
+        }
 
         Ok(Self {
             input: WinitInputHelper::new(),
             window,
             viewport,
+            renderer,
             camera: EngineCamera::new(&config),
             environment,
+            overlay: Overlay::new(),
+            draw_mode: config.draw_mode,
             time: SystemTime::now(),
         })
     }
 
+    /// Returns the camera rendering the scene
+    pub fn camera(&mut self) -> &mut EngineCamera {
+        &mut self.camera
+    }
+
+    /// Returns the environment holding the actors of the scene
+    pub fn environment(&mut self) -> &mut Environment {
+        &mut self.environment
+    }
+
+    /// Returns the closest triangle of the environment under a pixel of the window, if any
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Coordinates of the pixel, from the upper-left corner of the window
+    ///
+    pub fn pick(&self, x: f32, y: f32) -> Option<Pick> {
+        self.environment.pick(&self.camera.screen_ray(x, y))
+    }
+
+    /// Returns the closest triangle of the environment under the mouse, if it's over the window
+    pub fn pick_at_mouse(&self) -> Option<Pick> {
+        self.input.mouse().and_then(|(x, y)| self.pick(x, y))
+    }
+
+    /// Returns the two-dimensional layer drawn on top of the environment
+    pub fn overlay(&mut self) -> &mut Overlay {
+        &mut self.overlay
+    }
+
+    /// Returns the passes applied to the drawn environment before the overlay
+    pub fn post_processing(&mut self) -> &mut PostProcessChain {
+        self.renderer.post_processing()
+    }
+
     /// Draws the current frame
     ///
     /// # Error
     /// If some problem in the rendering happens a [EngineError::Rendering] is thrown
     ///
     pub fn draw(&mut self) -> Result<(), EngineError> {
-        self.environment.draw(&mut self.viewport, &self.camera);
+        let (width, height) = self.camera.screen_size();
+        let mut framebuffer = WindowFramebuffer::new(&mut self.viewport, width, height);
+        self.renderer.render(&mut framebuffer, &self.environment, &self.camera);
+        debug!("{} actors culled", self.environment.culled_actors());
+        self.overlay.draw(&mut OverlayCanvas::new(&mut framebuffer));
         self.viewport.render().map_err(|e| {
             error!("{:?}", e);
             EngineError::Rendering
@@ -112,9 +172,17 @@ impl Rust3DEngine {
                 return Err(EngineError::CloseInvocation);
             }
 
+            // Switch the drawing mode
+            if self.input.key_pressed(DRAW_MODE_KEY) {
+                self.draw_mode = self.draw_mode.next();
+                info!("Drawing mode: {:?}", self.draw_mode);
+                self.environment.set_draw_mode(self.draw_mode);
+            }
+
             // Resize the window
             if let Some(size) = self.input.window_resized() {
                 self.viewport.resize(size.width, size.height);
+                self.camera.set_screen_size(size.width, size.height);
             }
         }
 
@@ -0,0 +1,195 @@
+use crate::geometry::vector::Point3;
+use crate::geometry::Aabb;
+
+/// Maximum number of items of a leaf of the tree
+const LEAF_SIZE: usize = 4;
+
+/// Node of the hierarchy, enclosing all the items below it
+#[derive(Debug)]
+struct Node {
+    aabb: Aabb,
+    kind: NodeKind,
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    /// Range of the items of the leaf
+    Leaf { start: usize, end: usize },
+    /// Positions of the two children in the node list
+    Branch { left: usize, right: usize },
+}
+
+/// Bounding volume hierarchy, a binary tree of axis-aligned boxes to find quickly the items
+/// overlapping a region of the space. Items without bounds are kept outside the tree and
+/// returned by every query.
+#[derive(Debug, Default)]
+pub struct Bvh {
+    /// Nodes of the tree, every parent before its children
+    nodes: Vec<Node>,
+    /// Identifiers and bounds of the items, grouped by leaf
+    items: Vec<(usize, Aabb)>,
+    /// Identifiers of the items without bounds
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    /// Builds the hierarchy splitting the items by the middle of their longest axis
+    ///
+    /// # Arguments
+    /// * `items` - Identifiers of the items and their bounds, if any
+    ///
+    pub fn build(items: impl IntoIterator<Item = (usize, Option<Aabb>)>) -> Self {
+        let mut bvh = Self::default();
+        for (id, aabb) in items {
+            match aabb {
+                Some(aabb) => bvh.items.push((id, aabb)),
+                None => bvh.unbounded.push(id),
+            }
+        }
+        if !bvh.items.is_empty() {
+            bvh.split(0, bvh.items.len());
+        }
+        bvh
+    }
+
+    /// Updates the boxes of the tree with the new bounds of the items, keeping its structure.
+    /// It's faster than building it again but the tree gets worse as the items move away.
+    ///
+    /// # Arguments
+    /// * `bounds` - Returns the current bounds of each item
+    ///
+    pub fn refit(&mut self, bounds: impl Fn(usize) -> Option<Aabb>) {
+        for (id, aabb) in &mut self.items {
+            if let Some(bounds) = bounds(*id) {
+                *aabb = bounds;
+            }
+        }
+        for i in (0..self.nodes.len()).rev() {
+            self.nodes[i].aabb = match self.nodes[i].kind {
+                NodeKind::Leaf { start, end } => self.enclose(start, end),
+                NodeKind::Branch { left, right } => self.nodes[left].aabb.union(&self.nodes[right].aabb),
+            };
+        }
+    }
+
+    /// Returns the identifiers of the items whose bounds overlap a region, and the ones without
+    /// bounds
+    ///
+    /// # Arguments
+    /// * `overlaps` - Checks if a box overlaps the region
+    ///
+    pub fn query(&self, overlaps: impl Fn(&Aabb) -> bool) -> Vec<usize> {
+        let mut found = self.unbounded.clone();
+        let mut pending = if self.nodes.is_empty() { vec![] } else { vec![0] };
+        while let Some(i) = pending.pop() {
+            let node = &self.nodes[i];
+            if !overlaps(&node.aabb) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => found.extend(
+                    self.items[start..end].iter().filter(|(_, aabb)| overlaps(aabb)).map(|(id, _)| *id),
+                ),
+                NodeKind::Branch { left, right } => pending.extend([right, left]),
+            }
+        }
+        found
+    }
+
+    /// Returns the number of items of the hierarchy
+    pub fn len(&self) -> usize {
+        self.items.len() + self.unbounded.len()
+    }
+
+    /// Checks if the hierarchy has no items
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds the node of a range of items, and the nodes below it. Returns its position.
+    fn split(&mut self, start: usize, end: usize) -> usize {
+        let index = self.nodes.len();
+        let aabb = self.enclose(start, end);
+        if end - start <= LEAF_SIZE {
+            self.nodes.push(Node { aabb, kind: NodeKind::Leaf { start, end } });
+            return index;
+        }
+
+        let centers: Vec<Point3> = self.items[start..end].iter().map(|(_, aabb)| aabb.center()).collect();
+        let centers = Aabb::from_points(&centers);
+        let size = [
+            centers.max.x - centers.min.x,
+            centers.max.y - centers.min.y,
+            centers.max.z - centers.min.z,
+        ];
+        let axis = (0..3).fold(0, |longest, axis| if size[axis] > size[longest] { axis } else { longest });
+        let coordinate = |aabb: &Aabb| {
+            let center = aabb.center();
+            [center.x, center.y, center.z][axis]
+        };
+        self.items[start..end].sort_by(|(_, a), (_, b)| coordinate(a).total_cmp(&coordinate(b)));
+
+        self.nodes.push(Node { aabb, kind: NodeKind::Leaf { start, end } });
+        let middle = start + (end - start) / 2;
+        let left = self.split(start, middle);
+        let right = self.split(middle, end);
+        self.nodes[index].kind = NodeKind::Branch { left, right };
+        index
+    }
+
+    /// Returns the box enclosing a range of items
+    fn enclose(&self, start: usize, end: usize) -> Aabb {
+        let items = &self.items[start..end];
+        items.iter().skip(1).fold(items[0].1.clone(), |aabb, (_, item)| aabb.union(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bvh;
+    use crate::geometry::vector::Point3;
+    use crate::geometry::Aabb;
+
+    /// Box of size one with the lowest corner in the given position
+    fn cube(x: f32, y: f32, z: f32) -> Aabb {
+        Aabb { min: Point3 { x, y, z }, max: Point3 { x: x + 1.0, y: y + 1.0, z: z + 1.0 } }
+    }
+
+    /// Row of cubes along the X-axis, separated by one unit
+    fn row(count: usize) -> Bvh {
+        Bvh::build((0..count).map(|i| (i, Some(cube(i as f32 * 2.0, 0.0, 0.0)))))
+    }
+
+    fn sorted(mut ids: Vec<usize>) -> Vec<usize> {
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn query() {
+        let bvh = row(20);
+        assert_eq!(bvh.len(), 20);
+        let region = Aabb { min: Point3 { x: 3.5, y: 0.0, z: 0.0 }, max: Point3 { x: 8.5, y: 1.0, z: 1.0 } };
+        assert_eq!(sorted(bvh.query(|aabb| aabb.intersects(&region))), vec![2, 3, 4]);
+        assert_eq!(sorted(bvh.query(|_| true)), (0..20).collect::<Vec<_>>());
+        assert!(bvh.query(|_| false).is_empty());
+    }
+
+    #[test]
+    fn unbounded_items() {
+        let bvh = Bvh::build([(0, Some(cube(0.0, 0.0, 0.0))), (1, None)]);
+        assert_eq!(bvh.query(|_| false), vec![1]);
+        assert!(Bvh::build([]).is_empty());
+    }
+
+    #[test]
+    fn refit() {
+        let mut bvh = row(20);
+        // Moves every cube up
+        bvh.refit(|i| Some(cube(i as f32 * 2.0, 10.0, 0.0)));
+        let origin = Point3 { x: 0.5, y: 0.5, z: 0.5 };
+        assert!(bvh.query(|aabb| aabb.distance_to(&origin) == 0.0).is_empty());
+        let moved = Point3 { x: 0.5, y: 10.5, z: 0.5 };
+        assert_eq!(bvh.query(|aabb| aabb.distance_to(&moved) == 0.0), vec![0]);
+    }
+}
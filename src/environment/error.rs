@@ -5,12 +5,15 @@ use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 pub(crate) enum EnvironmentError<'a> {
     /// A file to load in the environment is missing or invalid
     BadFile(&'a str),
+    /// The material library of a mesh or one of its textures can't be loaded
+    BadMaterial(String),
 }
 
 impl<'a> EnvironmentError<'a> {
     fn message(&self) -> &str {
         match self {
-            Self::BadFile(file) => file
+            Self::BadFile(file) => file,
+            Self::BadMaterial(message) => message,
         }
     }
 }
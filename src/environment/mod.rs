@@ -1,50 +1,536 @@
 pub mod error;
+mod bvh;
+mod pick;
+
+pub use bvh::Bvh;
+pub use pick::Pick;
 
 use crate::{geometry::Mesh, actors::mesh_actor::MeshActor};
-use crate::actors::Actor;
-use crate::engine::EngineCamera;
+use crate::actors::{Actor, Drawable};
+use crate::collision::{detect_contacts, Contact};
+use crate::engine::{EngineCamera, EngineConfig};
 use crate::environment::error::EnvironmentError;
-use crate::FerruxViewport;
+use crate::geometry::vector::Point3;
+use crate::geometry::Ray;
+use crate::lighting::{Color, Light, Lighting, Material, MaterialLibrary};
+use crate::physics::resolve_collision;
+use crate::render::{DrawMode, Frustum, Rasterizer, RenderContext, ShadowMap};
 use log::{error, info};
+use std::cell::{Cell, Ref, RefCell};
 use std::fs;
+use std::path::Path;
+
+/// Maximum number of physics steps run in one update, to catch up after slow frames without
+/// falling further behind
+const MAX_FIXED_STEPS: u32 = 8;
 
-/// Represents the environment that is drawn in the screen. It holds all the actors to draw.
+/// Identifier of an actor added to the [Environment]. It's the position of the actor in the
+/// environment, which can't remove them, so the identifiers are never reused and the physics can
+/// index the actors of each contact directly.
+pub type ActorId = usize;
+
+/// Represents the environment that is drawn in the screen. It holds all the actors to draw and
+/// the lights illuminating them.
 pub struct Environment {
     actors: Vec<Box<dyn Actor>>,
+    lighting: Lighting,
+    /// Number of actors outside the camera frustum in the last draw
+    culled: Cell<usize>,
+    /// Hierarchy of the bounds of the actors, to find them by position
+    index: RefCell<Bvh>,
+    /// Whether any actor was added since the index was built
+    stale_index: Cell<bool>,
+    /// Whether any actor may have moved since the bounds of the index were refitted
+    stale_bounds: Cell<bool>,
+    /// Collisions between the actors found in the last physics step, or in the last update if it
+    /// didn't run any step
+    contacts: Vec<Contact>,
+    /// Acceleration pulling the rigid bodies
+    gravity: Point3,
+    /// Seconds simulated by each step of the physics
+    fixed_step: f32,
+    /// Seconds elapsed since the last step of the physics
+    elapsed: f32,
 }
 
 impl Environment {
-    /// Returns a new instance of the environment
-    pub(crate) fn new(file: &str) -> Result<Self, EnvironmentError> {
+    /// Returns a new instance of the environment with the mesh of the configured file
+    pub(crate) fn new<'a>(config: &EngineConfig<'a>) -> Result<Self, EnvironmentError<'a>> {
         info!("Creating environment");
-        //let cube1 = get_3d_cube();
 
+        let file = config.file;
         let content = fs::read_to_string(file).map_err(|e| {
             error!("{}", e);
             EnvironmentError::BadFile(file)
         })?;
 
-		let mesh = Mesh::try_from(content).unwrap();
+		let mut mesh = Mesh::try_from(content).unwrap();
+		mesh.process(&config.mesh_processing);
+
+        let mut actor = MeshActor::new(mesh);
+        if let Some(material) = load_material(file, actor.mesh(), config)? {
+            actor = actor.with_material(material);
+        }
+
+        let mut environment = Environment::empty(config);
+        environment.add_actor(Box::new(actor));
+        environment.set_draw_mode(config.draw_mode);
+        Ok(environment)
+    }
+
+    /// Returns an environment without actors, lit by the configured global lights
+    pub fn empty(config: &EngineConfig) -> Self {
+        let mut lighting = Lighting::new();
+        lighting.add(Light::ambient(Color::WHITE, config.ambient_light));
+        lighting.add(Light::directional(config.light_direction.clone(), Color::WHITE, 1.0));
+
+        Environment {
+            actors: Vec::new(),
+            lighting,
+            culled: Cell::new(0),
+            index: RefCell::new(Bvh::default()),
+            stale_index: Cell::new(false),
+            stale_bounds: Cell::new(false),
+            contacts: Vec::new(),
+            gravity: config.gravity.clone(),
+            fixed_step: config.fixed_step,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Draws the environment and actors with the given rasterizer. The actors whose bounding
+    /// volumes are out of the camera frustum are skipped.
+    ///
+    /// # Arguments
+    /// * `rasterizer` - Rasterizer to draw the actors with
+    /// * `camera` - Camera rendering the scene
+    /// * `shadow` - Shadow map of the directional light, if the shadows are enabled
+    pub fn draw(&self, rasterizer: &mut Rasterizer, camera: &EngineCamera, shadow: Option<&ShadowMap>) {
+        let context = RenderContext { camera, lighting: &self.lighting, shadow };
+        let frustum = Frustum::new(camera);
+        let mut visible = self.index().query(|aabb| frustum.intersects_aabb(aabb));
+        visible.sort_unstable();
+        let mut drawn = 0;
+        for actor in visible.into_iter().map(|id| &self.actors[id]) {
+            if is_visible(&frustum, actor.as_ref()) {
+                actor.draw(rasterizer, &context);
+                drawn += 1;
+            }
+        }
+        #[cfg(feature = "parallel")]
+        rasterizer.flush(&context);
+        rasterizer.draw_hidden_lines();
+        self.culled.set(self.actors.len() - drawn);
+    }
 
-        Ok(Environment {
-            actors: vec![ Box::new(MeshActor::new(mesh)) ],
-        })
+    /// Returns the number of actors skipped in the last draw for being out of the camera view
+    pub fn culled_actors(&self) -> usize {
+        self.culled.get()
     }
 
-    /// Draws the environment and actors in the given canvas
+    /// Renders the depth of the actors from the first directional light, to find the points in
+    /// the shadow. Returns [None] if there's no directional light or actor casting shadows.
     ///
     /// # Arguments
-    /// * `canvas` - [EngineCanvas] to draw the actors on
-    pub fn draw(&self, viewport: &mut FerruxViewport, camera: &EngineCamera) {
+    /// * `camera` - Camera rendering the scene
+    /// * `resolution` - Width and height of the map in texels
+    /// * `bias` - Distance to tolerate between a point and the closest caster
+    pub fn shadow_map(&self, camera: &EngineCamera, resolution: u32, bias: f32) -> Option<ShadowMap> {
+        let context = RenderContext { camera, lighting: &self.lighting, shadow: None };
+        let mut casters = Vec::new();
         for actor in &self.actors {
-            actor.draw(viewport, camera);
+            actor.cast_shadows(&mut casters, &context);
         }
+        ShadowMap::new(&self.lighting, &casters, resolution, bias)
+    }
+
+    /// Returns the light sources of the environment
+    pub fn lighting(&mut self) -> &mut Lighting {
+        &mut self.lighting
+    }
+
+    /// Adds a new actor to the environment and returns its identifier
+    ///
+    /// # Arguments
+    /// * `actor` - Actor to add
+    pub fn add_actor(&mut self, actor: Box<dyn Actor>) -> ActorId {
+        self.actors.push(actor);
+        self.stale_index.set(true);
+        self.actors.len() - 1
+    }
+
+    /// Returns the actor with the given identifier
+    pub fn actor(&self, id: ActorId) -> Option<&dyn Actor> {
+        self.actors.get(id).map(|actor| actor.as_ref())
     }
 
-    /// Procs an update of all the actors
+    /// Returns the actor with the given identifier to change it. Its new bounds are indexed before
+    /// the next query.
+    pub fn actor_mut(&mut self, id: ActorId) -> Option<&mut (dyn Actor + 'static)> {
+        let actor = self.actors.get_mut(id)?;
+        self.stale_bounds.set(true);
+        Some(actor.as_mut())
+    }
+
+    /// Returns the actors whose bounding boxes are crossed by the ray, from the closest to the
+    /// farthest. Actors without bounds are always returned first.
+    ///
+    /// # Arguments
+    /// * `ray` - Ray in world space
+    pub fn ray_query(&self, ray: &Ray) -> Vec<ActorId> {
+        self.crossed_bounds(ray).into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Returns the closest triangle of the actors crossed by the ray
+    ///
+    /// # Arguments
+    /// * `ray` - Ray in world space, like the one of [EngineCamera::screen_ray]
+    pub fn pick(&self, ray: &Ray) -> Option<Pick> {
+        let mut closest: Option<Pick> = None;
+        for (actor, entry) in self.crossed_bounds(ray) {
+            if closest.as_ref().is_some_and(|pick| pick.distance < entry) {
+                // The rest of the actors start farther than the hit
+                break;
+            }
+            if let Some((triangle, distance)) = self.actors[actor].intersect(ray) {
+                if closest.as_ref().is_none_or(|pick| distance < pick.distance) {
+                    closest = Some(Pick { actor, triangle, point: ray.point_at(distance), distance });
+                }
+            }
+        }
+        closest
+    }
+
+    /// Returns the actors whose bounding boxes are within the distance of a point, and the ones
+    /// without bounds
+    ///
+    /// # Arguments
+    /// * `point` - Point in world space
+    /// * `distance` - Maximum distance from the point to the bounds
+    pub fn actors_near(&self, point: &Point3, distance: f32) -> Vec<ActorId> {
+        let mut near = self.index().query(|aabb| aabb.distance_to(point) <= distance);
+        near.sort_unstable();
+        near
+    }
+
+    /// Changes the global [DrawMode] of all the actors
+    pub fn set_draw_mode(&mut self, mode: DrawMode) {
+        for actor in &mut self.actors {
+            actor.set_draw_mode(mode);
+        }
+    }
+
+    /// Procs an update of all the actors and advances the physics in fixed steps for the elapsed
+    /// time, refitting their bounds in the index before the next query. The collisions between
+    /// the actors with colliders found in the last step, or in their current positions if no step
+    /// was run, are sent to both actors.
     pub fn update(&mut self, delta: u128) {
         for actor in &mut self.actors {
             actor.update(delta);
         }
+        self.elapsed = (self.elapsed + delta as f32 / 1000.0).min(self.fixed_step * MAX_FIXED_STEPS as f32);
+        let mut stepped = false;
+        while self.fixed_step > 0.0 && self.elapsed >= self.fixed_step {
+            self.elapsed -= self.fixed_step;
+            self.fixed_update(self.fixed_step);
+            stepped = true;
+        }
+        self.stale_bounds.set(true);
+
+        if !stepped {
+            self.contacts = self.find_contacts();
+        }
+        for contact in &self.contacts {
+            self.actors[contact.actor].on_collision(contact);
+            self.actors[contact.other].on_collision(&contact.flipped());
+        }
+    }
+
+    /// Returns the collisions between the actors found in the last update, each one seen by the
+    /// actor with the lowest identifier. If the update ran physics steps, they are the ones found
+    /// in the last step before bouncing the actors apart.
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Moves the actors with rigid bodies the given seconds and bounces the colliding ones apart,
+    /// keeping their contacts
+    fn fixed_update(&mut self, step: f32) {
+        for actor in &mut self.actors {
+            if let Some(body) = actor.rigid_body() {
+                let offset = body.integrate(&self.gravity, step);
+                actor.translate(&offset);
+            }
+        }
+        self.contacts = self.find_contacts();
+        for contact in &self.contacts {
+            // The actor of each contact comes before the other one
+            debug_assert!(contact.actor < contact.other && contact.other < self.actors.len());
+            let (first, second) = self.actors.split_at_mut(contact.other);
+            let (a, b) = (&mut first[contact.actor], &mut second[0]);
+            if let Some((a_offset, b_offset)) = resolve_collision(a.rigid_body(), b.rigid_body(), contact) {
+                a.translate(&a_offset);
+                b.translate(&b_offset);
+            }
+        }
+    }
+
+    /// Returns the contacts between the actors with colliders in their current positions
+    fn find_contacts(&self) -> Vec<Contact> {
+        let colliders: Vec<_> = self.actors.iter().enumerate()
+            .filter_map(|(id, actor)| actor.collider().map(|collider| (id, collider)))
+            .collect();
+        detect_contacts(&colliders)
+    }
+
+    /// Returns the actors whose bounding boxes are crossed by the ray and the distance to enter
+    /// them, from the closest to the farthest
+    fn crossed_bounds(&self, ray: &Ray) -> Vec<(ActorId, f32)> {
+        let distance = |id: ActorId| self.actors[id].aabb().map_or(Some(0.0), |aabb| ray.intersect_aabb(aabb));
+        let mut hits: Vec<(ActorId, f32)> = self.index()
+            .query(|aabb| ray.intersect_aabb(aabb).is_some())
+            .into_iter()
+            .filter_map(|id| distance(id).map(|distance| (id, distance)))
+            .collect();
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        hits
+    }
+
+    /// Returns the index of the bounds of the actors, building it again if any actor was added or
+    /// refitting it if any actor may have moved
+    fn index(&self) -> Ref<'_, Bvh> {
+        let refit = self.stale_bounds.replace(false);
+        if self.stale_index.replace(false) {
+            let bounds = self.actors.iter().map(|actor| actor.aabb().cloned()).enumerate();
+            *self.index.borrow_mut() = Bvh::build(bounds);
+        } else if refit {
+            let actors = &self.actors;
+            self.index.borrow_mut().refit(|id| actors[id].aabb().cloned());
+        }
+        self.index.borrow()
+    }
+}
+
+/// Checks if any bounding volume of the drawable may be inside the frustum
+fn is_visible(frustum: &Frustum, drawable: &dyn Drawable) -> bool {
+    drawable.bounding_sphere().is_none_or(|sphere| frustum.contains_sphere(sphere))
+        && drawable.aabb().is_none_or(|aabb| frustum.intersects_aabb(aabb))
+}
+
+/// Loads the material used by the mesh from its library, if it references one
+///
+/// # Arguments
+/// * `file` - Path of the file of the mesh, the library is relative to it
+/// * `mesh` - Mesh to load the material of
+/// * `config` - Configuration with the texture filter
+///
+fn load_material<'a>(file: &str, mesh: &Mesh, config: &EngineConfig) -> Result<Option<Material>, EnvironmentError<'a>> {
+    let library = match mesh.material_library() {
+        Some(library) => library,
+        None => return Ok(None),
+    };
+    let path = Path::new(file).parent().unwrap_or_else(|| Path::new("")).join(library);
+    let library = MaterialLibrary::load(&path).map_err(|e| {
+        error!("{}", e);
+        EnvironmentError::BadMaterial(e.to_string())
+    })?;
+    let material = mesh.material().and_then(|name| library.get(name)).cloned().map(|mut material| {
+        material.sampler.filter = config.texture_filter;
+        material
+    });
+    Ok(material)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Environment;
+    use crate::actors::mesh_actor::MeshActor;
+    use crate::actors::{Actor, Drawable};
+    use crate::collision::{Collider, ColliderShape, Contact};
+    use crate::physics::RigidBody;
+    use crate::render::{Rasterizer, RenderContext};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::engine::{EngineCamera, EngineConfig};
+    use crate::geometry::vector::Point3;
+    use crate::geometry::{BoundingSphere, Mesh, Ray};
+
+    /// Environment with a triangle of size one at each of the given positions along the Z-axis
+    fn environment(depths: &[f32]) -> Environment {
+        let mut environment = Environment::empty(&EngineConfig::default());
+        for z in depths {
+            let content = format!("v 0 0 {z}\nv 1 0 {z}\nv 0 1 {z}\nf 1 2 3\n");
+            environment.add_actor(Box::new(MeshActor::new(Mesh::try_from(content).unwrap())));
+        }
+        environment
+    }
+
+    #[test]
+    fn ray_query() {
+        let environment = environment(&[5.0, 1.0, 3.0]);
+        let ray = Ray::new(Point3 { x: 0.2, y: 0.2, z: -1.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(environment.ray_query(&ray), vec![1, 2, 0]);
+
+        let miss = Ray::new(Point3 { x: 2.0, y: 0.2, z: -1.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert!(environment.ray_query(&miss).is_empty());
+    }
+
+    #[test]
+    fn pick() {
+        let environment = environment(&[5.0, 1.0, 3.0]);
+        let ray = Ray::new(Point3 { x: 0.2, y: 0.3, z: -1.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        let pick = environment.pick(&ray).unwrap();
+        assert_eq!(pick.actor, 1);
+        assert_eq!(pick.triangle, 0);
+        assert_eq!(pick.point, Point3 { x: 0.2, y: 0.3, z: 1.0 });
+        assert_eq!(pick.distance, 2.0);
+
+        // Inside the bounds of the triangles but out of them
+        let miss = Ray::new(Point3 { x: 0.8, y: 0.8, z: -1.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(environment.pick(&miss), None);
+    }
+
+    #[test]
+    fn screen_pick() {
+        let config = EngineConfig::default().with_width(64).with_height(64);
+        let camera = EngineCamera::new(&config);
+        let mut environment = Environment::empty(&config);
+        let triangle = "v -1.0 -1.0 0.0\nv 0.0 1.0 0.0\nv 1.0 -1.0 0.0\nf 1 2 3\n".to_string();
+        environment.add_actor(Box::new(MeshActor::new(Mesh::try_from(triangle).unwrap())));
+
+        let pick = environment.pick(&camera.screen_ray(32.0, 32.0)).unwrap();
+        assert_eq!(pick.actor, 0);
+        assert!((pick.distance - config.z_offset).abs() < 1e-4);
+        assert!(environment.pick(&camera.screen_ray(1.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn proximity_query() {
+        let mut environment = environment(&[0.0, 10.0, 20.0]);
+        let point = Point3 { x: 0.0, y: 0.0, z: 9.0 };
+        assert_eq!(environment.actors_near(&point, 1.0), vec![1]);
+        assert_eq!(environment.actors_near(&point, 11.0), vec![0, 1, 2]);
+
+        let content = "v 0 0 8\nv 1 0 8\nv 0 1 8\nf 1 2 3\n".to_string();
+        let id = environment.add_actor(Box::new(MeshActor::new(Mesh::try_from(content).unwrap())));
+        assert_eq!(environment.actors_near(&point, 1.0), vec![1, id]);
+    }
+
+    #[test]
+    fn moved_actor_query() {
+        let mut environment = environment(&[5.0, 1.0]);
+        let ray = Ray::new(Point3 { x: 0.2, y: 0.2, z: -1.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(environment.ray_query(&ray), vec![1, 0]);
+
+        // The moved actor is found in its new place without waiting for an update
+        environment.actor_mut(1).unwrap().translate(&Point3 { x: 0.0, y: 0.0, z: 8.0 });
+        assert_eq!(environment.ray_query(&ray), vec![0, 1]);
+        assert_eq!(environment.actors_near(&Point3 { x: 0.0, y: 0.0, z: 9.0 }, 0.5), vec![1]);
+    }
+
+    /// Sphere keeping the contacts it receives, still unless it has a body
+    struct Ball {
+        collider: BoundingSphere,
+        contacts: Rc<RefCell<Vec<Contact>>>,
+        body: Option<RigidBody>,
+    }
+
+    impl Drawable for Ball {
+        fn draw(&self, _rasterizer: &mut Rasterizer, _context: &RenderContext) {}
+    }
+
+    impl Actor for Ball {
+        fn update(&mut self, _delta: u128) {}
+
+        fn collider(&self) -> Option<Collider<'_>> {
+            Some(Collider::Sphere(self.collider.clone()))
+        }
+
+        fn on_collision(&mut self, contact: &Contact) {
+            self.contacts.borrow_mut().push(contact.clone());
+        }
+
+        fn rigid_body(&mut self) -> Option<&mut RigidBody> {
+            self.body.as_mut()
+        }
+
+        fn translate(&mut self, offset: &Point3) {
+            self.collider.center = &self.collider.center + offset;
+        }
+    }
+
+    #[test]
+    fn collisions() {
+        let mut environment = environment(&[]);
+        let contacts = Rc::new(RefCell::new(Vec::new()));
+        let ball = |x: f32| Box::new(Ball {
+            collider: BoundingSphere { center: Point3 { x, y: 0.0, z: 0.0 }, radius: 1.0 },
+            contacts: contacts.clone(),
+            body: None,
+        });
+        let first = environment.add_actor(ball(0.0));
+        let second = environment.add_actor(ball(1.5));
+        environment.add_actor(ball(10.0));
+        // Overlapping the balls, but without a collider
+        environment.add_actor(Box::new(MeshActor::new(Mesh::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n".to_string()).unwrap())));
+        let with_collider = MeshActor::new(Mesh::try_from("v 9 0 0\nv 11 0 0\nv 9 1 0\nf 1 2 3\n".to_string()).unwrap())
+            .with_collider(ColliderShape::Mesh);
+        let triangle = environment.add_actor(Box::new(with_collider));
+        environment.update(0);
+
+        let found: Vec<_> = environment.contacts().iter().map(|contact| (contact.actor, contact.other)).collect();
+        assert_eq!(found, vec![(first, second), (2, triangle)]);
+        let contact = &environment.contacts()[0];
+        assert_eq!(contact.normal, Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(contact.depth, 0.5);
+
+        let received = contacts.borrow();
+        assert_eq!(received.len(), 3);
+        assert_eq!(received[0], *contact);
+        assert_eq!(received[1], contact.flipped());
+        assert_eq!(received[2].actor, 2);
+    }
+
+    #[test]
+    fn physics() {
+        let config = EngineConfig::default().with_gravity(Point3::default()).with_fixed_step(0.1);
+        let mut environment = Environment::empty(&config);
+        let ball = |x: f32, body: Option<RigidBody>| Box::new(Ball {
+            collider: BoundingSphere { center: Point3 { x, y: 0.0, z: 0.0 }, radius: 1.0 },
+            contacts: Rc::new(RefCell::new(Vec::new())),
+            body,
+        });
+        let body = RigidBody::new().with_restitution(1.0).with_velocity(Point3 { x: 10.0, y: 0.0, z: 0.0 });
+        let moving = environment.add_actor(ball(-2.5, Some(body)));
+        let wall = environment.add_actor(ball(0.0, None));
+
+        // Too short for a step
+        environment.update(50);
+        assert!(environment.contacts().is_empty());
+
+        // The step moves the ball into the wall and bounces it back out, keeping the contact
+        environment.update(50);
+        let found: Vec<_> = environment.contacts().iter().map(|contact| (contact.actor, contact.other)).collect();
+        assert_eq!(found, vec![(moving, wall)]);
+        let body = environment.actor_mut(moving).unwrap().rigid_body().unwrap().clone();
+        assert_eq!(body.velocity, Point3 { x: -10.0, y: 0.0, z: 0.0 });
+        let aabb = environment.actor(moving).unwrap().collider().unwrap().aabb();
+        assert!((aabb.max.x + 1.0).abs() < 1e-5);
+        assert!(environment.actor_mut(wall).unwrap().rigid_body().is_none());
+    }
+
+    #[test]
+    fn gravity() {
+        let config = EngineConfig::default().with_fixed_step(0.5);
+        let mut environment = Environment::empty(&config);
+        let mesh = Mesh::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n".to_string()).unwrap();
+        let body = RigidBody::new().with_velocity(Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        let id = environment.add_actor(Box::new(MeshActor::new(mesh).with_rigid_body(body)));
+
+        // Two steps, the slow frame doesn't lose time
+        environment.update(1000);
+        let aabb = environment.actor(id).unwrap().aabb().unwrap().clone();
+        assert!((aabb.min.x - 1.0).abs() < 1e-5);
+        assert!((aabb.min.y + 9.81 * 0.75).abs() < 1e-4);
     }
 }
@@ -0,0 +1,15 @@
+use crate::environment::ActorId;
+use crate::geometry::vector::Point3;
+
+/// Closest surface of the environment crossed by a ray
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pick {
+    /// Actor owning the surface
+    pub actor: ActorId,
+    /// Index of the triangle in the mesh of the actor
+    pub triangle: usize,
+    /// Point where the ray crosses the triangle, in world space
+    pub point: Point3,
+    /// Distance from the origin of the ray to the point
+    pub distance: f32,
+}
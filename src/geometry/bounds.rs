@@ -0,0 +1,267 @@
+use std::borrow::Borrow;
+
+use crate::geometry::vector::ops::{Dot, Module};
+use crate::geometry::vector::Point3;
+use crate::math::Float;
+
+/// Axis-aligned box enclosing a group of points
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Aabb<T: Float = f32> {
+    /// Corner with the lowest coordinates
+    pub min: Point3<T>,
+    /// Corner with the highest coordinates
+    pub max: Point3<T>,
+}
+
+impl<T: Float> Aabb<T> {
+    /// Returns the smallest box enclosing all the points. Without points, it's a box of size zero
+    /// at the origin.
+    ///
+    /// # Arguments
+    /// * `points` - Points to enclose
+    ///
+    pub fn from_points<P: Borrow<Point3<T>>>(points: impl IntoIterator<Item = P>) -> Self {
+        let mut points = points.into_iter();
+        let first = match points.next() {
+            Some(point) => point.borrow().clone(),
+            None => return Self::default(),
+        };
+        points.fold(Self { min: first.clone(), max: first }, |aabb, point| {
+            let point = point.borrow();
+            Self {
+                min: Point3 { x: aabb.min.x.min(point.x), y: aabb.min.y.min(point.y), z: aabb.min.z.min(point.z) },
+                max: Point3 { x: aabb.max.x.max(point.x), y: aabb.max.y.max(point.y), z: aabb.max.z.max(point.z) },
+            }
+        })
+    }
+
+    /// Returns the point in the middle of the box
+    pub fn center(&self) -> Point3<T> {
+        let half = T::from_f32(0.5);
+        Point3 {
+            x: (self.min.x + self.max.x) * half,
+            y: (self.min.y + self.max.y) * half,
+            z: (self.min.z + self.max.z) * half,
+        }
+    }
+
+    /// Returns the eight corners of the box
+    pub fn corners(&self) -> [Point3<T>; 8] {
+        let (min, max) = (&self.min, &self.max);
+        [
+            Point3 { x: min.x, y: min.y, z: min.z },
+            Point3 { x: max.x, y: min.y, z: min.z },
+            Point3 { x: min.x, y: max.y, z: min.z },
+            Point3 { x: max.x, y: max.y, z: min.z },
+            Point3 { x: min.x, y: min.y, z: max.z },
+            Point3 { x: max.x, y: min.y, z: max.z },
+            Point3 { x: min.x, y: max.y, z: max.z },
+            Point3 { x: max.x, y: max.y, z: max.z },
+        ]
+    }
+
+    /// Checks if the point is inside the box or on its faces
+    pub fn contains(&self, point: &Point3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Checks if both boxes share any point
+    pub fn intersects(&self, other: &Aabb<T>) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x
+            && self.min.y <= other.max.y && other.min.y <= self.max.y
+            && self.min.z <= other.max.z && other.min.z <= self.max.z
+    }
+
+    /// Returns the smallest box enclosing both boxes
+    pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+        Aabb::from_points([&self.min, &self.max, &other.min, &other.max])
+    }
+
+    /// Returns the distance from the point to the closest point of the box, zero if it's inside
+    pub fn distance_to(&self, point: &Point3<T>) -> T {
+        let closest = Point3 {
+            x: point.x.max(self.min.x).min(self.max.x),
+            y: point.y.max(self.min.y).min(self.max.y),
+            z: point.z.max(self.min.z).min(self.max.z),
+        };
+        (point - &closest).module()
+    }
+
+    /// Returns the box converted to another precision
+    pub fn cast<U: Float>(&self) -> Aabb<U> {
+        Aabb { min: self.min.cast(), max: self.max.cast() }
+    }
+}
+
+/// Box enclosing a group of points, oriented with its own axes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Obb<T: Float = f32> {
+    pub center: Point3<T>,
+    /// Normalized and perpendicular directions of the sides of the box
+    pub axes: [Point3<T>; 3],
+    /// Half of the size of the box along each of its axes
+    pub half_extents: [T; 3],
+}
+
+impl<T: Float> Obb<T> {
+    /// Returns the eight corners of the box
+    pub fn corners(&self) -> [Point3<T>; 8] {
+        let [x, y, z] = self.half_extents;
+        [(-x, -y, -z), (x, -y, -z), (-x, y, -z), (x, y, -z), (-x, -y, z), (x, -y, z), (-x, y, z), (x, y, z)]
+            .map(|(x, y, z)| self.point_at([x, y, z]))
+    }
+
+    /// Returns the point of the box closest to the given one, the point itself if it's inside
+    pub fn closest_point(&self, point: &Point3<T>) -> Point3<T> {
+        let offset = point - &self.center;
+        let mut distances = [T::ZERO; 3];
+        for (i, axis) in self.axes.iter().enumerate() {
+            distances[i] = (&offset).dot(axis).max(-self.half_extents[i]).min(self.half_extents[i]);
+        }
+        self.point_at(distances)
+    }
+
+    /// Returns the box converted to another precision
+    pub fn cast<U: Float>(&self) -> Obb<U> {
+        Obb {
+            center: self.center.cast(),
+            axes: self.axes.each_ref().map(Point3::cast),
+            half_extents: self.half_extents.map(|half| U::from_f64(half.to_f64())),
+        }
+    }
+
+    /// Returns the point at the given distances from the center along each axis
+    fn point_at(&self, distances: [T; 3]) -> Point3<T> {
+        let mut point = self.center.clone();
+        for (axis, distance) in self.axes.iter().zip(distances) {
+            point.x += axis.x * distance;
+            point.y += axis.y * distance;
+            point.z += axis.z * distance;
+        }
+        point
+    }
+}
+
+impl<T: Float> From<&Aabb<T>> for Obb<T> {
+    fn from(aabb: &Aabb<T>) -> Self {
+        let (zero, one, half) = (T::ZERO, T::ONE, T::from_f32(0.5));
+        Self {
+            center: aabb.center(),
+            axes: [
+                Point3 { x: one, y: zero, z: zero },
+                Point3 { x: zero, y: one, z: zero },
+                Point3 { x: zero, y: zero, z: one },
+            ],
+            half_extents: [
+                (aabb.max.x - aabb.min.x) * half,
+                (aabb.max.y - aabb.min.y) * half,
+                (aabb.max.z - aabb.min.z) * half,
+            ],
+        }
+    }
+}
+
+/// Sphere enclosing a group of points
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoundingSphere<T: Float = f32> {
+    pub center: Point3<T>,
+    pub radius: T,
+}
+
+impl<T: Float> BoundingSphere<T> {
+    /// Returns a sphere enclosing all the points, centered in their bounding box
+    ///
+    /// # Arguments
+    /// * `points` - Points to enclose
+    ///
+    pub fn from_points<P: Borrow<Point3<T>>>(points: impl IntoIterator<Item = P> + Clone) -> Self {
+        let center = Aabb::from_points(points.clone()).center();
+        let radius = points.into_iter().map(|point| (point.borrow() - &center).module()).fold(T::ZERO, T::max);
+        Self { center, radius }
+    }
+
+    /// Checks if the point is inside the sphere or on its surface
+    pub fn contains(&self, point: &Point3<T>) -> bool {
+        (point - &self.center).module() <= self.radius
+    }
+
+    /// Returns the sphere converted to another precision
+    pub fn cast<U: Float>(&self) -> BoundingSphere<U> {
+        BoundingSphere { center: self.center.cast(), radius: U::from_f64(self.radius.to_f64()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aabb, BoundingSphere, Obb};
+    use crate::geometry::vector::Point3;
+
+    fn points() -> Vec<Point3> {
+        vec![
+            Point3 { x: -1.0, y: 0.0, z: 2.0 },
+            Point3 { x: 3.0, y: -2.0, z: 0.0 },
+            Point3 { x: 1.0, y: 2.0, z: 1.0 },
+        ]
+    }
+
+    #[test]
+    fn aabb() {
+        let aabb = Aabb::from_points(points());
+        assert_eq!(aabb.min, Point3 { x: -1.0, y: -2.0, z: 0.0 });
+        assert_eq!(aabb.max, Point3 { x: 3.0, y: 2.0, z: 2.0 });
+        assert_eq!(aabb.center(), Point3 { x: 1.0, y: 0.0, z: 1.0 });
+        assert!(points().iter().all(|point| aabb.contains(point)));
+        assert!(!aabb.contains(&Point3 { x: 0.0, y: 0.0, z: 3.0 }));
+        assert_eq!(Aabb::from_points(Vec::<Point3>::new()), Aabb::default());
+    }
+
+    #[test]
+    fn aabb_intersection() {
+        let aabb = Aabb::from_points(points());
+        let touching = Aabb { min: Point3 { x: 3.0, y: 0.0, z: 0.0 }, max: Point3 { x: 4.0, y: 1.0, z: 1.0 } };
+        let apart = Aabb { min: Point3 { x: 3.5, y: 0.0, z: 0.0 }, max: Point3 { x: 4.0, y: 1.0, z: 1.0 } };
+        assert!(aabb.intersects(&touching));
+        assert!(!aabb.intersects(&apart));
+        assert_eq!(aabb.union(&apart).max, Point3 { x: 4.0, y: 2.0, z: 2.0 });
+    }
+
+    #[test]
+    fn aabb_distance() {
+        let aabb = Aabb::from_points(points());
+        assert_eq!(aabb.distance_to(&Point3 { x: 0.0, y: 0.0, z: 1.0 }), 0.0);
+        assert_eq!(aabb.distance_to(&Point3 { x: 6.0, y: 6.0, z: 1.0 }), 5.0);
+    }
+
+    #[test]
+    fn obb() {
+        let aabb = Aabb::from_points(points());
+        let obb = Obb::from(&aabb);
+        assert_eq!(Aabb::from_points(obb.corners()), aabb);
+        assert_eq!(obb.closest_point(&Point3 { x: 0.0, y: 0.5, z: 1.5 }), Point3 { x: 0.0, y: 0.5, z: 1.5 });
+        assert_eq!(obb.closest_point(&Point3 { x: 5.0, y: 0.5, z: -3.0 }), Point3 { x: 3.0, y: 0.5, z: 0.0 });
+
+        // Box of size two rotated 45 degrees around the Z-axis
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+        let rotated = Obb {
+            center: Point3::default(),
+            axes: [
+                Point3 { x: diagonal, y: diagonal, z: 0.0 },
+                Point3 { x: -diagonal, y: diagonal, z: 0.0 },
+                Point3 { x: 0.0, y: 0.0, z: 1.0 },
+            ],
+            half_extents: [1.0, 1.0, 1.0],
+        };
+        let closest = rotated.closest_point(&Point3 { x: 5.0, y: 0.0, z: 0.0 });
+        assert!((closest.x - 2.0f32.sqrt()).abs() < 1e-5 && closest.y.abs() < 1e-5);
+    }
+
+    #[test]
+    fn bounding_sphere() {
+        let sphere = BoundingSphere::from_points(points());
+        assert_eq!(sphere.center, Point3 { x: 1.0, y: 0.0, z: 1.0 });
+        assert!((sphere.radius - 3.0).abs() < 1e-6);
+        assert!(points().iter().all(|point| sphere.contains(point)));
+    }
+}
@@ -5,7 +5,8 @@ use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 pub enum GeometryError {
 	EmptyMesh,
 	MissingValue(String),
-	WrongNumber(String)
+	WrongNumber(String),
+	WrongIndex(String),
 }
 
 impl GeometryError {
@@ -14,6 +15,7 @@ impl GeometryError {
 			Self::EmptyMesh => "The specified mesh is missing points or triangles".to_string(),
 			Self::MissingValue(line) => format!("Missing value on line: {}", line),
 			Self::WrongNumber(line) => format!("Invalid number on line: {}", line),
+			Self::WrongIndex(line) => format!("Invalid index on line: {}", line),
         }
     }
 }
@@ -1,94 +1,583 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::collections::HashMap;
 
 use crate::geometry::geometry_error::GeometryError;
-use crate::math::Matrix4;
+use crate::math::{Float, Matrix4};
 
-use super::Rotation;
+use super::{Aabb, BoundingSphere, MeshProcessing, Obb, Ray, Rotation, VertexBuffer};
+use super::TextureCoordinate;
+use super::Winding;
 use super::triangle::Triangle;
 use super::vector::Point3;
-use super::util::parse_next;
+use super::vector::ops::{Cross, Dot, Module, Normalizable};
+use super::util::{parse_face_vertex, parse_next};
 
-/// Mesh of triangles
+/// Normals of the three vertices of a triangle
+pub type VertexNormals<T = f32> = [Point3<T>; 3];
+
+/// Mesh of triangles. The points and normals are stored once and the triangles refer to them by
+/// their indices, so the vertices shared by several triangles are transformed only once. Both are
+/// kept in [VertexBuffer]s, transformed in place in a single batch.
+///
+/// The geometry has `f32` precision by default. Meshes far from the origin can use `f64` to
+/// keep their detail, they are converted to `f32` only once they are relative to the camera.
 ///
 /// # Properties
 /// * `points` - List of points of the mesh
-/// * `triangles` - List of triangles of the mesh
+/// * `normals` - List of vertex normals of the mesh
+/// * `indices` - Indices of the points of each triangle
+/// * `normal_indices` - Indices of the normals of each vertex of the triangles
+/// * `texture_coordinates` - Texture coordinates of the vertices of each triangle
+/// * `material_library` - MTL file referenced by the mesh
+/// * `material` - Name of the material of the mesh in the library
+/// * `aabb` - Axis-aligned box enclosing the mesh
+/// * `bounding_sphere` - Sphere enclosing the mesh
 ///
-#[derive(Debug)]
-pub struct Mesh {
+#[derive(Clone, Debug)]
+pub struct Mesh<T: Float = f32> {
 	/// List of points conforming the mesh
-	points: Vec<Rc<RefCell<Point3>>>,
-    /// List of triangles conforming the mesh
-    pub triangles: Vec<Triangle>,
+	points: VertexBuffer<T>,
+	/// List of vertex normals of the mesh
+	normals: VertexBuffer<T>,
+    /// Indices of the points of the triangles, three per triangle
+    indices: Vec<u32>,
+    /// Indices of the normals of the vertices, in the same order as the indices of the points
+    normal_indices: Vec<u32>,
+    /// Texture coordinates of the vertices of each triangle, in the same order as the triangles.
+    /// It's empty if any face of the file lacks them.
+    pub texture_coordinates: Vec<[TextureCoordinate; 3]>,
+    /// MTL file referenced by the mesh, relative to its own file
+    material_library: Option<String>,
+    /// Name of the material of the mesh in the library
+    material: Option<String>,
+    /// Axis-aligned box enclosing the points, updated with every transformation
+    aabb: Aabb<T>,
+    /// Sphere enclosing the points, updated with every transformation
+    bounding_sphere: BoundingSphere<T>,
+    /// Axis-aligned box enclosing the points as they were loaded
+    local_aabb: Aabb<T>,
+    /// Rotations applied to the mesh since it was loaded
+    orientation: Matrix4<T>,
 }
 
-impl Mesh {
-    /// Returns a new empty mesh
+impl<T: Float> Mesh<T> {
+    /// Returns a new mesh with the given points and triangles. The vertex normals are computed
+    /// from the faces.
     ///
     /// # Arguments
-    /// * `triangles` - List of triangles of the mesh
+    /// * `points` - List of points of the mesh
+    /// * `indices` - Valid indices of the points of the triangles, three per triangle
     ///
-    fn new(points: Vec<Rc<RefCell<Point3>>>, triangles: Vec<Triangle>) -> Self {
-        Self { points, triangles }
+    pub(super) fn new(points: Vec<Point3<T>>, indices: Vec<u32>) -> Self {
+        let mut mesh = Self {
+            points: points.iter().collect(),
+            normals: VertexBuffer::new(),
+            indices,
+            normal_indices: Vec::new(),
+            texture_coordinates: Vec::new(),
+            material_library: None,
+            material: None,
+            aabb: Aabb::default(),
+            bounding_sphere: BoundingSphere::default(),
+            local_aabb: Aabb::default(),
+            orientation: Matrix4::identity(),
+        };
+        mesh.compute_normals(Winding::default());
+        mesh.update_bounds();
+        mesh.local_aabb = mesh.aabb.clone();
+        mesh
     }
-}
-
-impl Rotation for Mesh {
-	fn rotate(&mut self, rotation: &Matrix4) {
-		for point in &self.points {
-			(*point).borrow_mut().rotate(rotation);
-		}
-	}
-}
-
-impl TryFrom<String> for Mesh {
-    type Error = GeometryError;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
+    /// Returns the mesh described by the content of an OBJ file. Meshes of the default precision
+    /// can also be parsed with [TryFrom].
+    ///
+    /// # Arguments
+    /// * `content` - Content of the file
+    ///
+    pub fn parse(content: &str) -> Result<Self, GeometryError> {
 		let mut points = Vec::new();
-		let mut triangles = Vec::new();
-
-		// TODO this can be optimized using a single loop
+		let mut normals = Vec::new();
+		let mut texture_coordinates = Vec::new();
+		let mut faces = Vec::new();
+		let mut material_library = None;
+		let mut material = None;
 
-		// Populate point list				// TODO make function of parser
-		for line in value.lines() {
+		for line in content.lines() {
 			let mut iter =  line.split_whitespace();
 			match iter.next() {
-				Some("v") => {
-					let x = parse_next(iter.next(), line)?;
-					let y = parse_next(iter.next(), line)?;
-					let z = parse_next(iter.next(), line)?;
-					points.push(Rc::new(RefCell::new(Point3 { x, y, z })));
-				},
-				Some("f") => break,
+				Some("v") => points.push(parse_point(&mut iter, line)?),
+				Some("vn") => normals.push(parse_point(&mut iter, line)?),
+				Some("vt") => texture_coordinates.push(parse_texture_coordinate(&mut iter, line)?),
+				Some("f") => faces.push((line, [
+					parse_face_vertex(iter.next(), line)?,
+					parse_face_vertex(iter.next(), line)?,
+					parse_face_vertex(iter.next(), line)?,
+				])),
+				Some("mtllib") => material_library = iter.next().map(str::to_string),
+				Some("usemtl") if material.is_none() => material = iter.next().map(str::to_string),
 				_ => {}
 			}
 		}
-		// Populate triangle list
-		for line in value.lines() {
-			let mut iter =  line.split_whitespace();
-			if let Some("f") = iter.next() {
-				let first= parse_next::<usize>(iter.next(), line)? - 1;
-				let second = parse_next::<usize>(iter.next(), line)? - 1;
-				let third = parse_next::<usize>(iter.next(), line)? - 1;
-				triangles.push(Triangle(points[first].clone(), points[second].clone(), points[third].clone()));
+
+		if points.is_empty() || faces.is_empty() {
+			return Err(GeometryError::EmptyMesh);
+		}
+
+		// Populate the indices of the triangles
+		let mut indices = Vec::with_capacity(faces.len() * 3);
+		for (line, face) in &faces {
+			if face.iter().any(|vertex| vertex.point >= points.len()) {
+				return Err(GeometryError::WrongIndex(line.to_string()));
 			}
+			indices.extend(face.iter().map(|vertex| vertex.point as u32));
 		}
 
-		if points.is_empty() || triangles.is_empty() {
-			Err(GeometryError::EmptyMesh)
-		} else {
-			Ok(Mesh::new(points, triangles))
+		let mut mesh = Self::new(points, indices);
+
+		// Use the normals of the file for the vertices with them, followed by the computed ones
+		// for the rest
+		let vertices = faces.iter().flat_map(|(line, face)| face.iter().map(move |vertex| (line, vertex)));
+		if vertices.clone().any(|(_, vertex)| vertex.normal.is_some()) {
+			let mut normal_indices = Vec::with_capacity(faces.len() * 3);
+			let mut computed = false;
+			for (line, vertex) in vertices {
+				normal_indices.push(match vertex.normal {
+					Some(i) if i < normals.len() => i as u32,
+					Some(_) => return Err(GeometryError::WrongIndex(line.to_string())),
+					None => {
+						computed = true;
+						(normals.len() + vertex.point) as u32
+					}
+				});
+			}
+			let file_normals = normals.iter().map(|normal| normal.normal());
+			mesh.normals = if computed {
+				file_normals.chain(mesh.normals.iter()).collect()
+			} else {
+				file_normals.collect()
+			};
+			mesh.normal_indices = normal_indices;
 		}
 
+		// Keep the texture coordinates only if all the faces have them
+		let face_coordinates: Option<Vec<[TextureCoordinate; 3]>> = faces.iter()
+			.map(|(_, face)| {
+				let [a, b, c] = face.each_ref()
+					.map(|vertex| vertex.texture.and_then(|i| texture_coordinates.get(i)).copied());
+				Some([a?, b?, c?])
+			})
+			.collect();
+		mesh.texture_coordinates = face_coordinates.unwrap_or_default();
+		mesh.material_library = material_library;
+		mesh.material = material;
+
+		Ok(mesh)
+    }
+
+    /// Computes the normal of each point averaging the normals of the faces sharing it. It
+    /// replaces the normals read from the file, if any.
+    ///
+    /// # Arguments
+    /// * `winding` - Vertex order of the front faces, to point the normals outwards
+    ///
+    pub fn compute_normals(&mut self, winding: Winding) {
+        let mut sums = vec![Point3::default(); self.points.len()];
+        for (triangle, indices) in self.triangles().zip(self.indices.chunks_exact(3)) {
+            let normal = winding.orient(triangle.normal());
+            if normal.x.is_nan() {
+                continue;
+            }
+            for &i in indices {
+                sums[i as usize] += &normal;
+            }
+        }
+
+        self.normals = normalize_sums(&sums);
+        self.normal_indices = self.indices.clone();
+    }
+
+    /// Returns the points of the mesh, shared by the triangles
+    pub fn points(&self) -> &VertexBuffer<T> {
+        &self.points
+    }
+
+    /// Returns the normals of the mesh, shared by the vertices of the triangles
+    pub fn normals(&self) -> &VertexBuffer<T> {
+        &self.normals
+    }
+
+    /// Returns the indices of the points of the triangles, three per triangle
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns the number of triangles of the mesh
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Returns the triangle with the given index, or [None] if there are fewer triangles
+    pub fn triangle(&self, index: usize) -> Option<Triangle<T>> {
+        let indices = self.indices.get(index * 3..index * 3 + 3)?;
+        let [a, b, c] = gather(&self.points, indices);
+        Some(Triangle(a, b, c))
+    }
+
+    /// Returns all the triangles of the mesh, in order
+    pub fn triangles(&self) -> impl ExactSizeIterator<Item = Triangle<T>> + '_ {
+        self.indices.chunks_exact(3).map(|indices| {
+            let [a, b, c] = gather(&self.points, indices);
+            Triangle(a, b, c)
+        })
+    }
+
+    /// Returns the normals of the vertices of each triangle, in the same order as the triangles
+    pub fn vertex_normals(&self) -> impl ExactSizeIterator<Item = VertexNormals<T>> + '_ {
+        self.normal_indices.chunks_exact(3).map(|indices| gather(&self.normals, indices))
+    }
+
+    /// Returns the MTL file referenced by the mesh, relative to its own file
+    pub fn material_library(&self) -> Option<&str> {
+        self.material_library.as_deref()
+    }
+
+    /// Returns the name of the material of the mesh in the library. Only the first material used
+    /// by the file is supported.
+    pub fn material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+
+    /// Returns the axis-aligned box enclosing the mesh
+    pub fn aabb(&self) -> &Aabb<T> {
+        &self.aabb
+    }
+
+    /// Returns the sphere enclosing the mesh
+    pub fn bounding_sphere(&self) -> &BoundingSphere<T> {
+        &self.bounding_sphere
+    }
+
+    /// Returns the box enclosing the mesh as it was loaded, turned and moved with all its
+    /// transformations since
+    pub fn obb(&self) -> Obb<T> {
+        let local = Obb::from(&self.local_aabb);
+        let origin = Point3::default().transform(&self.orientation);
+        Obb {
+            center: local.center.transform(&self.orientation),
+            axes: local.axes.map(|axis| &axis.transform(&self.orientation) - &origin),
+            half_extents: local.half_extents,
+        }
+    }
+
+    /// Moves all the points of the mesh, keeping its normals
+    ///
+    /// # Arguments
+    /// * `offset` - Distance to move in each axis
+    ///
+    pub fn translate(&mut self, offset: &Point3<T>) {
+        let translation = Matrix4::translation(offset.x, offset.y, offset.z);
+        self.points.transform(&translation);
+        self.orientation = &self.orientation * &translation;
+        self.update_bounds();
+    }
+
+    /// Checks if the point is inside the mesh, counting the faces crossed by rays going out of it.
+    /// The mesh must be closed for the result to make sense.
+    ///
+    /// # Arguments
+    /// * `point` - Point to check
+    ///
+    pub fn contains(&self, point: &Point3<T>) -> bool {
+        if !self.aabb.contains(point) {
+            return false;
+        }
+        // Odd crossings mean inside. Several skewed rays vote to dodge the edges and vertices.
+        let directions: [Point3; 3] = [
+            Point3 { x: 0.577, y: 0.583, z: 0.571 },
+            Point3 { x: -0.611, y: 0.532, z: -0.586 },
+            Point3 { x: 0.548, y: -0.619, z: -0.563 },
+        ];
+        let inside = directions.iter()
+            .filter(|direction| {
+                let ray = Ray::new(point.clone(), &direction.cast());
+                let crossings = self.triangles().filter(|triangle| ray.intersect_triangle(triangle).is_some());
+                crossings.count() % 2 == 1
+            })
+            .count();
+        inside >= 2
+    }
+
+    /// Scales the mesh around the origin, keeping its normals
+    ///
+    /// # Arguments
+    /// * `factor` - Positive factor to multiply the coordinates of the points
+    ///
+    pub fn scale(&mut self, factor: T) {
+        self.points.transform(&Matrix4::scaling(factor, factor, factor));
+        // The scaling is moved to the loaded points, so the orientation keeps turning and moving
+        self.local_aabb = Aabb { min: &self.local_aabb.min * factor, max: &self.local_aabb.max * factor };
+        for cell in &mut self.orientation.matrix[3][..3] {
+            *cell *= factor;
+        }
+        self.update_bounds();
+    }
+
+    /// Moves the mesh to have the center of its bounding box in the origin
+    pub fn center(&mut self) {
+        let center = self.aabb.center();
+        self.translate(&-center);
+    }
+
+    /// Centers the mesh and scales it to have one as the longest side of its bounding box.
+    /// Meshes without size are only centered.
+    pub fn normalize(&mut self) {
+        self.center();
+        let size = &self.aabb.max - &self.aabb.min;
+        let longest = size.x.max(size.y).max(size.z);
+        if longest > T::ZERO {
+            self.scale(T::ONE / longest);
+        }
+    }
+
+    /// Merges the points closer than the given distance, so the triangles sharing them are
+    /// connected. Each point is merged with the first one kept within the distance, and the
+    /// points without triangles are removed. Returns the number of points removed.
+    ///
+    /// The normals computed from the faces are averaged as their points are merged, smoothing
+    /// them across the new connections. The ones read from the file are kept.
+    ///
+    /// # Arguments
+    /// * `tolerance` - Greatest distance between two points to merge them
+    ///
+    pub fn weld(&mut self, tolerance: T) -> usize {
+        // Points are hashed in cells of the tolerance size, so only the neighbour cells are checked
+        let size = tolerance.max(T::EPSILON);
+        let cell = |point: &Point3<T>| [point.x, point.y, point.z].map(|value| (value / size).floor().to_f64() as i64);
+        let mut grid: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        let mut points: Vec<Point3<T>> = Vec::new();
+        let mut remap = Vec::with_capacity(self.points.len());
+        for point in self.points.iter() {
+            let [x, y, z] = cell(&point);
+            let neighbours = (x - 1..=x + 1)
+                .flat_map(|x| (y - 1..=y + 1).flat_map(move |y| (z - 1..=z + 1).map(move |z| [x, y, z])));
+            let existing = neighbours
+                .filter_map(|key| grid.get(&key))
+                .flatten()
+                .find(|&&i| points[i as usize].distance(&point) <= tolerance)
+                .copied();
+            remap.push(existing.unwrap_or_else(|| {
+                let i = points.len() as u32;
+                points.push(point);
+                grid.entry([x, y, z]).or_default().push(i);
+                i
+            }));
+        }
+
+        let count = self.points.len();
+        if self.normal_indices == self.indices {
+            let mut sums = vec![Point3::default(); points.len()];
+            for (normal, &new) in self.normals.iter().zip(&remap) {
+                sums[new as usize] += &normal;
+            }
+            self.normals = normalize_sums(&sums);
+            remap_indices(&mut self.normal_indices, &remap);
+        }
+        remap_indices(&mut self.indices, &remap);
+        self.points = points.into_iter().collect();
+        self.remove_unused_points();
+        count - self.points.len()
+    }
+
+    /// Removes the triangles without area, like the ones with a repeated point, and the points
+    /// only they used. Returns the number of triangles removed.
+    pub fn remove_degenerate_triangles(&mut self) -> usize {
+        let keep: Vec<bool> = self.triangles().map(|triangle| !triangle.normal().x.is_nan()).collect();
+        let retain = |indices: &[u32]| -> Vec<u32> {
+            indices.chunks_exact(3).zip(&keep).filter(|(_, &keep)| keep).flat_map(|(triangle, _)| triangle.to_vec()).collect()
+        };
+        self.indices = retain(&self.indices);
+        self.normal_indices = retain(&self.normal_indices);
+        if !self.texture_coordinates.is_empty() {
+            let mut keep = keep.iter();
+            self.texture_coordinates.retain(|_| *keep.next().unwrap_or(&true));
+        }
+        self.remove_unused_points();
+        keep.iter().filter(|&&keep| !keep).count()
+    }
+
+    /// Reverses the vertex order of all the triangles, turning the normals to the other side
+    pub fn flip_winding(&mut self) {
+        for triangle in self.indices.chunks_exact_mut(3).chain(self.normal_indices.chunks_exact_mut(3)) {
+            triangle.swap(1, 2);
+        }
+        for coordinates in &mut self.texture_coordinates {
+            coordinates.swap(1, 2);
+        }
+        self.normals.transform(&Matrix4::scaling(-T::ONE, -T::ONE, -T::ONE));
+    }
+
+    /// Returns the sum of the areas of all the triangles
+    pub fn surface_area(&self) -> T {
+        let half = T::from_f32(0.5);
+        self.triangles()
+            .map(|triangle| (&triangle.1 - &triangle.0).cross(&(&triangle.2 - &triangle.0)).module() * half)
+            .fold(T::ZERO, |sum, area| sum + area)
+    }
+
+    /// Returns the volume enclosed by the mesh. The mesh must be closed and have all its
+    /// triangles in the same vertex order for the result to make sense.
+    pub fn volume(&self) -> T {
+        let signed = self.triangles()
+            .map(|triangle| triangle.0.dot(&triangle.1.cross(&triangle.2)))
+            .fold(T::ZERO, |sum, volume| sum + volume);
+        (signed / T::from_f32(6.0)).abs()
+    }
+
+    /// Applies the enabled operations: welding, removal of degenerate triangles, flipping of the
+    /// winding, centering or normalization and computation of the normals, in that order
+    ///
+    /// # Arguments
+    /// * `processing` - Operations to apply
+    ///
+    pub fn process(&mut self, processing: &MeshProcessing) {
+        if let Some(tolerance) = processing.weld {
+            self.weld(T::from_f32(tolerance));
+        }
+        if processing.remove_degenerates {
+            self.remove_degenerate_triangles();
+        }
+        if processing.flip_winding {
+            self.flip_winding();
+        }
+        if processing.normalize {
+            self.normalize();
+        } else if processing.center {
+            self.center();
+        }
+        if let Some(winding) = processing.recompute_normals {
+            self.compute_normals(winding);
+        }
+    }
+
+    /// Recomputes the bounding volumes from the current position of the points
+    fn update_bounds(&mut self) {
+        self.aabb = Aabb::from_points(self.points.iter());
+        self.bounding_sphere = BoundingSphere::from_points(self.points.iter());
+    }
+
+    /// Removes the points and normals not used by any triangle, so they don't count in the bounds
+    fn remove_unused_points(&mut self) {
+        let points = self.points.len();
+        let normals = self.normals.len();
+        compact(&mut self.points, &mut self.indices);
+        compact(&mut self.normals, &mut self.normal_indices);
+        if self.points.len() == points && self.normals.len() == normals {
+            return;
+        }
+        self.update_bounds();
+
+        // The box as loaded is found undoing the orientation. Its rotation is orthonormal, so the
+        // inverse is the transpose.
+        let m = &self.orientation.matrix;
+        let local = self.points.iter().map(|point| {
+            let p = [point.x - m[3][0], point.y - m[3][1], point.z - m[3][2]];
+            let [x, y, z] = [0, 1, 2].map(|i| p[0] * m[i][0] + p[1] * m[i][1] + p[2] * m[i][2]);
+            Point3 { x, y, z }
+        });
+        self.local_aabb = Aabb::from_points(local);
+    }
+}
+
+/// Returns the values at the three indices
+fn gather<T: Float>(values: &VertexBuffer<T>, indices: &[u32]) -> [Point3<T>; 3] {
+    [0, 1, 2].map(|i| values.get(indices[i] as usize).expect("The indices of the mesh are valid"))
+}
+
+/// Returns the sums of normals normalized, keeping the empty ones
+fn normalize_sums<T: Float>(sums: &[Point3<T>]) -> VertexBuffer<T> {
+    sums.iter().map(|sum| if sum.module() > T::ZERO { sum.normal() } else { sum.clone() }).collect()
+}
+
+/// Replaces the indices by their new values
+fn remap_indices(indices: &mut [u32], remap: &[u32]) {
+    for index in indices {
+        *index = remap[*index as usize];
+    }
+}
+
+/// Removes the values not referenced by the indices, keeping the order of the rest, and updates
+/// the indices to their new positions
+fn compact<T: Float>(values: &mut VertexBuffer<T>, indices: &mut [u32]) {
+    let mut used = vec![false; values.len()];
+    for &index in indices.iter() {
+        used[index as usize] = true;
+    }
+    if used.iter().all(|&used| used) {
+        return;
+    }
+    let mut remap = vec![0; values.len()];
+    let mut kept = 0;
+    for (i, &used) in used.iter().enumerate() {
+        if used {
+            values.swap(kept, i);
+            remap[i] = kept as u32;
+            kept += 1;
+        }
+    }
+    values.truncate(kept);
+    remap_indices(indices, &remap);
+}
+
+impl<T: Float> Rotation for Mesh<T> {
+	fn rotate(&mut self, rotation: &Matrix4) {
+		let rotation = rotation.cast();
+		self.points.transform(&rotation);
+		self.normals.transform(&rotation);
+		self.orientation = &self.orientation * &rotation;
+		self.update_bounds();
+	}
+}
+
+impl TryFrom<String> for Mesh {
+    type Error = GeometryError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
     }
 }
 
+/// Reads the next three values of the line as a point
+fn parse_point<'a, T: Float>(iter: &mut impl Iterator<Item = &'a str>, line: &str) -> Result<Point3<T>, GeometryError> {
+	let x = parse_next(iter.next(), line)?;
+	let y = parse_next(iter.next(), line)?;
+	let z = parse_next(iter.next(), line)?;
+	Ok(Point3 { x, y, z })
+}
+
+/// Reads the next values of the line as a texture coordinate, the second one is optional
+fn parse_texture_coordinate<'a>(
+	iter: &mut impl Iterator<Item = &'a str>,
+	line: &str,
+) -> Result<TextureCoordinate, GeometryError> {
+	let u = parse_next(iter.next(), line)?;
+	let v = match iter.next() {
+		Some(value) => parse_next(Some(value), line)?,
+		None => 0.0,
+	};
+	Ok(TextureCoordinate { u, v })
+}
+
 #[cfg(test)]
 mod tests {
     use super::Mesh;
     use crate::geometry::geometry_error::GeometryError;
+    use crate::geometry::vector::Point3;
+    use crate::geometry::{MeshProcessing, Rotation, TextureCoordinate, Winding};
+    use crate::math::builders::{RotationAxis, RotationMatrixBuilder};
+
+    /// Unit cube from the origin, with two triangles per face
+    const CUBE: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 0 0 1\nv 1 0 1\nv 1 1 1\nv 0 1 1\n\
+        f 1 3 2\nf 1 4 3\nf 5 6 7\nf 5 7 8\nf 1 2 6\nf 1 6 5\n\
+        f 4 7 3\nf 4 8 7\nf 1 5 8\nf 1 8 4\nf 2 3 7\nf 2 7 6\n";
 
     #[test]
     fn valid_parsing() {
@@ -102,11 +591,16 @@ mod tests {
 			f 3 4 1
 			f 4 1 2
 			".to_string()).unwrap();
-        assert_eq!(mesh.triangles.len(), 4);
-		assert_eq!(mesh.triangles[0].1, mesh.triangles[1].0);
-		assert_eq!(mesh.triangles[1].1, mesh.triangles[2].0);
-		assert_eq!(mesh.triangles[2].1, mesh.triangles[3].0);
-		assert_eq!(mesh.triangles[3].1, mesh.triangles[0].0);
+        assert_eq!(mesh.triangle_count(), 4);
+        assert_eq!(mesh.indices(), &[0, 1, 2, 1, 2, 3, 2, 3, 0, 3, 0, 1]);
+        // The triangles share the points of the mesh
+        let shared = |a: usize, b: usize| mesh.triangle(a).unwrap().1 == mesh.triangle(b).unwrap().0;
+		assert!(shared(0, 1));
+		assert!(shared(1, 2));
+		assert!(shared(2, 3));
+		assert!(shared(3, 0));
+        assert!(mesh.triangle(4).is_none());
+        assert_eq!(mesh.triangles().len(), 4);
     }
 
     #[test]
@@ -124,4 +618,286 @@ mod tests {
             GeometryError::MissingValue("v 0 0".to_string())
         );
     }
+
+    #[test]
+    fn send_and_sync() {
+        fn shared_between_threads<T: Send + Sync>() {}
+        shared_between_threads::<Mesh>();
+    }
+
+    #[test]
+    fn computed_normals() {
+        let mesh = Mesh::try_from("
+			v 0.0 0.0 0.0
+			v 0.0 1.0 0.0
+			v 1.0 0.0 0.0
+			v 0.0 0.0 1.0
+			f 1 2 3
+			f 1 4 2
+			".to_string()).unwrap();
+        let vertex_normals: Vec<_> = mesh.vertex_normals().collect();
+        assert_eq!(vertex_normals[0][0], vertex_normals[1][0]);
+        assert_eq!(vertex_normals[0][2], Point3 { x: 0.0, y: 0.0, z: -1.0 });
+        assert_eq!(vertex_normals[1][1], Point3 { x: -1.0, y: 0.0, z: 0.0 });
+        let shared = &vertex_normals[0][0];
+        assert!((shared.x + std::f32::consts::FRAC_1_SQRT_2).abs() < 0.001);
+        assert!((shared.z + std::f32::consts::FRAC_1_SQRT_2).abs() < 0.001);
+    }
+
+    #[test]
+    fn file_normals() {
+        let mesh = Mesh::try_from("
+			v 0.0 0.0 0.0
+			v 0.0 1.0 0.0
+			v 1.0 0.0 0.0
+			vn 0.0 0.0 2.0
+			vn 0.0 1.0 0.0
+			f 1//1 2//2 3//1
+			".to_string()).unwrap();
+        let [a, b, c] = mesh.vertex_normals().next().unwrap();
+        assert_eq!(a, Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(b, Point3 { x: 0.0, y: 1.0, z: 0.0 });
+        assert_eq!(a, c);
+        assert_eq!(mesh.normals().len(), 2);
+    }
+
+    #[test]
+    fn texture_coordinates() {
+        let mesh = Mesh::try_from("
+			mtllib ship.mtl
+			usemtl hull
+			v 0.0 0.0 0.0
+			v 0.0 1.0 0.0
+			v 1.0 0.0 0.0
+			vt 0.0 0.0
+			vt 0.5 1.0
+			vt 1.0
+			f 1/1 2/2 3/3
+			usemtl glass
+			f 3/3 2/2 1/1
+			".to_string()).unwrap();
+        assert_eq!(mesh.texture_coordinates.len(), 2);
+        assert_eq!(mesh.texture_coordinates[0][1], TextureCoordinate { u: 0.5, v: 1.0 });
+        assert_eq!(mesh.texture_coordinates[1][0], TextureCoordinate { u: 1.0, v: 0.0 });
+        assert_eq!(mesh.material_library(), Some("ship.mtl"));
+        assert_eq!(mesh.material(), Some("hull"));
+    }
+
+    #[test]
+    fn missing_texture_coordinates() {
+        let mesh = Mesh::try_from("
+			v 0.0 0.0 0.0
+			v 0.0 1.0 0.0
+			v 1.0 0.0 0.0
+			vt 0.0 0.0
+			f 1/1 2/1 3/1
+			f 3 2 1
+			".to_string()).unwrap();
+        assert!(mesh.texture_coordinates.is_empty());
+        assert_eq!(mesh.material_library(), None);
+    }
+
+    #[test]
+    fn invalid_face_index() {
+        assert_eq!(
+            Mesh::try_from("v 0 0 0\nf 1 2 3".to_string()).unwrap_err(),
+            GeometryError::WrongIndex("f 1 2 3".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_normal_index() {
+        assert_eq!(
+            Mesh::try_from("v 0 0 0\nv 0 1 0\nv 1 0 0\nvn 0 0 1\nf 1//1 2//1 3//2".to_string()).unwrap_err(),
+            GeometryError::WrongIndex("f 1//1 2//1 3//2".to_string())
+        );
+    }
+
+    #[test]
+    fn partial_file_normals() {
+        // Only the faces without normals in the file use the computed ones
+        let mesh = Mesh::try_from("
+			v 0.0 0.0 0.0
+			v 0.0 1.0 0.0
+			v 1.0 0.0 0.0
+			v 0.0 0.0 1.0
+			vn 0.0 1.0 0.0
+			f 1//1 2//1 3//1
+			f 1 2 4
+			".to_string()).unwrap();
+        let vertex_normals: Vec<_> = mesh.vertex_normals().collect();
+        assert!(vertex_normals[0].iter().all(|normal| *normal == Point3 { x: 0.0, y: 1.0, z: 0.0 }));
+        assert_eq!(vertex_normals[1][2].x.abs(), 1.0);
+        assert_eq!(mesh.normals().len(), 5);
+    }
+
+    #[test]
+    fn bounds_follow_rotation() {
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 2 0 0\nv 0 1 0\nf 1 2 3".to_string()).unwrap();
+        assert_eq!(mesh.aabb().max, Point3 { x: 2.0, y: 1.0, z: 0.0 });
+        assert_eq!(mesh.bounding_sphere().center, Point3 { x: 1.0, y: 0.5, z: 0.0 });
+
+        let rotation = RotationMatrixBuilder::new()
+            .in_axis(RotationAxis::Y)
+            .with_theta(std::f32::consts::FRAC_PI_2)
+            .build();
+        mesh.rotate(&rotation);
+        // The point at (2, 0, 0) is now at (0, 0, -2)
+        let aabb = mesh.aabb();
+        assert!(aabb.max.x.abs() < 1e-6);
+        assert!((aabb.min.z + 2.0).abs() < 1e-6);
+        assert!((mesh.bounding_sphere().center.z + 1.0).abs() < 1e-6);
+
+        // The oriented box keeps its size, turned with the mesh
+        let obb = mesh.obb();
+        assert_eq!(obb.half_extents, [1.0, 0.5, 0.0]);
+        assert!((obb.axes[0].z + 1.0).abs() < 1e-6);
+        assert!((obb.center.z + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bounds_follow_translation() {
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 2 0 0\nv 0 1 0\nf 1 2 3".to_string()).unwrap();
+        let normal = mesh.normals().get(0);
+        mesh.translate(&Point3 { x: 1.0, y: -2.0, z: 3.0 });
+
+        assert_eq!(mesh.triangle(0).unwrap().1, Point3 { x: 3.0, y: -2.0, z: 3.0 });
+        assert_eq!(mesh.normals().get(0), normal);
+        assert_eq!(mesh.aabb().min, Point3 { x: 1.0, y: -2.0, z: 3.0 });
+        assert_eq!(mesh.bounding_sphere().center, Point3 { x: 2.0, y: -1.5, z: 3.0 });
+
+        let obb = mesh.obb();
+        assert_eq!(obb.center, Point3 { x: 2.0, y: -1.5, z: 3.0 });
+        assert_eq!(obb.axes[0], Point3 { x: 1.0, y: 0.0, z: 0.0 });
+    }
+
+    #[test]
+    fn contains_point() {
+        let cube = Mesh::try_from(CUBE.to_string()).unwrap();
+        assert!(cube.contains(&Point3 { x: 0.5, y: 0.5, z: 0.5 }));
+        assert!(cube.contains(&Point3 { x: 0.1, y: 0.9, z: 0.2 }));
+        assert!(cube.contains(&Point3 { x: 0.5, y: 0.5, z: 0.9 }));
+        assert!(!cube.contains(&Point3 { x: 1.5, y: 0.5, z: 0.5 }));
+        assert!(!cube.contains(&Point3 { x: -0.1, y: 0.5, z: 0.5 }));
+    }
+
+    #[test]
+    fn weld_close_points() {
+        // Two triangles sharing an edge, with its points repeated and slightly apart
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1.0001 0 0\nv 1 1 0\nv 0 1.0001 0\n\
+            f 1 2 3\nf 4 5 6".to_string()).unwrap();
+        assert_eq!(mesh.weld(0.001), 2);
+        assert_eq!(mesh.points().len(), 4);
+        assert_eq!(mesh.indices(), &[0, 1, 2, 1, 3, 2]);
+        assert_eq!(mesh.triangle(0).unwrap().1, mesh.triangle(1).unwrap().0);
+        assert_eq!(mesh.weld(0.0), 0);
+    }
+
+    #[test]
+    fn weld_smooths_normals() {
+        // Two faces of a roof sharing its ridge, with the points of the ridge repeated
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 1 1 0\nv 1 1 1\nv 1 1 0\nv 2 0 0\nv 1 1 1\n\
+            f 1 2 3\nf 4 5 6".to_string()).unwrap();
+        assert_eq!(mesh.weld(0.001), 2);
+        assert_eq!(mesh.normals().len(), mesh.points().len());
+
+        // The ridge has the same normal on both faces, halfway between them
+        let [_, ridge, _] = mesh.vertex_normals().next().unwrap();
+        let [other, _, _] = mesh.vertex_normals().nth(1).unwrap();
+        assert_eq!(ridge, other);
+        assert!(ridge.x.abs() < 1e-5 && ridge.z.abs() < 1e-5 && (ridge.y.abs() - 1.0).abs() < 1e-5);
+
+        let mut recomputed = mesh.clone();
+        recomputed.compute_normals(Winding::default());
+        for (welded, smooth) in mesh.normals().iter().zip(recomputed.normals().iter()) {
+            assert!(welded.distance(&smooth) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn degenerate_triangles() {
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nv 2 0 0\n\
+            vt 0 0\nvt 1 0\nvt 0 1\n\
+            f 1/1 2/2 3/3\nf 1/1 2/2 4/3\nf 3/1 3/2 2/3\nf 3/3 2/2 1/1".to_string()).unwrap();
+        assert_eq!(mesh.remove_degenerate_triangles(), 2);
+        assert_eq!(mesh.indices(), &[0, 1, 2, 2, 1, 0]);
+        assert_eq!(mesh.vertex_normals().len(), 2);
+        assert_eq!(mesh.texture_coordinates[1][0], TextureCoordinate { u: 0.0, v: 1.0 });
+
+        // The point only used by the removed triangles doesn't count in the bounds
+        assert_eq!(mesh.points().len(), 3);
+        assert_eq!(mesh.normals().len(), 3);
+        assert_eq!(mesh.aabb().max, Point3 { x: 1.0, y: 1.0, z: 0.0 });
+        assert_eq!(mesh.obb().half_extents, [0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn unused_points_after_transformations() {
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nv 5 0 0\nf 1 2 3\nf 1 4 4".to_string()).unwrap();
+        mesh.translate(&Point3 { x: 1.0, y: 2.0, z: 3.0 });
+        mesh.rotate(&RotationMatrixBuilder::new().in_axis(RotationAxis::Z).with_theta(std::f32::consts::FRAC_PI_2).build());
+        mesh.remove_degenerate_triangles();
+        assert_eq!(mesh.points().len(), 3);
+        let obb = mesh.obb();
+        assert!(obb.half_extents.iter().zip([0.5, 0.5, 0.0]).all(|(half, expected)| (half - expected).abs() < 1e-5));
+        assert!(obb.center.distance(&mesh.aabb().center()) < 1e-5);
+    }
+
+    #[test]
+    fn center_and_normalize() {
+        let content = "v 2 1 0\nv 6 1 0\nv 2 3 1\nf 1 2 3";
+        let mut centered = Mesh::try_from(content.to_string()).unwrap();
+        centered.center();
+        assert_eq!(centered.aabb().min, Point3 { x: -2.0, y: -1.0, z: -0.5 });
+        assert_eq!(centered.aabb().max, Point3 { x: 2.0, y: 1.0, z: 0.5 });
+
+        let mut normalized = Mesh::try_from(content.to_string()).unwrap();
+        let normal = normalized.normals().get(0);
+        normalized.normalize();
+        assert_eq!(normalized.aabb().min, Point3 { x: -0.5, y: -0.25, z: -0.125 });
+        assert_eq!(normalized.aabb().max, Point3 { x: 0.5, y: 0.25, z: 0.125 });
+        assert_eq!(normalized.normals().get(0), normal);
+        let obb = normalized.obb();
+        assert_eq!(obb.center, Point3::default());
+        assert_eq!(obb.axes[0], Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(obb.half_extents, [0.5, 0.25, 0.125]);
+    }
+
+    #[test]
+    fn flipped_winding() {
+        let mut mesh = Mesh::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvt 0 1\nf 1/1 2/2 3/3".to_string()).unwrap();
+        let normal = mesh.triangle(0).unwrap().normal();
+        mesh.flip_winding();
+        assert_eq!(mesh.indices(), &[0, 2, 1]);
+        assert_eq!(mesh.triangle(0).unwrap().normal(), -&normal);
+        assert_eq!(mesh.normals().get(0), Some(-&normal));
+        assert_eq!(mesh.texture_coordinates[0][1], TextureCoordinate { u: 0.0, v: 1.0 });
+    }
+
+    #[test]
+    fn area_and_volume() {
+        let mut cube = Mesh::try_from(CUBE.to_string()).unwrap();
+        assert!((cube.surface_area() - 6.0).abs() < 1e-5);
+        assert!((cube.volume() - 1.0).abs() < 1e-5);
+        cube.scale(2.0);
+        assert!((cube.surface_area() - 24.0).abs() < 1e-4);
+        assert!((cube.volume() - 8.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn processing() {
+        let mut mesh = Mesh::try_from(CUBE.to_string()).unwrap();
+        mesh.translate(&Point3 { x: 10.0, y: 0.0, z: 0.0 });
+        let processing = MeshProcessing::new()
+            .with_weld(0.01)
+            .with_degenerates_removed()
+            .with_normalization()
+            .with_recomputed_normals(Winding::CounterClockwise);
+        mesh.process(&processing);
+        assert_eq!(mesh.points().len(), 8);
+        assert_eq!(mesh.triangle_count(), 12);
+        assert_eq!(mesh.aabb().center(), Point3::default());
+        assert_eq!(mesh.aabb().max, Point3 { x: 0.5, y: 0.5, z: 0.5 });
+        assert!(mesh.contains(&Point3 { x: 0.4, y: -0.4, z: 0.0 }));
+    }
 }
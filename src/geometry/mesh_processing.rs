@@ -0,0 +1,62 @@
+use super::Winding;
+
+/// Operations applied to a mesh with [Mesh::process](super::Mesh::process), like the ones loaded
+/// by the engine, to fix the differences between the models. By default, none.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MeshProcessing {
+    /// Distance to merge the points closer than it, if they have to be welded
+    pub weld: Option<f32>,
+    /// Removes the triangles without area
+    pub remove_degenerates: bool,
+    /// Reverses the vertex order of the triangles
+    pub flip_winding: bool,
+    /// Moves the center of the bounding box to the origin
+    pub center: bool,
+    /// Centers and scales the mesh so the longest side of its bounding box is one
+    pub normalize: bool,
+    /// Vertex order of the front faces to compute the normals again, if they have to be
+    pub recompute_normals: Option<Winding>,
+}
+
+impl MeshProcessing {
+    /// Returns the options without any operation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges the points closer than the given distance
+    pub fn with_weld(mut self, tolerance: f32) -> Self {
+        self.weld = Some(tolerance);
+        self
+    }
+
+    /// Removes the triangles without area
+    pub fn with_degenerates_removed(mut self) -> Self {
+        self.remove_degenerates = true;
+        self
+    }
+
+    /// Reverses the vertex order of the triangles
+    pub fn with_flipped_winding(mut self) -> Self {
+        self.flip_winding = true;
+        self
+    }
+
+    /// Moves the center of the bounding box to the origin
+    pub fn with_centering(mut self) -> Self {
+        self.center = true;
+        self
+    }
+
+    /// Centers and scales the mesh to fit in a box of size one
+    pub fn with_normalization(mut self) -> Self {
+        self.normalize = true;
+        self
+    }
+
+    /// Computes the normals again from the faces with the given vertex order
+    pub fn with_recomputed_normals(mut self, winding: Winding) -> Self {
+        self.recompute_normals = Some(winding);
+        self
+    }
+}
@@ -1,11 +1,26 @@
-pub use mesh::Mesh;
+pub use bounds::{Aabb, BoundingSphere, Obb};
+pub use mesh::{Mesh, VertexNormals};
+pub use mesh_processing::MeshProcessing;
+pub use plane::Plane;
 pub use projectable::Projectable;
+pub use ray::Ray;
 pub use rotation::Rotation;
+pub use texture_coordinate::TextureCoordinate;
+pub use vertex_buffer::VertexBuffer;
+pub use winding::Winding;
 
+mod bounds;
 mod geometry_error;
 mod mesh;
+mod mesh_processing;
+mod plane;
 mod projectable;
+mod ray;
 mod rotation;
+mod texture_coordinate;
 mod util;
+mod vertex_buffer;
+mod winding;
+pub mod primitives;
 pub mod triangle;
 pub mod vector;
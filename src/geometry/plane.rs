@@ -0,0 +1,45 @@
+use crate::geometry::vector::ops::{Dot, Normalizable};
+use crate::geometry::vector::Point3;
+use crate::math::Float;
+
+/// Infinite plane, made of the points `p` where `normal · p + distance = 0`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plane<T: Float = f32> {
+    /// Normalized vector perpendicular to the plane, pointing to its front side
+    pub normal: Point3<T>,
+    /// Signed distance from the origin to the plane, along the normal
+    pub distance: T,
+}
+
+impl<T: Float> Plane<T> {
+    /// Returns the plane with the given normal going through a point
+    ///
+    /// # Arguments
+    /// * `point` - Point of the plane
+    /// * `normal` - Vector perpendicular to the plane, of any length but zero
+    ///
+    pub fn new(point: &Point3<T>, normal: &Point3<T>) -> Self {
+        let normal = normal.normal();
+        let distance = -(&normal).dot(point);
+        Self { normal, distance }
+    }
+
+    /// Returns the distance from the point to the plane, negative if it's behind it
+    pub fn signed_distance(&self, point: &Point3<T>) -> T {
+        (&self.normal).dot(point) + self.distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Plane;
+    use crate::geometry::vector::Point3;
+
+    #[test]
+    fn signed_distance() {
+        let plane = Plane::new(&Point3 { x: 0.0, y: 2.0, z: 0.0 }, &Point3 { x: 0.0, y: 3.0, z: 0.0 });
+        assert_eq!(plane.normal, Point3 { x: 0.0, y: 1.0, z: 0.0 });
+        assert_eq!(plane.signed_distance(&Point3 { x: 5.0, y: 5.0, z: 1.0 }), 3.0);
+        assert_eq!(plane.signed_distance(&Point3 { x: 5.0, y: 0.0, z: 1.0 }), -2.0);
+    }
+}
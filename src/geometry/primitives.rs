@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+use super::vector::ops::Normalizable;
+use super::vector::Point3;
+use super::Mesh;
+
+/// Fewest segments around the round primitives
+const MIN_SEGMENTS: u32 = 3;
+
+/// Returns a cube centered in the origin. Each face has its own points, so it keeps its normal
+/// with smooth shading.
+///
+/// # Arguments
+/// * `size` - Length of the sides
+///
+pub fn cube(size: f32) -> Mesh {
+    let half = size * 0.5;
+    let axes = [
+        Point3 { x: half, y: 0.0, z: 0.0 },
+        Point3 { x: 0.0, y: half, z: 0.0 },
+        Point3 { x: 0.0, y: 0.0, z: half },
+    ];
+    let mut builder = Builder::default();
+    for i in 0..3 {
+        let (normal, u, v) = (&axes[i], &axes[(i + 1) % 3], &axes[(i + 2) % 3]);
+        // Both sides of each axis, with the tangents swapped to keep the faces outwards
+        for (normal, u, v) in [(normal.clone(), u, v), (-normal, v, u)] {
+            let a = builder.point(&(&normal - u) - v);
+            let b = builder.point(&(&normal + u) - v);
+            let c = builder.point(&(&normal + u) + v);
+            let d = builder.point(&(&normal - u) + v);
+            builder.quad(a, b, c, d);
+        }
+    }
+    builder.build()
+}
+
+/// Returns a flat grid on the XZ plane centered in the origin, facing up
+///
+/// # Arguments
+/// * `width` - Size along the X-axis
+/// * `depth` - Size along the Z-axis
+/// * `columns` - Number of cells along the X-axis, at least one
+/// * `rows` - Number of cells along the Z-axis, at least one
+///
+pub fn plane(width: f32, depth: f32, columns: u32, rows: u32) -> Mesh {
+    let (columns, rows) = (columns.max(1), rows.max(1));
+    let mut builder = Builder::default();
+    for column in 0..=columns {
+        for row in 0..=rows {
+            builder.point(Point3 {
+                x: width * (column as f32 / columns as f32 - 0.5),
+                y: 0.0,
+                z: depth * (row as f32 / rows as f32 - 0.5),
+            });
+        }
+    }
+    let index = |column: u32, row: u32| column * (rows + 1) + row;
+    for column in 0..columns {
+        for row in 0..rows {
+            builder.quad(index(column, row), index(column, row + 1), index(column + 1, row + 1), index(column + 1, row));
+        }
+    }
+    builder.build()
+}
+
+/// Returns a sphere centered in the origin made of rings of points from pole to pole
+///
+/// # Arguments
+/// * `radius` - Radius of the sphere
+/// * `segments` - Number of points of each ring, at least three
+/// * `rings` - Number of divisions from pole to pole, at least two
+///
+pub fn uv_sphere(radius: f32, segments: u32, rings: u32) -> Mesh {
+    let (segments, rings) = (segments.max(MIN_SEGMENTS), rings.max(2));
+    let mut builder = Builder::default();
+    let north = builder.point(Point3 { x: 0.0, y: radius, z: 0.0 });
+    let latitudes: Vec<u32> = (1..rings)
+        .map(|ring| {
+            let angle = std::f32::consts::PI * ring as f32 / rings as f32;
+            builder.ring(radius * angle.cos(), radius * angle.sin(), segments)
+        })
+        .collect();
+    let south = builder.point(Point3 { x: 0.0, y: -radius, z: 0.0 });
+
+    builder.fan(north, latitudes[0], segments, true);
+    for pair in latitudes.windows(2) {
+        builder.band(pair[0], pair[1], segments);
+    }
+    builder.fan(south, latitudes[latitudes.len() - 1], segments, false);
+    builder.build()
+}
+
+/// Returns a sphere centered in the origin made of triangles of similar size, dividing each face
+/// of an icosahedron in four as many times as requested. Each subdivision quadruples the triangles.
+///
+/// # Arguments
+/// * `radius` - Radius of the sphere
+/// * `subdivisions` - Times to divide the faces, zero for the icosahedron
+///
+pub fn icosphere(radius: f32, subdivisions: u32) -> Mesh {
+    let t = (1.0 + 5.0f32.sqrt()) * 0.5;
+    let corners = [
+        (-1.0, t, 0.0), (1.0, t, 0.0), (-1.0, -t, 0.0), (1.0, -t, 0.0),
+        (0.0, -1.0, t), (0.0, 1.0, t), (0.0, -1.0, -t), (0.0, 1.0, -t),
+        (t, 0.0, -1.0), (t, 0.0, 1.0), (-t, 0.0, -1.0), (-t, 0.0, 1.0),
+    ];
+    let mut faces: Vec<[u32; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+    let mut builder = Builder::default();
+    for (x, y, z) in corners {
+        builder.point(&Point3 { x, y, z }.normal() * radius);
+    }
+
+    for _ in 0..subdivisions {
+        // The middle of each edge is shared by the two faces on its sides
+        let mut middles: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut middle = |builder: &mut Builder, a: u32, b: u32| {
+            *middles.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let point = &builder.points[a as usize] + &builder.points[b as usize];
+                builder.point(&point.normal() * radius)
+            })
+        };
+        faces = faces.iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (middle(&mut builder, a, b), middle(&mut builder, b, c), middle(&mut builder, c, a));
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+    for [a, b, c] in faces {
+        builder.triangle(a, b, c);
+    }
+    builder.build()
+}
+
+/// Returns a closed cylinder centered in the origin along the Y-axis. The caps have their own
+/// points, so they keep their normals with smooth shading.
+///
+/// # Arguments
+/// * `radius` - Radius of the caps
+/// * `height` - Distance between the caps
+/// * `segments` - Number of points around each cap, at least three
+///
+pub fn cylinder(radius: f32, height: f32, segments: u32) -> Mesh {
+    let segments = segments.max(MIN_SEGMENTS);
+    let (top, bottom) = (height * 0.5, -height * 0.5);
+    let mut builder = Builder::default();
+    builder.cap(top, radius, segments, true);
+    let upper = builder.ring(top, radius, segments);
+    let lower = builder.ring(bottom, radius, segments);
+    builder.band(upper, lower, segments);
+    builder.cap(bottom, radius, segments, false);
+    builder.build()
+}
+
+/// Returns a closed cone centered in the origin along the Y-axis, with the tip up
+///
+/// # Arguments
+/// * `radius` - Radius of the base
+/// * `height` - Distance from the base to the tip
+/// * `segments` - Number of points around the base, at least three
+///
+pub fn cone(radius: f32, height: f32, segments: u32) -> Mesh {
+    let segments = segments.max(MIN_SEGMENTS);
+    let mut builder = Builder::default();
+    let tip = builder.point(Point3 { x: 0.0, y: height * 0.5, z: 0.0 });
+    let base = builder.ring(-height * 0.5, radius, segments);
+    builder.fan(tip, base, segments, true);
+    builder.cap(-height * 0.5, radius, segments, false);
+    builder.build()
+}
+
+/// Returns a torus centered in the origin around the Y-axis
+///
+/// # Arguments
+/// * `radius` - Distance from the center to the middle of the tube
+/// * `tube_radius` - Radius of the tube
+/// * `segments` - Number of divisions around the center, at least three
+/// * `sides` - Number of divisions around the tube, at least three
+///
+pub fn torus(radius: f32, tube_radius: f32, segments: u32, sides: u32) -> Mesh {
+    let (segments, sides) = (segments.max(MIN_SEGMENTS), sides.max(MIN_SEGMENTS));
+    let mut builder = Builder::default();
+    for segment in 0..segments {
+        let angle = TAU * segment as f32 / segments as f32;
+        for side in 0..sides {
+            let tube_angle = TAU * side as f32 / sides as f32;
+            let distance = radius + tube_radius * tube_angle.cos();
+            builder.point(Point3 { x: distance * angle.cos(), y: tube_radius * tube_angle.sin(), z: distance * angle.sin() });
+        }
+    }
+    let index = |segment: u32, side: u32| (segment % segments) * sides + side % sides;
+    for segment in 0..segments {
+        for side in 0..sides {
+            builder.quad(
+                index(segment, side),
+                index(segment, side + 1),
+                index(segment + 1, side + 1),
+                index(segment + 1, side),
+            );
+        }
+    }
+    builder.build()
+}
+
+/// Returns an arrow from the origin pointing up the Y-axis: a cylindrical shaft ended by a cone
+/// twice as wide. The head is four times the radius long, up to half the arrow.
+///
+/// # Arguments
+/// * `length` - Distance from the origin to the tip
+/// * `radius` - Radius of the shaft
+/// * `segments` - Number of points around the shaft and the head, at least three
+///
+pub fn arrow(length: f32, radius: f32, segments: u32) -> Mesh {
+    let segments = segments.max(MIN_SEGMENTS);
+    let neck = length - (radius * 4.0).min(length * 0.5);
+    let mut builder = Builder::default();
+    builder.cap(0.0, radius, segments, false);
+    let shaft_top = builder.ring(neck, radius, segments);
+    let shaft_bottom = builder.ring(0.0, radius, segments);
+    builder.band(shaft_top, shaft_bottom, segments);
+
+    // Ring under the head around the shaft
+    let inner = builder.ring(neck, radius, segments);
+    let outer = builder.ring(neck, radius * 2.0, segments);
+    builder.band(outer, inner, segments);
+
+    let tip = builder.point(Point3 { x: 0.0, y: length, z: 0.0 });
+    let head = builder.ring(neck, radius * 2.0, segments);
+    builder.fan(tip, head, segments, true);
+    builder.build()
+}
+
+/// Points and triangles of a primitive being generated. The triangles are added with their
+/// vertices in clockwise order seen from the outside, the default [Winding](super::Winding).
+#[derive(Default)]
+struct Builder {
+    points: Vec<Point3>,
+    indices: Vec<u32>,
+}
+
+impl Builder {
+    /// Adds a point and returns its index
+    fn point(&mut self, point: Point3) -> u32 {
+        self.points.push(point);
+        self.points.len() as u32 - 1
+    }
+
+    fn triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.indices.extend([a, b, c]);
+    }
+
+    /// Adds two triangles with the four points, in order around the quad
+    fn quad(&mut self, a: u32, b: u32, c: u32, d: u32) {
+        self.triangle(a, b, c);
+        self.triangle(a, c, d);
+    }
+
+    /// Adds a horizontal circle of points around the Y-axis and returns the index of the first one
+    fn ring(&mut self, y: f32, radius: f32, segments: u32) -> u32 {
+        let first = self.points.len() as u32;
+        for segment in 0..segments {
+            let angle = TAU * segment as f32 / segments as f32;
+            self.point(Point3 { x: radius * angle.cos(), y, z: radius * angle.sin() });
+        }
+        first
+    }
+
+    /// Joins a ring with the ring under it or, for two rings at the same height, with the one
+    /// inside it to face down
+    fn band(&mut self, upper: u32, lower: u32, segments: u32) {
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+            self.quad(upper + segment, upper + next, lower + next, lower + segment);
+        }
+    }
+
+    /// Joins a ring with a point on the Y-axis, facing up or down
+    fn fan(&mut self, center: u32, ring: u32, segments: u32, up: bool) {
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+            if up {
+                self.triangle(center, ring + next, ring + segment);
+            } else {
+                self.triangle(center, ring + segment, ring + next);
+            }
+        }
+    }
+
+    /// Adds a flat disc around the Y-axis, facing up or down
+    fn cap(&mut self, y: f32, radius: f32, segments: u32, up: bool) {
+        let center = self.point(Point3 { x: 0.0, y, z: 0.0 });
+        let ring = self.ring(y, radius, segments);
+        self.fan(center, ring, segments, up);
+    }
+
+    fn build(self) -> Mesh {
+        Mesh::new(self.points, self.indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::f32::consts::PI;
+
+    use super::{arrow, cone, cube, cylinder, icosphere, plane, torus, uv_sphere};
+    use crate::geometry::vector::ops::{Cross, Dot};
+    use crate::geometry::vector::Point3;
+    use crate::geometry::Mesh;
+
+    /// Checks that the mesh is closed and all its faces point outwards, as every edge is shared
+    /// by two triangles going through it in opposite directions and the signed volume is positive
+    fn assert_closed_outwards(mesh: &Mesh) {
+        let mut welded = mesh.clone();
+        welded.weld(1e-5);
+        let edges: Vec<(u32, u32)> = welded.indices()
+            .chunks_exact(3)
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect();
+        let unique: HashSet<&(u32, u32)> = edges.iter().collect();
+        assert_eq!(unique.len(), edges.len(), "repeated edge");
+        assert!(edges.iter().all(|(a, b)| unique.contains(&(*b, *a))), "open edge");
+        let signed: f32 = mesh.triangles().map(|t| t.0.dot(&t.1.cross(&t.2))).sum();
+        assert!(signed > 0.0, "faces pointing inwards");
+        assert!(mesh.triangles().all(|triangle| !triangle.normal().x.is_nan()), "degenerate triangle");
+    }
+
+    fn assert_close(value: f32, expected: f32, tolerance: f32) {
+        assert!((value - expected).abs() <= expected * tolerance, "{} is not close to {}", value, expected);
+    }
+
+    #[test]
+    fn cube_mesh() {
+        let mesh = cube(2.0);
+        assert_closed_outwards(&mesh);
+        assert_eq!(mesh.triangle_count(), 12);
+        assert_eq!(mesh.aabb().max, Point3 { x: 1.0, y: 1.0, z: 1.0 });
+        assert_close(mesh.volume(), 8.0, 1e-5);
+        assert_close(mesh.surface_area(), 24.0, 1e-5);
+        // Each face keeps its own normal
+        assert!(mesh.normals().iter().all(|normal| [normal.x, normal.y, normal.z].iter().filter(|v| v.abs() == 1.0).count() == 1));
+    }
+
+    #[test]
+    fn plane_mesh() {
+        let mesh = plane(4.0, 2.0, 4, 3);
+        assert_eq!(mesh.points().len(), 20);
+        assert_eq!(mesh.triangle_count(), 24);
+        assert_eq!(mesh.aabb().min, Point3 { x: -2.0, y: 0.0, z: -1.0 });
+        assert_eq!(mesh.aabb().max, Point3 { x: 2.0, y: 0.0, z: 1.0 });
+        assert!(mesh.triangles().all(|triangle| triangle.normal() == Point3 { x: 0.0, y: 1.0, z: 0.0 }));
+        assert_close(mesh.surface_area(), 8.0, 1e-5);
+        assert_eq!(plane(1.0, 1.0, 0, 0).triangle_count(), 2);
+    }
+
+    #[test]
+    fn sphere_meshes() {
+        let uv = uv_sphere(2.0, 32, 16);
+        assert_closed_outwards(&uv);
+        assert_eq!(uv.points().len(), 32 * 15 + 2);
+        assert_close(uv.volume(), 4.0 / 3.0 * PI * 8.0, 0.03);
+
+        let ico = icosphere(2.0, 3);
+        assert_closed_outwards(&ico);
+        assert_eq!(ico.triangle_count(), 20 * 64);
+        assert_eq!(ico.points().len(), 10 * 64 + 2);
+        assert!(ico.points().iter().all(|point| (point.distance(&Point3::default()) - 2.0).abs() < 1e-5));
+        assert_close(ico.volume(), 4.0 / 3.0 * PI * 8.0, 0.03);
+        assert_eq!(icosphere(1.0, 0).triangle_count(), 20);
+    }
+
+    #[test]
+    fn round_meshes() {
+        let cylinder = cylinder(1.0, 2.0, 64);
+        assert_closed_outwards(&cylinder);
+        assert_close(cylinder.volume(), PI * 2.0, 0.01);
+        assert_eq!(cylinder.aabb().min.y, -1.0);
+
+        let cone = cone(1.0, 3.0, 64);
+        assert_closed_outwards(&cone);
+        assert_close(cone.volume(), PI, 0.01);
+        assert_eq!(cone.aabb().max.y, 1.5);
+
+        let torus = torus(2.0, 0.5, 48, 24);
+        assert_closed_outwards(&torus);
+        assert_close(torus.volume(), 2.0 * PI * PI * 2.0 * 0.25, 0.02);
+        assert_eq!(torus.triangle_count(), 48 * 24 * 2);
+    }
+
+    #[test]
+    fn arrow_mesh() {
+        let mesh = arrow(2.0, 0.1, 16);
+        assert_closed_outwards(&mesh);
+        assert_eq!(mesh.aabb().min.y, 0.0);
+        assert_eq!(mesh.aabb().max.y, 2.0);
+        assert_close(mesh.aabb().max.x, 0.2, 1e-5);
+        // The tip of a short arrow is half of it
+        assert_close(arrow(0.2, 0.1, 16).aabb().max.y, 0.2, 1e-5);
+    }
+
+    #[test]
+    fn minimum_subdivisions() {
+        assert_eq!(uv_sphere(1.0, 0, 0).triangle_count(), 6);
+        assert_eq!(cone(1.0, 1.0, 1).triangle_count(), 6);
+    }
+}
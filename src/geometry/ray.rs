@@ -0,0 +1,230 @@
+use crate::geometry::triangle::Triangle;
+use crate::geometry::vector::ops::{Cross, Dot, Normalizable};
+use crate::geometry::vector::Point3;
+use crate::geometry::{Aabb, BoundingSphere, Mesh, Plane};
+use crate::math::Float;
+
+/// Half-line starting in a point and going infinitely in one direction
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ray<T: Float = f32> {
+    pub origin: Point3<T>,
+    /// Normalized direction of the ray
+    pub direction: Point3<T>,
+}
+
+impl<T: Float> Ray<T> {
+    /// Returns a new ray, normalizing the direction
+    ///
+    /// # Arguments
+    /// * `origin` - Start of the ray
+    /// * `direction` - Direction of the ray, of any length but zero
+    ///
+    pub fn new(origin: Point3<T>, direction: &Point3<T>) -> Self {
+        Self { origin, direction: direction.normal() }
+    }
+
+    /// Returns the ray converted to another precision
+    pub fn cast<U: Float>(&self) -> Ray<U> {
+        Ray { origin: self.origin.cast(), direction: self.direction.cast() }
+    }
+
+    /// Returns the point of the ray at the given distance from its origin
+    pub fn point_at(&self, distance: T) -> Point3<T> {
+        Point3 {
+            x: self.origin.x + self.direction.x * distance,
+            y: self.origin.y + self.direction.y * distance,
+            z: self.origin.z + self.direction.z * distance,
+        }
+    }
+
+    /// Returns the distance from the origin to the point where the ray enters the box, zero if
+    /// it starts inside, or [None] if it misses the box
+    pub fn intersect_aabb(&self, aabb: &Aabb<T>) -> Option<T> {
+        let axes = [
+            (self.origin.x, self.direction.x, aabb.min.x, aabb.max.x),
+            (self.origin.y, self.direction.y, aabb.min.y, aabb.max.y),
+            (self.origin.z, self.direction.z, aabb.min.z, aabb.max.z),
+        ];
+        let (mut near, mut far) = (T::ZERO, T::INFINITY);
+        for (origin, direction, min, max) in axes {
+            if direction == T::ZERO {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+            let (t1, t2) = ((min - origin) / direction, (max - origin) / direction);
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+            if near > far {
+                return None;
+            }
+        }
+        Some(near)
+    }
+
+    /// Returns the distance from the origin to the point where the ray enters the sphere, zero if
+    /// it starts inside, or [None] if it misses the sphere
+    pub fn intersect_sphere(&self, sphere: &BoundingSphere<T>) -> Option<T> {
+        let to_center = &sphere.center - &self.origin;
+        let projection = (&to_center).dot(&self.direction);
+        let squared_distance = (&to_center).dot(&to_center) - projection * projection;
+        let squared_radius = sphere.radius * sphere.radius;
+        if squared_distance > squared_radius {
+            return None;
+        }
+        let half_chord = (squared_radius - squared_distance).sqrt();
+        let (near, far) = (projection - half_chord, projection + half_chord);
+        if far < T::ZERO {
+            return None;
+        }
+        Some(near.max(T::ZERO))
+    }
+
+    /// Returns the distance from the origin to the point where the ray crosses the plane, from any
+    /// of its sides, or [None] if it's parallel or goes away from it
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<T> {
+        let speed = (&plane.normal).dot(&self.direction);
+        if speed.abs() < T::EPSILON {
+            return None;
+        }
+        let distance = -plane.signed_distance(&self.origin) / speed;
+        (distance >= T::ZERO).then_some(distance)
+    }
+
+    /// Returns the distance from the origin to the point where the ray crosses the triangle, from
+    /// any of its sides, or [None] if it misses it
+    pub fn intersect_triangle(&self, triangle: &Triangle<T>) -> Option<T> {
+        self.intersect_points(&triangle.0, &triangle.1, &triangle.2)
+    }
+
+    /// Returns the index of the closest triangle of the mesh crossed by the ray and the distance
+    /// to it, or [None] if it misses all of them
+    pub fn intersect_mesh(&self, mesh: &Mesh<T>) -> Option<(usize, T)> {
+        self.intersect_aabb(mesh.aabb())?;
+        mesh.triangles()
+            .enumerate()
+            .filter_map(|(i, triangle)| self.intersect_triangle(&triangle).map(|distance| (i, distance)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// Returns the distance to the triangle with the given vertices with the Möller–Trumbore
+    /// algorithm
+    fn intersect_points(&self, a: &Point3<T>, b: &Point3<T>, c: &Point3<T>) -> Option<T> {
+        let edge_ab = b - a;
+        let edge_ac = c - a;
+        let p = (&self.direction).cross(&edge_ac);
+        let determinant = (&edge_ab).dot(&p);
+        if determinant.abs() < T::EPSILON {
+            // Parallel to the triangle
+            return None;
+        }
+        let inverse = T::ONE / determinant;
+        let t = &self.origin - a;
+        let u = (&t).dot(&p) * inverse;
+        if !(T::ZERO..=T::ONE).contains(&u) {
+            return None;
+        }
+        let q = (&t).cross(&edge_ab);
+        let v = (&self.direction).dot(&q) * inverse;
+        if v < T::ZERO || u + v > T::ONE {
+            return None;
+        }
+        let distance = (&edge_ac).dot(&q) * inverse;
+        (distance >= T::ZERO).then_some(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ray;
+    use crate::geometry::triangle::Triangle;
+    use crate::geometry::vector::Point3;
+    use crate::geometry::{Aabb, BoundingSphere, Mesh, Plane};
+
+    fn points(corners: [[f32; 3]; 3]) -> [Point3; 3] {
+        corners.map(|[x, y, z]| Point3 { x, y, z })
+    }
+
+    fn unit_box() -> Aabb {
+        Aabb { min: Point3 { x: -1.0, y: -1.0, z: -1.0 }, max: Point3 { x: 1.0, y: 1.0, z: 1.0 } }
+    }
+
+    #[test]
+    fn normalized_direction() {
+        let ray = Ray::new(Point3::default(), &Point3 { x: 0.0, y: 3.0, z: 4.0 });
+        assert_eq!(ray.direction, Point3 { x: 0.0, y: 0.6, z: 0.8 });
+        assert_eq!(ray.point_at(5.0), Point3 { x: 0.0, y: 3.0, z: 4.0 });
+    }
+
+    #[test]
+    fn aabb_intersection() {
+        let hit = Ray::new(Point3 { x: 0.0, y: 0.0, z: -5.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(hit.intersect_aabb(&unit_box()), Some(4.0));
+
+        let inside = Ray::new(Point3::default(), &Point3 { x: 1.0, y: 1.0, z: 0.0 });
+        assert_eq!(inside.intersect_aabb(&unit_box()), Some(0.0));
+
+        let behind = Ray::new(Point3 { x: 0.0, y: 0.0, z: 5.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(behind.intersect_aabb(&unit_box()), None);
+
+        let aside = Ray::new(Point3 { x: 2.0, y: 0.0, z: -5.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(aside.intersect_aabb(&unit_box()), None);
+    }
+
+    #[test]
+    fn sphere_intersection() {
+        let sphere = BoundingSphere { center: Point3 { x: 0.0, y: 0.0, z: 5.0 }, radius: 2.0 };
+        let forward = Point3 { x: 0.0, y: 0.0, z: 1.0 };
+        assert_eq!(Ray::new(Point3::default(), &forward).intersect_sphere(&sphere), Some(3.0));
+        assert_eq!(Ray::new(Point3 { x: 0.0, y: 0.0, z: 5.0 }, &forward).intersect_sphere(&sphere), Some(0.0));
+        assert_eq!(Ray::new(Point3 { x: 0.0, y: 0.0, z: 8.0 }, &forward).intersect_sphere(&sphere), None);
+        assert_eq!(Ray::new(Point3 { x: 3.0, y: 0.0, z: 0.0 }, &forward).intersect_sphere(&sphere), None);
+    }
+
+    #[test]
+    fn plane_intersection() {
+        let plane = Plane::new(&Point3 { x: 0.0, y: 1.0, z: 0.0 }, &Point3 { x: 0.0, y: 1.0, z: 0.0 });
+        let down = Ray::new(Point3 { x: 4.0, y: 5.0, z: 2.0 }, &Point3 { x: 0.0, y: -1.0, z: 0.0 });
+        assert_eq!(down.intersect_plane(&plane), Some(4.0));
+        let up = Ray::new(Point3::default(), &Point3 { x: 0.0, y: 2.0, z: 0.0 });
+        assert_eq!(up.intersect_plane(&plane), Some(1.0));
+        let away = Ray::new(Point3 { x: 4.0, y: 5.0, z: 2.0 }, &Point3 { x: 0.0, y: 1.0, z: 0.0 });
+        assert_eq!(away.intersect_plane(&plane), None);
+        let parallel = Ray::new(Point3::default(), &Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(parallel.intersect_plane(&plane), None);
+    }
+
+    #[test]
+    fn triangle_intersection() {
+        let [a, b, c] = points([[0.0, 0.0, 2.0], [1.0, 0.0, 2.0], [0.0, 1.0, 2.0]]);
+        let triangle = Triangle(a.clone(), b.clone(), c.clone());
+        let reversed = Triangle(a, c, b);
+        let forward = Point3 { x: 0.0, y: 0.0, z: 1.0 };
+        let hit = Ray::new(Point3 { x: 0.25, y: 0.25, z: 0.0 }, &forward);
+        assert_eq!(hit.intersect_triangle(&triangle), Some(2.0));
+        // Both sides are hit
+        assert_eq!(hit.intersect_triangle(&reversed), Some(2.0));
+
+        let miss = Ray::new(Point3 { x: 0.75, y: 0.75, z: 0.0 }, &forward);
+        assert_eq!(miss.intersect_triangle(&triangle), None);
+
+        let behind = Ray::new(Point3 { x: 0.25, y: 0.25, z: 3.0 }, &forward);
+        assert_eq!(behind.intersect_triangle(&triangle), None);
+
+        let parallel = Ray::new(Point3 { x: 0.25, y: 0.25, z: 2.0 }, &Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(parallel.intersect_triangle(&triangle), None);
+    }
+
+    #[test]
+    fn mesh_intersection() {
+        let content = "v 0 0 1\nv 1 0 1\nv 0 1 1\n\
+            v 0 0 3\nv 1 0 3\nv 0 1 3\n\
+            f 4 5 6\nf 1 2 3\n";
+        let mesh = Mesh::try_from(content.to_string()).unwrap();
+        let hit = Ray::new(Point3 { x: 0.2, y: 0.2, z: 0.0 }, &Point3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(hit.intersect_mesh(&mesh), Some((1, 1.0)));
+        let miss = Ray::new(Point3 { x: 0.2, y: 0.2, z: 0.0 }, &Point3 { x: 0.0, y: 0.0, z: -1.0 });
+        assert_eq!(miss.intersect_mesh(&mesh), None);
+    }
+}
@@ -0,0 +1,27 @@
+use std::ops::{Add, Mul};
+
+/// Coordinates of a point of a texture, from zero to one in both axes. The origin is the
+/// lower-left corner of the image, as in the OBJ files.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextureCoordinate {
+    pub u: f32,
+    pub v: f32,
+}
+
+// Coordinate addition
+impl Add for TextureCoordinate {
+    type Output = TextureCoordinate;
+
+    fn add(self, rhs: TextureCoordinate) -> Self::Output {
+        TextureCoordinate { u: self.u + rhs.u, v: self.v + rhs.v }
+    }
+}
+
+// Coordinate scaling
+impl Mul<f32> for TextureCoordinate {
+    type Output = TextureCoordinate;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        TextureCoordinate { u: self.u * rhs, v: self.v * rhs }
+    }
+}
@@ -1,61 +1,105 @@
 use crate::geometry::vector::Point3;
-use crate::geometry::vector::ops::{Cross, Normalizable};
-use crate::math::Matrix4;
+use crate::geometry::vector::ops::{Cross, Dot, Normalizable};
+use crate::math::{Float, Matrix4};
 use super::Projectable;
-use std::cell::RefCell;
-use std::rc::Rc;
 
-/// Three-dimensional triangle composed with three [Point3] references
-#[derive(Debug)]
-pub struct Triangle(pub Rc<RefCell<Point3>>, pub Rc<RefCell<Point3>>, pub Rc<RefCell<Point3>>);
+/// Three-dimensional triangle of three [Point3], usually copied from the shared points of its mesh
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triangle<T: Float = f32>(pub Point3<T>, pub Point3<T>, pub Point3<T>);
 
-impl Triangle {
+impl<T: Float> Triangle<T> {
 
     /// Returns the normal vector of the triangle
-    pub fn normal(&self) -> Point3 {
-        let line_0_1 = &*self.1.borrow() - &*self.0.borrow();
-        let line_0_2 = &*self.2.borrow() - &*self.0.borrow();
+    pub fn normal(&self) -> Point3<T> {
+        let line_0_1 = &self.1 - &self.0;
+        let line_0_2 = &self.2 - &self.0;
         (&line_0_1).cross(&line_0_2).normal()
     }
 
     /// Returns a vector of the same plane as the triangle
-    pub fn plain_component(&self) -> Point3 {
-        self.0.borrow().clone()
+    pub fn plain_component(&self) -> Point3<T> {
+        self.0.clone()
+    }
+
+    /// Returns the point of the triangle closest to the given one
+    ///
+    /// # Arguments
+    /// * `point` - Point to approach, anywhere in the space
+    ///
+    pub fn closest_point(&self, point: &Point3<T>) -> Point3<T> {
+        let (a, b, c) = (&self.0, &self.1, &self.2);
+        let zero = T::ZERO;
+        let along = |origin: &Point3<T>, vector: &Point3<T>, t: T| Point3 {
+            x: origin.x + vector.x * t,
+            y: origin.y + vector.y * t,
+            z: origin.z + vector.z * t,
+        };
+        let ab = b - a;
+        let ac = c - a;
+
+        // Regions of the vertices and the edges, by the projection of the point on the edges
+        let ap = point - a;
+        let (d1, d2) = ((&ab).dot(&ap), (&ac).dot(&ap));
+        if d1 <= zero && d2 <= zero {
+            return a.clone();
+        }
+        let bp = point - b;
+        let (d3, d4) = ((&ab).dot(&bp), (&ac).dot(&bp));
+        if d3 >= zero && d4 <= d3 {
+            return b.clone();
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= zero && d1 >= zero && d3 <= zero {
+            return along(a, &ab, d1 / (d1 - d3));
+        }
+        let cp = point - c;
+        let (d5, d6) = ((&ab).dot(&cp), (&ac).dot(&cp));
+        if d6 >= zero && d5 <= d6 {
+            return c.clone();
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= zero && d2 >= zero && d6 <= zero {
+            return along(a, &ac, d2 / (d2 - d6));
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= zero && d4 - d3 >= zero && d5 - d6 >= zero {
+            return along(b, &(c - b), (d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // Inside the face, by its barycentric coordinates
+        let denominator = T::ONE / (va + vb + vc);
+        along(&along(a, &ab, vb * denominator), &ac, vc * denominator)
+    }
+
+    /// Returns the triangle converted to another precision
+    pub fn cast<U: Float>(&self) -> Triangle<U> {
+        Triangle(self.0.cast(), self.1.cast(), self.2.cast())
     }
 
 }
 
 pub type TriangleProjection = (Point3, Point3, Point3);
-impl Projectable<TriangleProjection> for Triangle {
+impl<T: Float> Projectable<TriangleProjection> for Triangle<T> {
     fn get_projection(&self, matrix: &Matrix4, offset: f32) -> TriangleProjection {
         (
-            self.0.borrow().get_projection(matrix, offset),
-            self.1.borrow().get_projection(matrix, offset),
-            self.2.borrow().get_projection(matrix, offset),
+            self.0.get_projection(matrix, offset),
+            self.1.get_projection(matrix, offset),
+            self.2.get_projection(matrix, offset),
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::cell::RefCell;
-    use std::rc::Rc;
-
     use crate::geometry::triangle::Triangle;
     use crate::geometry::vector::Point3;
     
-	macro_rules! wrap {
-		($point:tt) => {
-			Rc::new(RefCell::new($point))
-		};
-	}
-
     #[test]
     fn normal() {
         let point_a = Point3 { x: 0.0, y: 0.0, z: 0.0 };
         let point_b = Point3 { x: 3.0, y: 2.0, z: 1.0 };
         let point_c = Point3 { x: 1.0, y: 2.0, z: 3.0 };
-		let triangle = Triangle(wrap!(point_a), wrap!(point_b), wrap!(point_c));
+		let triangle: Triangle = Triangle(point_a, point_b, point_c);
 
         let expected = Point3 { x: 0.41, y: -0.82, z: 0.41 };
         let normal = triangle.normal();
@@ -64,13 +108,31 @@ mod tests {
         assert!((normal.y - expected.y).abs() < 0.01);
         assert!((normal.z - expected.z).abs() < 0.01);
     }
+
+    #[test]
+    fn closest_point() {
+        let point_a = Point3 { x: 0.0, y: 0.0, z: 0.0 };
+        let point_b = Point3 { x: 2.0, y: 0.0, z: 0.0 };
+        let point_c = Point3 { x: 0.0, y: 2.0, z: 0.0 };
+		let triangle = Triangle(point_a, point_b, point_c);
+        let closest = |x, y, z| triangle.closest_point(&Point3 { x, y, z });
+
+        // Face, vertices and edges
+        assert_eq!(closest(0.5, 0.5, 3.0), Point3 { x: 0.5, y: 0.5, z: 0.0 });
+        assert_eq!(closest(-1.0, -1.0, 1.0), Point3 { x: 0.0, y: 0.0, z: 0.0 });
+        assert_eq!(closest(3.0, -1.0, 0.0), Point3 { x: 2.0, y: 0.0, z: 0.0 });
+        assert_eq!(closest(-1.0, 5.0, 0.0), Point3 { x: 0.0, y: 2.0, z: 0.0 });
+        assert_eq!(closest(1.0, -1.0, 0.0), Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(closest(-1.0, 1.0, 0.0), Point3 { x: 0.0, y: 1.0, z: 0.0 });
+        assert_eq!(closest(2.0, 2.0, 0.0), Point3 { x: 1.0, y: 1.0, z: 0.0 });
+    }
 /*
 	#[test]
 	fn plain_component() {
 		let point_a = Point3 { x: 0.0, y: 0.0, z: 0.0 };
         let point_b = Point3 { x: 3.0, y: 2.0, z: 1.0 };
         let point_c = Point3 { x: 1.0, y: 2.0, z: 3.0 };
-		let triangle = Triangle(&point_a, &point_b, &point_c);
+		let triangle = Triangle(point_a, point_b, point_c);
 
 		assert_eq!(&point_a, triangle.plain_component());
 	}
@@ -80,7 +142,7 @@ mod tests {
 		let point_a = Point3 { x: 0.0, y: 0.0, z: 0.0 };
         let point_b = Point3 { x: 3.0, y: 2.0, z: 1.0 };
         let point_c = Point3 { x: 1.0, y: 2.0, z: 3.0 };
-		let triangle = Triangle(&point_a, &point_b, &point_c);
+		let triangle = Triangle(point_a, point_b, point_c);
 		
 		let matrix = ferrux_projection_matrix::ProjectionMatrixBuilder::new().build();
 		let projection = triangle.get_projection(&Matrix4::new(matrix), 1.0);
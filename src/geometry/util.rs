@@ -9,9 +9,39 @@ pub(crate) fn parse_next<T: FromStr>(slice: Option<&str>, line: &str) -> Result<
         .map_err(|_| GeometryError::WrongNumber(line.to_string()))
 }
 
+/// Indices of a vertex of a face, like `v`, `v/vt`, `v//vn` or `v/vt/vn`
+#[derive(Debug, PartialEq)]
+pub(crate) struct FaceVertex {
+    pub point: usize,
+    pub texture: Option<usize>,
+    pub normal: Option<usize>,
+}
+
+/// Parses the next vertex of a face, turning the one-based indices into zero-based ones
+pub(crate) fn parse_face_vertex(slice: Option<&str>, line: &str) -> Result<FaceVertex, GeometryError> {
+    let slice = slice.ok_or_else(|| GeometryError::MissingValue(line.to_string()))?;
+    let mut indices = slice.split('/');
+    let point = parse_index(indices.next(), line)?
+        .ok_or_else(|| GeometryError::MissingValue(line.to_string()))?;
+    let texture = parse_index(indices.next(), line)?;
+    let normal = parse_index(indices.next(), line)?;
+    Ok(FaceVertex { point, texture, normal })
+}
+
+/// Parses an optional one-based index into a zero-based one
+fn parse_index(slice: Option<&str>, line: &str) -> Result<Option<usize>, GeometryError> {
+    match slice {
+        None | Some("") => Ok(None),
+        Some(index) => match parse_next::<usize>(Some(index), line)? {
+            0 => Err(GeometryError::WrongIndex(line.to_string())),
+            index => Ok(Some(index - 1)),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_next;
+    use super::{parse_face_vertex, parse_next, FaceVertex};
     use crate::geometry::geometry_error::GeometryError;
 
     #[test]
@@ -35,4 +65,32 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn parse_face_vertex_formats() {
+        assert_eq!(
+            parse_face_vertex(Some("3"), "line").unwrap(),
+            FaceVertex { point: 2, texture: None, normal: None }
+        );
+        assert_eq!(
+            parse_face_vertex(Some("3/1"), "line").unwrap(),
+            FaceVertex { point: 2, texture: Some(0), normal: None }
+        );
+        assert_eq!(
+            parse_face_vertex(Some("3//2"), "line").unwrap(),
+            FaceVertex { point: 2, texture: None, normal: Some(1) }
+        );
+        assert_eq!(
+            parse_face_vertex(Some("3/1/2"), "line").unwrap(),
+            FaceVertex { point: 2, texture: Some(0), normal: Some(1) }
+        );
+    }
+
+    #[test]
+    fn parse_face_vertex_invalid() {
+        assert_eq!(parse_face_vertex(None, "line").unwrap_err(), GeometryError::MissingValue("line".to_string()));
+        assert_eq!(parse_face_vertex(Some("/1"), "line").unwrap_err(), GeometryError::MissingValue("line".to_string()));
+        assert_eq!(parse_face_vertex(Some("0"), "line").unwrap_err(), GeometryError::WrongIndex("line".to_string()));
+        assert_eq!(parse_face_vertex(Some("1/a"), "line").unwrap_err(), GeometryError::WrongNumber("line".to_string()));
+    }
 }
@@ -0,0 +1,185 @@
+/// Implements the arithmetic operators, the [Vector](crate::geometry::vector::Vector) operations
+/// and the common helpers of a vector type generic over its [Float](crate::math::Float) components.
+/// Every binary operator accepts owned and borrowed operands.
+macro_rules! impl_vector {
+	($point:ident { $($axis:ident),+ }) => {
+		impl_vector!(@binary $point, Add, add, +, $($axis),+);
+		impl_vector!(@binary $point, Sub, sub, -, $($axis),+);
+		impl_vector!(@assign $point, AddAssign, add_assign, +=, $($axis),+);
+		impl_vector!(@assign $point, SubAssign, sub_assign, -=, $($axis),+);
+		impl_vector!(@scalar $point, Mul, mul, MulAssign, mul_assign, *, $($axis),+);
+		impl_vector!(@scalar $point, Div, div, DivAssign, div_assign, /, $($axis),+);
+
+		// Vector negation
+		impl<T: $crate::math::Float> std::ops::Neg for &$point<T> {
+			type Output = $point<T>;
+
+			fn neg(self) -> Self::Output {
+				$point { $($axis: -self.$axis),+ }
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::Neg for $point<T> {
+			type Output = $point<T>;
+
+			fn neg(self) -> Self::Output {
+				-&self
+			}
+		}
+
+		// Scalar multiplication from the left
+		impl_vector!(@left_scalar $point, f32);
+		impl_vector!(@left_scalar $point, f64);
+
+		// Vector dot-product
+		impl<'a, T: $crate::math::Float> $crate::geometry::vector::ops::Dot<&'a $point<T>> for &'a $point<T> {
+			type Output = T;
+
+			fn dot(self, rhs: &'a $point<T>) -> Self::Output {
+				T::ZERO $(+ self.$axis * rhs.$axis)+
+			}
+		}
+
+		// Vector module
+		impl<'a, T: $crate::math::Float> $crate::geometry::vector::ops::Module for &'a $point<T> {
+			type Output = T;
+
+			fn module(self) -> T {
+				$crate::geometry::vector::ops::Dot::dot(self, self).sqrt()
+			}
+		}
+
+		// Vector normal
+		impl<'a, T: $crate::math::Float> $crate::geometry::vector::ops::Normalizable<&'a $point<T>> for &'a $point<T> {
+			type Output = $point<T>;
+
+			fn normal(self) -> Self::Output {
+				self / $crate::geometry::vector::ops::Module::module(self)
+			}
+		}
+
+		impl<'a, T: $crate::math::Float> $crate::geometry::vector::Vector<&'a $point<T>> for &'a $point<T> {}
+
+		impl<T: $crate::math::Float> $point<T> {
+			/// Returns the point at the given fraction of the way to another one
+			///
+			/// # Arguments
+			/// * `other` - Point reached at one
+			/// * `t` - Fraction of the way, zero for this point
+			///
+			pub fn lerp(&self, other: &Self, t: T) -> Self {
+				self + &((other - self) * t)
+			}
+
+			/// Returns the distance to another point
+			pub fn distance(&self, other: &Self) -> T {
+				$crate::geometry::vector::ops::Module::module(&(other - self))
+			}
+
+			/// Returns the vector bounced off a surface
+			///
+			/// # Arguments
+			/// * `normal` - Normalized vector perpendicular to the surface
+			///
+			pub fn reflect(&self, normal: &Self) -> Self {
+				let dot = $crate::geometry::vector::ops::Dot::dot(self, normal);
+				self - &(normal * (dot + dot))
+			}
+
+			/// Returns the vector converted to another precision
+			pub fn cast<U: $crate::math::Float>(&self) -> $point<U> {
+				$point { $($axis: U::from_f64(self.$axis.to_f64())),+ }
+			}
+		}
+	};
+
+	(@binary $point:ident, $trait:ident, $method:ident, $op:tt, $($axis:ident),+) => {
+		impl<T: $crate::math::Float> std::ops::$trait<&$point<T>> for &$point<T> {
+			type Output = $point<T>;
+
+			fn $method(self, rhs: &$point<T>) -> Self::Output {
+				$point { $($axis: self.$axis $op rhs.$axis),+ }
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::$trait<$point<T>> for &$point<T> {
+			type Output = $point<T>;
+
+			fn $method(self, rhs: $point<T>) -> Self::Output {
+				self $op &rhs
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::$trait<&$point<T>> for $point<T> {
+			type Output = $point<T>;
+
+			fn $method(self, rhs: &$point<T>) -> Self::Output {
+				&self $op rhs
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::$trait<$point<T>> for $point<T> {
+			type Output = $point<T>;
+
+			fn $method(self, rhs: $point<T>) -> Self::Output {
+				&self $op &rhs
+			}
+		}
+	};
+
+	(@assign $point:ident, $trait:ident, $method:ident, $op:tt, $($axis:ident),+) => {
+		impl<T: $crate::math::Float> std::ops::$trait<&$point<T>> for $point<T> {
+			fn $method(&mut self, rhs: &$point<T>) {
+				$(self.$axis $op rhs.$axis;)+
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::$trait<$point<T>> for $point<T> {
+			fn $method(&mut self, rhs: $point<T>) {
+				$(self.$axis $op rhs.$axis;)+
+			}
+		}
+	};
+
+	(@scalar $point:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt, $($axis:ident),+) => {
+		impl<T: $crate::math::Float> std::ops::$trait<T> for &$point<T> {
+			type Output = $point<T>;
+
+			fn $method(self, rhs: T) -> Self::Output {
+				$point { $($axis: self.$axis $op rhs),+ }
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::$trait<T> for $point<T> {
+			type Output = $point<T>;
+
+			fn $method(self, rhs: T) -> Self::Output {
+				&self $op rhs
+			}
+		}
+
+		impl<T: $crate::math::Float> std::ops::$assign_trait<T> for $point<T> {
+			fn $assign_method(&mut self, rhs: T) {
+				$(self.$axis = self.$axis $op rhs;)+
+			}
+		}
+	};
+
+	(@left_scalar $point:ident, $float:ty) => {
+		impl std::ops::Mul<&$point<$float>> for $float {
+			type Output = $point<$float>;
+
+			fn mul(self, rhs: &$point<$float>) -> Self::Output {
+				rhs * self
+			}
+		}
+
+		impl std::ops::Mul<$point<$float>> for $float {
+			type Output = $point<$float>;
+
+			fn mul(self, rhs: $point<$float>) -> Self::Output {
+				&rhs * self
+			}
+		}
+	};
+}
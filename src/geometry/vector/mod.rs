@@ -1,13 +1,20 @@
+pub use point2::Point2;
 pub use point3::Point3;
+pub use point4::Point4;
 
-pub(crate) mod ops;
+#[macro_use]
+mod macros;
+pub mod ops;
+mod point2;
 mod point3;
+mod point4;
 
 use crate::geometry::vector::ops::{Dot, Module, Normalizable};
 use std::ops::{Add, Sub};
 
 /// N-dimensional vectors. It should be implemented with the vector reference if the intention is
-/// to not consume the vector. Implementations of this trait are **Point2** and **Point3**.
+/// to not consume the vector. Implementations of this trait are **Point2**, **Point3** and
+/// **Point4**.
 ///
 /// Operations of certain dimension vectors (like the cross-product of R3) are not forced with this
 /// trait
@@ -21,8 +21,10 @@ pub trait Cross<T = Self> {
 /// Entity with a length module
 pub trait Module {
 
+	type Output;
+
 	/// Returns the length module of the vector
-	fn module(self) -> f32;
+	fn module(self) -> Self::Output;
 
 }
 
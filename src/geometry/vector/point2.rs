@@ -0,0 +1,50 @@
+use crate::math::Float;
+
+/// Two-dimensional vector, with `f32` or `f64` precision
+///
+/// # Properties
+/// * `x`, `y` - Axes of the vector
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Point2<T: Float = f32> {
+	pub x: T,
+	pub y: T,
+}
+
+impl_vector!(Point2 { x, y });
+
+#[cfg(test)]
+mod test {
+	use crate::geometry::vector::Point2;
+	use crate::geometry::vector::ops::*;
+
+	#[test]
+	fn operators() {
+		let a = Point2 { x: 1.0, y: 2.0 };
+		let b = Point2 { x: 3.0, y: -1.0 };
+
+		assert_eq!(&a + &b, Point2 { x: 4.0, y: 1.0 });
+		assert_eq!(a.clone() - b.clone(), Point2 { x: -2.0, y: 3.0 });
+		assert_eq!(-&a, Point2 { x: -1.0, y: -2.0 });
+		assert_eq!(2.0 * &a, Point2 { x: 2.0, y: 4.0 });
+		assert_eq!(&b / 2.0, Point2 { x: 1.5, y: -0.5 });
+		assert_eq!(a.dot(&b), 1.0);
+
+		let mut c = a.clone();
+		c += &b;
+		c *= 2.0;
+		assert_eq!(c, Point2 { x: 8.0, y: 2.0 });
+	}
+
+	#[test]
+	fn helpers() {
+		let a = Point2 { x: 0.0, y: 0.0 };
+		let b = Point2 { x: 3.0, y: 4.0 };
+
+		assert_eq!(b.module(), 5.0);
+		assert_eq!(b.normal(), Point2 { x: 0.6, y: 0.8 });
+		assert_eq!(a.distance(&b), 5.0);
+		assert_eq!(a.lerp(&b, 0.5), Point2 { x: 1.5, y: 2.0 });
+		assert_eq!(b.reflect(&Point2 { x: 0.0, y: 1.0 }), Point2 { x: 3.0, y: -4.0 });
+	}
+}
@@ -1,31 +1,29 @@
-use std::ops::{Add, Sub};
 use crate::geometry::Rotation;
 use crate::geometry::projectable::Projectable;
-use crate::geometry::vector::ops::{Cross, Dot, Module, Normalizable};
-use crate::geometry::vector::Vector;
+use crate::geometry::vector::ops::Cross;
 use crate::math::vector_dot_matrix;
-use crate::math::Matrix4;
+use crate::math::{Float, Matrix4};
 
-/// Three-dimensional vector
+/// Three-dimensional vector, with `f32` or `f64` precision
 ///
 /// # Properties
 /// * `x`, `y`, `z` - Axes of the vector
 ///
-#[derive(Clone, Debug, PartialEq)]
-pub struct Point3 {
-	pub x: f32,
-	pub y: f32,
-	pub z: f32,
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Point3<T: Float = f32> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
 }
 
-impl Point3 {
+impl<T: Float> Point3<T> {
 
 	/// Moves the point to the given position
 	///
 	/// # Arguments
 	/// * `new_pos` - New position like (x, y, z)
 	///
-	pub fn translate(&mut self, new_pos: (f32, f32, f32)) {
+	pub fn translate(&mut self, new_pos: (T, T, T)) {
 		self.x = new_pos.0;
 		self.y = new_pos.1;
 		self.z = new_pos.2;
@@ -36,65 +34,43 @@ impl Point3 {
 	/// # Arguments
 	/// * `offset` - Offset to apply
 	///
-	pub fn apply_offset(mut self, offset: f32) -> Self {
+	pub fn apply_offset(mut self, offset: T) -> Self {
 		self.z += offset;
 		self
 	}
 
-}
-
-impl Projectable<Point3> for Point3 {
-	fn get_projection(&self, matrix: &Matrix4, offset: f32) -> Self {
-		let (x, y, z) = vector_dot_matrix((self.x, self.y, self.z + offset), matrix);
-		Point3 { x, y, z }
+	/// Returns the point transformed by the given matrix
+	///
+	/// # Arguments
+	/// * `matrix` - Transformation matrix
+	///
+	pub fn transform(&self, matrix: &Matrix4<T>) -> Self {
+		vector_dot_matrix(self, matrix)
 	}
-}
 
-impl Rotation for Point3 {
-	fn rotate(&mut self, rotation: &Matrix4) {
-		self.translate(vector_dot_matrix((self.x, self.y, self.z), rotation));
-	}
 }
-// Vector addition
-impl<'a> Add<&'a Point3> for &'a Point3 {
-	type Output = Point3;
 
-	fn add(self, rhs: &'a Point3) -> Self::Output {
-		Point3 {
-			x: self.x + rhs.x,
-			y: self.y + rhs.y,
-			z: self.z + rhs.z
-		}
+// The renderer works in f32, so the points of other precisions are converted after their offset,
+// right before the projection
+impl<T: Float> Projectable<Point3> for Point3<T> {
+	fn get_projection(&self, matrix: &Matrix4, offset: f32) -> Point3 {
+		vector_dot_matrix(&self.clone().apply_offset(T::from_f32(offset)).cast(), matrix)
 	}
 }
 
-// Vector subtraction
-impl<'a> Sub<&'a Point3> for &'a Point3 {
-	type Output = Point3;
-
-	fn sub(self, rhs: &'a Point3) -> Self::Output {
-		Point3 {
-			x: self.x - rhs.x,
-			y: self.y - rhs.y,
-			z: self.z - rhs.z
-		}
+impl<T: Float> Rotation for Point3<T> {
+	fn rotate(&mut self, rotation: &Matrix4) {
+		*self = vector_dot_matrix(self, &rotation.cast());
 	}
 }
 
-// Vector dot-product
-impl<'a> Dot<&'a Point3> for &'a Point3 {
-	type Output = f32;
-
-	fn dot(self, rhs: &'a Point3) -> Self::Output {
-		self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
-	}
-}
+impl_vector!(Point3 { x, y, z });
 
 // Vector cross-product
-impl<'a> Cross<&'a Point3> for &'a Point3 {
-	type Output = Point3;
+impl<'a, T: Float> Cross<&'a Point3<T>> for &'a Point3<T> {
+	type Output = Point3<T>;
 
-	fn cross(self, rhs: &'a Point3) -> Self::Output {
+	fn cross(self, rhs: &'a Point3<T>) -> Self::Output {
 		Point3 {
 			x: self.y * rhs.z - self.z * rhs.y,
 			y: self.z * rhs.x - self.x * rhs.z,
@@ -103,29 +79,6 @@ impl<'a> Cross<&'a Point3> for &'a Point3 {
 	}
 }
 
-// Vector module
-impl<'a> Module for &'a Point3 {
-	fn module(self) -> f32 {
-		(self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
-	}
-}
-
-// Vector normal
-impl<'a> Normalizable<&'a Point3> for &'a Point3 {
-	type Output = Point3;
-
-	fn normal(self) -> Self::Output {
-		let module = self.module();
-		Point3 {
-			x: self.x / module,
-			y: self.y / module,
-			z: self.z / module
-		}
-	}
-}
-
-impl<'a> Vector<&'a Point3> for &'a Point3 {}
-
 #[cfg(test)]
 mod test {
 	use crate::geometry::projectable::Projectable;
@@ -205,4 +158,43 @@ mod test {
 		assert_eq!(12.0, point_a.dot(&point_b));
 	}
 
-}
\ No newline at end of file
+	#[test]
+	fn owned_operators() {
+		let point_a = Point3 { x: 2.0, y: 0.0, z: -10.0, };
+		let point_b = Point3 { x: 1.0, y: 1.0, z: -11.0, };
+
+		assert_eq!(point_a.clone() + &point_b, Point3 { x: 3.0, y: 1.0, z: -21.0 });
+		assert_eq!(&point_a - point_b.clone(), Point3 { x: 1.0, y: -1.0, z: 1.0 });
+		assert_eq!(-point_a.clone(), Point3 { x: -2.0, y: 0.0, z: 10.0 });
+		assert_eq!(point_b * 2.0, Point3 { x: 2.0, y: 2.0, z: -22.0 });
+
+		let mut point = point_a;
+		point -= Point3 { x: 2.0, y: 0.0, z: 0.0 };
+		point /= 2.0;
+		assert_eq!(point, Point3 { x: 0.0, y: 0.0, z: -5.0 });
+	}
+
+	#[test]
+	fn helpers() {
+		let point_a = Point3 { x: 1.0, y: 2.0, z: 2.0, };
+		let origin = Point3::default();
+
+		assert_eq!(origin.distance(&point_a), 3.0);
+		assert_eq!(origin.lerp(&point_a, 0.5), Point3 { x: 0.5, y: 1.0, z: 1.0 });
+		assert_eq!(point_a.reflect(&Point3 { x: 0.0, y: 0.0, z: -1.0 }), Point3 { x: 1.0, y: 2.0, z: -2.0 });
+	}
+
+	#[test]
+	fn double_precision() {
+		let point: Point3<f64> = Point3 { x: 100_000_000.5, y: 0.25, z: 2.0 };
+		let moved = point.transform(&Matrix4::translation(0.25, 0.0, 0.0));
+		assert_eq!(moved.x, 100_000_000.75);
+
+		// Projected in f32 like the rest of the scene
+		let matrix = Matrix4::translation(1.0, 1.0, 1.0);
+		let small: Point3<f64> = Point3 { x: 1.0, y: 2.0, z: 3.0 };
+		assert_eq!(small.get_projection(&matrix, 1.0), Point3 { x: 2.0, y: 3.0, z: 5.0 });
+		assert_eq!(small.cast::<f32>().cast::<f64>(), small);
+	}
+
+}
@@ -0,0 +1,75 @@
+use std::ops::Mul;
+use crate::geometry::vector::Point3;
+use crate::math::{Float, Matrix4};
+
+/// Four-dimensional vector, used as the homogeneous coordinates of a three-dimensional point, with
+/// `f32` or `f64` precision
+///
+/// # Properties
+/// * `x`, `y`, `z` - Axes of the vector
+/// * `w` - Homogeneous coordinate, dividing the others to get the point
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Point4<T: Float = f32> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+	pub w: T,
+}
+
+impl<T: Float> Point4<T> {
+
+	/// Returns the three-dimensional point, dividing the axes by the homogeneous coordinate.
+	/// A zero coordinate is replaced by a tiny one to keep the point finite.
+	pub fn to_cartesian(&self) -> Point3<T> {
+		let w = if self.w == T::ZERO { T::from_f32(0.000001) } else { self.w };
+		Point3 { x: self.x / w, y: self.y / w, z: self.z / w }
+	}
+
+}
+
+impl_vector!(Point4 { x, y, z, w });
+
+impl<T: Float> From<&Point3<T>> for Point4<T> {
+	fn from(point: &Point3<T>) -> Self {
+		Point4 { x: point.x, y: point.y, z: point.z, w: T::ONE }
+	}
+}
+
+// Vector-matrix multiplication, with the vector as a row
+impl<'a, T: Float> Mul<&'a Matrix4<T>> for &'a Point4<T> {
+	type Output = Point4<T>;
+
+	fn mul(self, rhs: &'a Matrix4<T>) -> Self::Output {
+		let column = |j: usize| self.x * rhs[0][j] + self.y * rhs[1][j] + self.z * rhs[2][j] + self.w * rhs[3][j];
+		Point4 { x: column(0), y: column(1), z: column(2), w: column(3) }
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::geometry::vector::{Point3, Point4};
+	use crate::geometry::vector::ops::*;
+	use crate::math::Matrix4;
+
+	#[test]
+	fn homogeneous() {
+		let point = Point3 { x: 1.0, y: 2.0, z: 3.0 };
+		let homogeneous = Point4::from(&point);
+		assert_eq!(homogeneous.w, 1.0);
+
+		let moved = &homogeneous * &Matrix4::translation(1.0, 1.0, 1.0);
+		assert_eq!(moved, Point4 { x: 2.0, y: 3.0, z: 4.0, w: 1.0 });
+		assert_eq!((moved * 2.0).to_cartesian(), Point3 { x: 2.0, y: 3.0, z: 4.0 });
+	}
+
+	#[test]
+	fn operators() {
+		let a = Point4 { x: 1.0, y: 2.0, z: 3.0, w: 4.0 };
+		let b = Point4 { x: 1.0, y: 1.0, z: 1.0, w: 1.0 };
+
+		assert_eq!(&a - b.clone(), Point4 { x: 0.0, y: 1.0, z: 2.0, w: 3.0 });
+		assert_eq!(a.dot(&b), 10.0);
+		assert_eq!(-(a / 2.0), Point4 { x: -0.5, y: -1.0, z: -1.5, w: -2.0 });
+	}
+}
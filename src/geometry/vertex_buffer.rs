@@ -0,0 +1,144 @@
+use crate::geometry::vector::Point3;
+#[cfg(not(feature = "parallel"))]
+use crate::math::batch_dot_matrix;
+#[cfg(feature = "parallel")]
+use crate::math::par_batch_dot_matrix;
+use crate::math::{Float, Matrix4};
+
+/// Positions of a group of vertices stored by axis (structure of arrays), so they can be
+/// transformed in batches, with `f32` or `f64` precision
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VertexBuffer<T: Float = f32> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+    zs: Vec<T>,
+}
+
+impl<T: Float> VertexBuffer<T> {
+    /// Returns an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an empty buffer with room for the given number of vertices
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { xs: Vec::with_capacity(capacity), ys: Vec::with_capacity(capacity), zs: Vec::with_capacity(capacity) }
+    }
+
+    /// Adds a vertex at the end of the buffer
+    pub fn push(&mut self, point: &Point3<T>) {
+        self.xs.push(point.x);
+        self.ys.push(point.y);
+        self.zs.push(point.z);
+    }
+
+    /// Returns the vertex at the given position, if any
+    pub fn get(&self, index: usize) -> Option<Point3<T>> {
+        Some(Point3 { x: *self.xs.get(index)?, y: self.ys[index], z: self.zs[index] })
+    }
+
+    /// Replaces the vertex at the given position
+    ///
+    /// # Panics
+    /// If the position is out of the buffer
+    pub fn set(&mut self, index: usize, point: &Point3<T>) {
+        self.xs[index] = point.x;
+        self.ys[index] = point.y;
+        self.zs[index] = point.z;
+    }
+
+    /// Swaps the vertices at the given positions
+    ///
+    /// # Panics
+    /// If any position is out of the buffer
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.xs.swap(a, b);
+        self.ys.swap(a, b);
+        self.zs.swap(a, b);
+    }
+
+    /// Keeps the first vertices, removing the rest
+    pub fn truncate(&mut self, len: usize) {
+        self.xs.truncate(len);
+        self.ys.truncate(len);
+        self.zs.truncate(len);
+    }
+
+    /// Returns the number of vertices
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// Checks if the buffer has no vertices
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// Returns the vertices in order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Point3<T>> + Clone + '_ {
+        self.xs.iter().zip(&self.ys).zip(&self.zs).map(|((x, y), z)| Point3 { x: *x, y: *y, z: *z })
+    }
+
+    /// Returns the X, Y and Z axes of all the vertices
+    pub fn axes(&self) -> (&[T], &[T], &[T]) {
+        (&self.xs, &self.ys, &self.zs)
+    }
+
+    /// Multiplies all the vertices with the matrix at once. With the `parallel` feature, big
+    /// buffers are split among several threads.
+    pub fn transform(&mut self, matrix: &Matrix4<T>) {
+        #[cfg(not(feature = "parallel"))]
+        batch_dot_matrix(&mut self.xs, &mut self.ys, &mut self.zs, matrix);
+        #[cfg(feature = "parallel")]
+        par_batch_dot_matrix(&mut self.xs, &mut self.ys, &mut self.zs, matrix);
+    }
+}
+
+impl<T: Float> FromIterator<Point3<T>> for VertexBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = Point3<T>>>(points: I) -> Self {
+        let mut buffer = Self::new();
+        for point in points {
+            buffer.push(&point);
+        }
+        buffer
+    }
+}
+
+impl<'a, T: Float> FromIterator<&'a Point3<T>> for VertexBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = &'a Point3<T>>>(points: I) -> Self {
+        let mut buffer = Self::new();
+        for point in points {
+            buffer.push(point);
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VertexBuffer;
+    use crate::geometry::vector::Point3;
+    use crate::math::Matrix4;
+
+    #[test]
+    fn transform() {
+        let points = [Point3 { x: 1.0, y: 2.0, z: 3.0 }, Point3 { x: -1.0, y: 0.0, z: 0.5 }];
+        let mut buffer: VertexBuffer = points.iter().collect();
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.get(1), Some(points[1].clone()));
+        assert_eq!(buffer.get(2), None);
+
+        buffer.transform(&Matrix4::translation(1.0, 1.0, 1.0));
+        let moved: Vec<Point3> = buffer.iter().collect();
+        assert_eq!(moved, vec![Point3 { x: 2.0, y: 3.0, z: 4.0 }, Point3 { x: 0.0, y: 1.0, z: 1.5 }]);
+        assert_eq!(buffer.axes().0, &[2.0, 0.0]);
+    }
+
+    #[test]
+    fn double_precision() {
+        let point: Point3<f64> = Point3 { x: 100_000_000.5, y: 0.0, z: -1.0 };
+        let mut buffer: VertexBuffer<f64> = [point].into_iter().collect();
+        buffer.transform(&Matrix4::translation(0.25, 0.0, 0.0));
+        assert_eq!(buffer.get(0), Some(Point3 { x: 100_000_000.75, y: 0.0, z: -1.0 }));
+    }
+}
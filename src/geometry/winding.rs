@@ -0,0 +1,20 @@
+use crate::geometry::vector::Point3;
+use crate::math::Float;
+
+/// Order of the vertices of the faces looking to the camera, as seen from it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Winding {
+    #[default]
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Winding {
+    /// Returns the given triangle normal, calculated from the vertex order, pointing outwards
+    pub fn orient<T: Float>(self, normal: Point3<T>) -> Point3<T> {
+        match self {
+            Self::Clockwise => normal,
+            Self::CounterClockwise => Point3 { x: -normal.x, y: -normal.y, z: -normal.z },
+        }
+    }
+}
@@ -1,7 +1,20 @@
-mod actors;
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+pub mod actors;
+pub mod collision;
 pub mod engine;
-mod environment;
-mod geometry;
-mod math;
+pub mod environment;
+pub mod geometry;
+pub mod lighting;
+pub mod math;
+pub mod overlay;
+pub mod physics;
+pub mod post_process;
+pub mod render;
+pub mod texture;
+
+mod slots;
+
+pub use slots::SlotId;
 
 type FerruxViewport = ferrux_viewport::viewport::WinitViewport<u32>;
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::ops::{Add, Mul};
+
+/// RGB color with linear channels, where one is the full intensity. Channels can go over one
+/// while the light is being accumulated, they are clamped when turned into pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Color {
+    pub const BLACK: Color = Color { r: 0.0, g: 0.0, b: 0.0 };
+    pub const WHITE: Color = Color { r: 1.0, g: 1.0, b: 1.0 };
+
+    /// Returns a new color with the given channels
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Returns the color of the given RGB bytes
+    pub fn from_rgb(rgb: [u8; 3]) -> Self {
+        let channel = |c: u8| c as f32 / u8::MAX as f32;
+        Self::new(channel(rgb[0]), channel(rgb[1]), channel(rgb[2]))
+    }
+
+    /// Returns the opaque RGBA bytes of the color, clamping the channels
+    pub fn to_rgba(self) -> [u8; 4] {
+        let channel = |c: f32| (c.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8;
+        [channel(self.r), channel(self.g), channel(self.b), u8::MAX]
+    }
+}
+
+// Color addition
+impl Add for Color {
+    type Output = Color;
+
+    fn add(self, rhs: Color) -> Self::Output {
+        Color::new(self.r + rhs.r, self.g + rhs.g, self.b + rhs.b)
+    }
+}
+
+// Color modulation, channel by channel
+impl Mul for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: Color) -> Self::Output {
+        Color::new(self.r * rhs.r, self.g * rhs.g, self.b * rhs.b)
+    }
+}
+
+// Color scaling
+impl Mul<f32> for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Color::new(self.r * rhs, self.g * rhs, self.b * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Color;
+
+    #[test]
+    fn to_rgba_clamps() {
+        assert_eq!(Color::new(2.0, -1.0, 0.5).to_rgba(), [255, 0, 128, 255]);
+    }
+
+    #[test]
+    fn operations() {
+        let color = Color::new(0.5, 0.25, 1.0);
+        assert_eq!(color + color, Color::new(1.0, 0.5, 2.0));
+        assert_eq!(color * color, Color::new(0.25, 0.0625, 1.0));
+        assert_eq!(color * 2.0, Color::new(1.0, 0.5, 2.0));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::geometry::vector::ops::{Dot, Module, Normalizable};
+use crate::geometry::vector::Point3;
+use crate::lighting::{Color, Light, Material};
+
+/// Collection of the light sources of the environment, calculating the light reflected by the
+/// surfaces with the Blinn-Phong model
+#[derive(Clone, Debug, Default)]
+pub struct Lighting {
+    lights: Vec<Light>,
+}
+
+impl Lighting {
+    /// Returns a lighting without lights
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new light source
+    pub fn add(&mut self, light: Light) {
+        self.lights.push(light);
+    }
+
+    /// Removes all the light sources
+    pub fn clear(&mut self) {
+        self.lights.clear();
+    }
+
+    /// Returns the light sources
+    pub fn lights(&self) -> &[Light] {
+        &self.lights
+    }
+
+    /// Returns the color reflected by a point of a surface to the viewer
+    ///
+    /// # Arguments
+    /// * `position` - Position of the point in world space
+    /// * `normal` - Outwards normal of the surface in the point
+    /// * `eye` - Position of the viewer in world space
+    /// * `material` - Material of the surface
+    ///
+    pub fn shade(&self, position: &Point3, normal: &Point3, eye: &Point3, material: &Material) -> Color {
+        (0..self.lights.len()).fold(Color::BLACK, |color, index| {
+            color + self.shade_light(index, position, normal, eye, material)
+        })
+    }
+
+    /// Returns the color reflected to the viewer from a single light source, or black if there's
+    /// no light with the given index
+    ///
+    /// # Arguments
+    /// * `index` - Position of the light in the lighting
+    /// * `position` - Position of the point in world space
+    /// * `normal` - Outwards normal of the surface in the point
+    /// * `eye` - Position of the viewer in world space
+    /// * `material` - Material of the surface
+    ///
+    pub fn shade_light(
+        &self,
+        index: usize,
+        position: &Point3,
+        normal: &Point3,
+        eye: &Point3,
+        material: &Material,
+    ) -> Color {
+        let normal = unit(normal);
+        let view = unit(&(eye - position));
+        let reflect = |to_light: &Point3, radiance: Color| {
+            reflection(to_light, &normal, &view, radiance, material)
+        };
+
+        match self.lights.get(index) {
+            None => Color::BLACK,
+            Some(Light::Ambient { color, intensity }) => *color * *intensity * material.diffuse,
+            Some(Light::Directional { direction, color, intensity }) => {
+                reflect(&unit(direction), *color * *intensity)
+            }
+            Some(Light::Point { position: source, color, intensity, attenuation }) => {
+                let to_light = source - position;
+                let distance = to_light.module();
+                reflect(&unit(&to_light), *color * (*intensity * attenuation.factor(distance)))
+            }
+            Some(Light::Spot {
+                position: source,
+                direction,
+                inner_angle,
+                outer_angle,
+                color,
+                intensity,
+                attenuation,
+            }) => {
+                let to_light = source - position;
+                let distance = to_light.module();
+                let to_light = unit(&to_light);
+                let cos = -to_light.dot(&unit(direction));
+                let (cos_inner, cos_outer) = (inner_angle.cos(), outer_angle.cos());
+                let cone = ((cos - cos_outer) / (cos_inner - cos_outer).max(f32::EPSILON)).clamp(0.0, 1.0);
+                reflect(&to_light, *color * (*intensity * attenuation.factor(distance) * cone))
+            }
+        }
+    }
+}
+
+/// Returns the diffuse and specular reflection of a light
+///
+/// # Arguments
+/// * `to_light` - Unit vector from the surface to the light
+/// * `normal` - Unit normal of the surface
+/// * `view` - Unit vector from the surface to the viewer
+/// * `radiance` - Light reaching the surface
+/// * `material` - Material of the surface
+///
+fn reflection(to_light: &Point3, normal: &Point3, view: &Point3, radiance: Color, material: &Material) -> Color {
+    let diffuse = normal.dot(to_light);
+    if diffuse <= 0.0 {
+        return Color::BLACK;
+    }
+    let halfway = unit(&(to_light + view));
+    let specular = normal.dot(&halfway).max(0.0).powf(material.shininess);
+    radiance * (material.diffuse * diffuse + material.specular * specular)
+}
+
+/// Returns the normalized vector, or the same vector if it has no length
+fn unit(vector: &Point3) -> Point3 {
+    if vector.module() > 0.0 { vector.normal() } else { vector.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lighting;
+    use crate::geometry::vector::Point3;
+    use crate::lighting::{Color, Light, Material};
+
+    const ORIGIN: Point3 = Point3 { x: 0.0, y: 0.0, z: 0.0 };
+    const UP: Point3 = Point3 { x: 0.0, y: 1.0, z: 0.0 };
+
+    fn matte() -> Material {
+        Material { specular: Color::BLACK, ..Material::default() }
+    }
+
+    fn lighting(light: Light) -> Lighting {
+        let mut lighting = Lighting::new();
+        lighting.add(light);
+        lighting
+    }
+
+    fn assert_color(result: Color, expected: Color) {
+        assert!((result.r - expected.r).abs() < 0.001, "{:?} != {:?}", result, expected);
+        assert!((result.g - expected.g).abs() < 0.001, "{:?} != {:?}", result, expected);
+        assert!((result.b - expected.b).abs() < 0.001, "{:?} != {:?}", result, expected);
+    }
+
+    #[test]
+    fn no_lights() {
+        let color = Lighting::new().shade(&ORIGIN, &UP, &UP, &Material::default());
+        assert_eq!(color, Color::BLACK);
+    }
+
+    #[test]
+    fn ambient() {
+        let lighting = lighting(Light::ambient(Color::new(1.0, 0.5, 0.0), 0.2));
+        let material = Material { diffuse: Color::new(0.5, 1.0, 1.0), ..matte() };
+        assert_color(lighting.shade(&ORIGIN, &UP, &UP, &material), Color::new(0.1, 0.1, 0.0));
+    }
+
+    #[test]
+    fn directional_diffuse() {
+        let direction = Point3 { x: 1.0, y: 1.0, z: 0.0 };
+        let lighting = lighting(Light::directional(direction, Color::WHITE, 1.0));
+        let expected = Color::WHITE * std::f32::consts::FRAC_1_SQRT_2;
+        assert_color(lighting.shade(&ORIGIN, &UP, &UP, &matte()), expected);
+    }
+
+    #[test]
+    fn directional_from_behind() {
+        let direction = Point3 { x: 0.0, y: -1.0, z: 0.0 };
+        let lighting = lighting(Light::directional(direction, Color::WHITE, 1.0));
+        assert_eq!(lighting.shade(&ORIGIN, &UP, &UP, &Material::default()), Color::BLACK);
+    }
+
+    #[test]
+    fn specular_highlight() {
+        let lighting = lighting(Light::directional(UP, Color::WHITE, 1.0));
+        let material = Material { diffuse: Color::BLACK, specular: Color::WHITE, shininess: 8.0, ..Material::default() };
+        assert_color(lighting.shade(&ORIGIN, &UP, &UP, &material), Color::WHITE);
+
+        let eye = Point3 { x: 1.0, y: 0.0, z: 0.0 };
+        let shade = lighting.shade(&ORIGIN, &UP, &eye, &material);
+        assert!(shade.r < 0.1);
+    }
+
+    #[test]
+    fn point_attenuation() {
+        let light = Light::point(Point3 { x: 0.0, y: 2.0, z: 0.0 }, Color::WHITE, 1.0);
+        let lighting = lighting(light);
+        let expected = Color::WHITE * (1.0 / (1.0 + 0.09 * 2.0 + 0.032 * 4.0));
+        assert_color(lighting.shade(&ORIGIN, &UP, &UP, &matte()), expected);
+    }
+
+    #[test]
+    fn single_light() {
+        let mut lighting = lighting(Light::ambient(Color::WHITE, 0.2));
+        lighting.add(Light::directional(UP, Color::WHITE, 1.0));
+        assert_color(lighting.shade_light(0, &ORIGIN, &UP, &UP, &matte()), Color::WHITE * 0.2);
+        assert_color(lighting.shade_light(1, &ORIGIN, &UP, &UP, &matte()), Color::WHITE);
+        assert_eq!(lighting.shade_light(2, &ORIGIN, &UP, &UP, &matte()), Color::BLACK);
+    }
+
+    #[test]
+    fn spot_cone() {
+        let down = Point3 { x: 0.0, y: -1.0, z: 0.0 };
+        let light = Light::spot(Point3 { x: 0.0, y: 1.0, z: 0.0 }, down, 0.5, Color::WHITE, 1.0);
+        let lighting = lighting(light);
+        assert!(lighting.shade(&ORIGIN, &UP, &UP, &matte()).r > 0.5);
+
+        let outside = Point3 { x: 5.0, y: 0.0, z: 0.0 };
+        assert_eq!(lighting.shade(&outside, &UP, &UP, &matte()), Color::BLACK);
+    }
+}
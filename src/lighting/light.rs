@@ -0,0 +1,104 @@
+use crate::geometry::vector::Point3;
+use crate::lighting::Color;
+
+/// Reduction of the light with the distance, as `1 / (constant + linear * d + quadratic * d²)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attenuation {
+    pub constant: f32,
+    pub linear: f32,
+    pub quadratic: f32,
+}
+
+impl Attenuation {
+    /// Returns the factor of the light remaining at the given distance
+    pub fn factor(&self, distance: f32) -> f32 {
+        1.0 / (self.constant + self.linear * distance + self.quadratic * distance * distance).max(f32::EPSILON)
+    }
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        Self { constant: 1.0, linear: 0.09, quadratic: 0.032 }
+    }
+}
+
+/// Light sources of the environment
+#[derive(Clone, Debug, PartialEq)]
+pub enum Light {
+    /// Light reaching every surface equally
+    Ambient {
+        color: Color,
+        intensity: f32,
+    },
+    /// Light coming from a source infinitely far, with all its rays parallel
+    Directional {
+        /// Direction pointing to the light source
+        direction: Point3,
+        color: Color,
+        intensity: f32,
+    },
+    /// Light emitted in all directions from a point
+    Point {
+        position: Point3,
+        color: Color,
+        intensity: f32,
+        attenuation: Attenuation,
+    },
+    /// Light emitted from a point in a cone
+    Spot {
+        position: Point3,
+        /// Direction the cone points to
+        direction: Point3,
+        /// Angle from the axis of the cone where the light starts fading, in radians
+        inner_angle: f32,
+        /// Angle from the axis of the cone where the light ends, in radians
+        outer_angle: f32,
+        color: Color,
+        intensity: f32,
+        attenuation: Attenuation,
+    },
+}
+
+impl Light {
+    /// Returns an ambient light
+    pub fn ambient(color: Color, intensity: f32) -> Self {
+        Self::Ambient { color, intensity }
+    }
+
+    /// Returns a directional light
+    ///
+    /// # Arguments
+    /// * `direction` - Direction pointing to the light source
+    /// * `color` - Color of the light
+    /// * `intensity` - Intensity of the light
+    ///
+    pub fn directional(direction: Point3, color: Color, intensity: f32) -> Self {
+        Self::Directional { direction, color, intensity }
+    }
+
+    /// Returns a point light with the default attenuation
+    pub fn point(position: Point3, color: Color, intensity: f32) -> Self {
+        Self::Point { position, color, intensity, attenuation: Attenuation::default() }
+    }
+
+    /// Returns a spot light with the default attenuation, fading in the outer tenth of the cone
+    ///
+    /// # Arguments
+    /// * `position` - Position of the light source
+    /// * `direction` - Direction the cone points to
+    /// * `angle` - Angle from the axis of the cone to its edge, in radians
+    /// * `color` - Color of the light
+    /// * `intensity` - Intensity of the light
+    ///
+    pub fn spot(position: Point3, direction: Point3, angle: f32, color: Color, intensity: f32) -> Self {
+        Self::Spot {
+            position,
+            direction,
+            inner_angle: angle * 0.9,
+            outer_angle: angle,
+            color,
+            intensity,
+            attenuation: Attenuation::default(),
+        }
+    }
+}
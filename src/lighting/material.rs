@@ -0,0 +1,41 @@
+use crate::lighting::Color;
+use crate::texture::{Sampler, Texture};
+use std::sync::Arc;
+
+const DEFAULT_SPECULAR: Color = Color::new(0.5, 0.5, 0.5);
+const DEFAULT_SHININESS: f32 = 32.0;
+
+/// Surface properties of a mesh used to reflect the lights
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    /// Color of the diffuse and ambient reflection
+    pub diffuse: Color,
+    /// Color of the specular highlights
+    pub specular: Color,
+    /// Exponent of the specular reflection, the higher the smaller and sharper the highlights
+    pub shininess: f32,
+    /// Texture modulating the reflected light, used by the meshes with texture coordinates
+    pub diffuse_map: Option<Arc<Texture>>,
+    /// Options to sample the diffuse map
+    pub sampler: Sampler,
+}
+
+impl Material {
+    /// Sets the texture modulating the reflected light
+    pub fn with_diffuse_map(mut self, texture: Arc<Texture>) -> Self {
+        self.diffuse_map = Some(texture);
+        self
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            diffuse: Color::WHITE,
+            specular: DEFAULT_SPECULAR,
+            shininess: DEFAULT_SHININESS,
+            diffuse_map: None,
+            sampler: Sampler::default(),
+        }
+    }
+}
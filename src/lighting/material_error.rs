@@ -0,0 +1,44 @@
+use crate::texture::TextureError;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+/// Errors than can occur loading a material library
+#[derive(PartialEq)]
+pub enum MaterialError {
+    /// The library file is missing or can't be read
+    BadFile(String),
+    /// A line of the library has a missing or invalid value
+    WrongValue(String),
+    /// A texture of the library can't be loaded
+    Texture(TextureError),
+}
+
+impl MaterialError {
+    fn message(&self) -> String {
+        match self {
+            Self::BadFile(file) => format!("The material library can't be read: {}", file),
+            Self::WrongValue(line) => format!("Invalid value on line: {}", line),
+            Self::Texture(error) => error.to_string(),
+        }
+    }
+}
+
+impl From<TextureError> for MaterialError {
+    fn from(error: TextureError) -> Self {
+        Self::Texture(error)
+    }
+}
+
+impl Error for MaterialError {}
+
+impl Debug for MaterialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Display for MaterialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.message())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::lighting::{Color, Material, MaterialError};
+use crate::texture::{Texture, Wrap};
+use log::{error, info};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Materials defined in an MTL file, by name. It reads the diffuse (`Kd`) and specular (`Ks`)
+/// colors, the shininess (`Ns`) and the diffuse map (`map_Kd`), with its `-clamp` option.
+#[derive(Debug, Default)]
+pub struct MaterialLibrary {
+    materials: HashMap<String, Material>,
+}
+
+impl MaterialLibrary {
+    /// Loads the library of the file. The textures are read relative to its directory.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the MTL file
+    ///
+    /// # Error
+    /// A [MaterialError] if the file or any of its textures can't be read
+    ///
+    pub fn load(path: &Path) -> Result<Self, MaterialError> {
+        info!("Loading material library {}", path.display());
+        let content = fs::read_to_string(path).map_err(|e| {
+            error!("{}", e);
+            MaterialError::BadFile(path.display().to_string())
+        })?;
+        Self::parse(&content, path.parent().unwrap_or_else(|| Path::new("")))
+    }
+
+    /// Reads the library of the given content
+    ///
+    /// # Arguments
+    /// * `content` - Content of the MTL file
+    /// * `directory` - Directory to read the textures from
+    ///
+    pub fn parse(content: &str, directory: &Path) -> Result<Self, MaterialError> {
+        let mut materials = HashMap::new();
+        let mut textures: HashMap<PathBuf, Arc<Texture>> = HashMap::new();
+        let mut current: Option<(String, Material)> = None;
+
+        for line in content.lines() {
+            let mut iter = line.split_whitespace();
+            let keyword = iter.next();
+            if keyword == Some("newmtl") {
+                let name = iter.next().ok_or_else(|| MaterialError::WrongValue(line.to_string()))?;
+                if let Some((name, material)) = current.replace((name.to_string(), Material::default())) {
+                    materials.insert(name, material);
+                }
+                continue;
+            }
+            let material = match &mut current {
+                Some((_, material)) => material,
+                None => continue,
+            };
+            match keyword {
+                Some("Kd") => material.diffuse = parse_color(&mut iter, line)?,
+                Some("Ks") => material.specular = parse_color(&mut iter, line)?,
+                Some("Ns") => material.shininess = parse_value(iter.next(), line)?,
+                Some("map_Kd") => {
+                    let arguments: Vec<&str> = iter.collect();
+                    let file = arguments.last().ok_or_else(|| MaterialError::WrongValue(line.to_string()))?;
+                    let clamp = arguments.windows(2).any(|pair| pair == ["-clamp", "on"]);
+                    material.sampler.wrap = if clamp { Wrap::Clamp } else { Wrap::Repeat };
+
+                    let path = directory.join(file);
+                    let texture = match textures.get(&path) {
+                        Some(texture) => texture.clone(),
+                        None => {
+                            let texture = Arc::new(Texture::load(&path)?);
+                            textures.insert(path, texture.clone());
+                            texture
+                        }
+                    };
+                    material.diffuse_map = Some(texture);
+                }
+                _ => {}
+            }
+        }
+        if let Some((name, material)) = current {
+            materials.insert(name, material);
+        }
+
+        Ok(Self { materials })
+    }
+
+    /// Returns the material with the given name
+    pub fn get(&self, name: &str) -> Option<&Material> {
+        self.materials.get(name)
+    }
+
+    /// Returns the number of materials of the library
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    /// Checks if the library has no materials
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+}
+
+/// Reads the next three values of the line as a color
+fn parse_color<'a>(iter: &mut impl Iterator<Item = &'a str>, line: &str) -> Result<Color, MaterialError> {
+    let r = parse_value(iter.next(), line)?;
+    let g = parse_value(iter.next(), line)?;
+    let b = parse_value(iter.next(), line)?;
+    Ok(Color::new(r, g, b))
+}
+
+fn parse_value(slice: Option<&str>, line: &str) -> Result<f32, MaterialError> {
+    slice
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| MaterialError::WrongValue(line.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaterialLibrary;
+    use crate::lighting::{Color, MaterialError};
+    use crate::texture::{TextureError, Wrap};
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    #[test]
+    fn colors() {
+        let library = MaterialLibrary::parse("
+            newmtl hull
+            Kd 0.5 0.25 1.0
+            Ks 0.0 0.0 0.0
+            Ns 8
+            newmtl glass
+            Kd 0 0 1
+            ", Path::new("")).unwrap();
+        assert_eq!(library.len(), 2);
+        let hull = library.get("hull").unwrap();
+        assert_eq!(hull.diffuse, Color::new(0.5, 0.25, 1.0));
+        assert_eq!(hull.specular, Color::BLACK);
+        assert_eq!(hull.shininess, 8.0);
+        assert_eq!(library.get("glass").unwrap().diffuse, Color::new(0.0, 0.0, 1.0));
+        assert!(library.get("hull").unwrap().diffuse_map.is_none());
+    }
+
+    #[test]
+    fn diffuse_maps() {
+        let directory = std::env::temp_dir().join("ferrux_engine_material_test");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("texture.ppm"), b"P3\n1 1\n255\n255 255 255\n").unwrap();
+        let library = MaterialLibrary::parse("
+            newmtl first
+            map_Kd -clamp on texture.ppm
+            newmtl second
+            map_Kd texture.ppm
+            ", &directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let (first, second) = (library.get("first").unwrap(), library.get("second").unwrap());
+        assert_eq!(first.sampler.wrap, Wrap::Clamp);
+        assert_eq!(second.sampler.wrap, Wrap::Repeat);
+        assert!(Arc::ptr_eq(first.diffuse_map.as_ref().unwrap(), second.diffuse_map.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert_eq!(
+            MaterialLibrary::parse("newmtl a\nKd 1 x 0", Path::new("")).unwrap_err(),
+            MaterialError::WrongValue("Kd 1 x 0".to_string())
+        );
+        assert_eq!(
+            MaterialLibrary::parse("newmtl a\nmap_Kd missing.png", Path::new("")).unwrap_err(),
+            MaterialError::Texture(TextureError::BadFile("missing.png".to_string()))
+        );
+    }
+}
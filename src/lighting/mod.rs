@@ -0,0 +1,13 @@
+pub use color::Color;
+pub use environment_lighting::Lighting;
+pub use light::{Attenuation, Light};
+pub use material::Material;
+pub use material_error::MaterialError;
+pub use material_library::MaterialLibrary;
+
+mod color;
+mod environment_lighting;
+mod light;
+mod material;
+mod material_error;
+mod material_library;
@@ -0,0 +1,161 @@
+use crate::math::{Float, Matrix4};
+
+/// Value replacing a zero homogeneous coordinate, like [vector_dot_matrix](crate::math::vector_dot_matrix)
+const MIN_W: f64 = 0.000001;
+
+/// Fewest points transformed by each thread of [par_batch_dot_matrix]
+#[cfg(feature = "parallel")]
+const MIN_PARALLEL_CHUNK: usize = 4096;
+
+/// Multiplies a batch of points with the given matrix as rows, dividing the results by their
+/// homogeneous coordinates. The points are given by axis, so the same position of the three
+/// slices is one point. With the `simd` feature the `f32` points are transformed in lanes of
+/// eight.
+///
+/// # Arguments
+/// * `xs`, `ys`, `zs` - Axes of the points, transformed in place
+/// * `matrix` - Matrix to multiply
+///
+/// # Panics
+/// If the slices have different lengths
+///
+pub fn batch_dot_matrix<T: Float>(xs: &mut [T], ys: &mut [T], zs: &mut [T], matrix: &Matrix4<T>) {
+    assert!(xs.len() == ys.len() && ys.len() == zs.len(), "The axes of the batch have different lengths");
+    let (xs, ys, zs) = T::dot_matrix_lanes(xs, ys, zs, matrix);
+    batch_dot_matrix_scalar(xs, ys, zs, matrix);
+}
+
+/// Multiplies a batch of points like [batch_dot_matrix], splitting it among several threads.
+/// Each point is transformed as in a single thread, so the result is the same. Batches too small
+/// to be worth spawning the threads are transformed in the current one.
+///
+/// # Arguments
+/// * `xs`, `ys`, `zs` - Axes of the points, transformed in place
+/// * `matrix` - Matrix to multiply
+///
+/// # Panics
+/// If the slices have different lengths
+///
+#[cfg(feature = "parallel")]
+pub fn par_batch_dot_matrix<T: Float>(xs: &mut [T], ys: &mut [T], zs: &mut [T], matrix: &Matrix4<T>) {
+    assert!(xs.len() == ys.len() && ys.len() == zs.len(), "The axes of the batch have different lengths");
+    let workers = std::thread::available_parallelism().map_or(1, |workers| workers.get());
+    let chunk = xs.len().div_ceil(workers).max(MIN_PARALLEL_CHUNK);
+    if chunk >= xs.len() {
+        return batch_dot_matrix(xs, ys, zs, matrix);
+    }
+    std::thread::scope(|scope| {
+        let chunks = xs.chunks_mut(chunk).zip(ys.chunks_mut(chunk)).zip(zs.chunks_mut(chunk));
+        for ((xs, ys), zs) in chunks {
+            scope.spawn(move || batch_dot_matrix(xs, ys, zs, matrix));
+        }
+    });
+}
+
+/// Multiplies a batch of points with the given matrix one by one, the fallback of
+/// [batch_dot_matrix] without SIMD
+///
+/// # Arguments
+/// * `xs`, `ys`, `zs` - Axes of the points, transformed in place
+/// * `matrix` - Matrix to multiply
+///
+pub fn batch_dot_matrix_scalar<T: Float>(xs: &mut [T], ys: &mut [T], zs: &mut [T], matrix: &Matrix4<T>) {
+    let m = &matrix.matrix;
+    for ((x, y), z) in xs.iter_mut().zip(ys.iter_mut()).zip(zs.iter_mut()) {
+        let (px, py, pz) = (*x, *y, *z);
+        let mut w = px * m[0][3] + py * m[1][3] + pz * m[2][3] + m[3][3];
+        if w == T::ZERO {
+            w = T::from_f64(MIN_W);
+        }
+        *x = (px * m[0][0] + py * m[1][0] + pz * m[2][0] + m[3][0]) / w;
+        *y = (px * m[0][1] + py * m[1][1] + pz * m[2][1] + m[3][1]) / w;
+        *z = (px * m[0][2] + py * m[1][2] + pz * m[2][2] + m[3][2]) / w;
+    }
+}
+
+#[cfg(feature = "simd")]
+pub(crate) mod simd {
+    use super::MIN_W;
+    use crate::math::Matrix4;
+    use std::simd::prelude::*;
+
+    const LANES: usize = 8;
+
+    /// Transforms the points filling whole lanes and returns the remaining ones
+    pub(crate) fn batch_dot_matrix<'a>(
+        xs: &'a mut [f32],
+        ys: &'a mut [f32],
+        zs: &'a mut [f32],
+        matrix: &Matrix4,
+    ) -> (&'a mut [f32], &'a mut [f32], &'a mut [f32]) {
+        let m = matrix.matrix.map(|row| row.map(f32x8::splat));
+        let (zero, min_w) = (f32x8::splat(0.0), f32x8::splat(MIN_W as f32));
+        let split = xs.len() - xs.len() % LANES;
+        let (xs, x_rest) = xs.split_at_mut(split);
+        let (ys, y_rest) = ys.split_at_mut(split);
+        let (zs, z_rest) = zs.split_at_mut(split);
+        let lanes = xs.chunks_exact_mut(LANES).zip(ys.chunks_exact_mut(LANES)).zip(zs.chunks_exact_mut(LANES));
+        for ((x, y), z) in lanes {
+            let (px, py, pz) = (f32x8::from_slice(x), f32x8::from_slice(y), f32x8::from_slice(z));
+            let w = px * m[0][3] + py * m[1][3] + pz * m[2][3] + m[3][3];
+            let w = w.simd_eq(zero).select(min_w, w);
+            ((px * m[0][0] + py * m[1][0] + pz * m[2][0] + m[3][0]) / w).copy_to_slice(x);
+            ((px * m[0][1] + py * m[1][1] + pz * m[2][1] + m[3][1]) / w).copy_to_slice(y);
+            ((px * m[0][2] + py * m[1][2] + pz * m[2][2] + m[3][2]) / w).copy_to_slice(z);
+        }
+        (x_rest, y_rest, z_rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{batch_dot_matrix, batch_dot_matrix_scalar};
+    use crate::geometry::vector::Point3;
+    use crate::math::builders::{RotationAxis, RotationMatrixBuilder};
+    use crate::math::{vector_dot_matrix, Matrix4};
+
+    #[test]
+    fn matches_single_points() {
+        let rotation = RotationMatrixBuilder::new().in_axis(RotationAxis::Y).with_theta(0.7).build();
+        let matrix = &rotation * &Matrix4::translation(1.0, -2.0, 0.5);
+        // More points than a lane, to cover the remainder too
+        let points: Vec<Point3> = (0..19)
+            .map(|i| Point3 { x: i as f32, y: (i * i) as f32 * 0.1, z: -(i as f32) * 0.5 })
+            .collect();
+        let mut xs: Vec<f32> = points.iter().map(|point| point.x).collect();
+        let mut ys: Vec<f32> = points.iter().map(|point| point.y).collect();
+        let mut zs: Vec<f32> = points.iter().map(|point| point.z).collect();
+        let (mut scalar_xs, mut scalar_ys, mut scalar_zs) = (xs.clone(), ys.clone(), zs.clone());
+
+        batch_dot_matrix(&mut xs, &mut ys, &mut zs, &matrix);
+        batch_dot_matrix_scalar(&mut scalar_xs, &mut scalar_ys, &mut scalar_zs, &matrix);
+        for (i, point) in points.iter().enumerate() {
+            let expected = vector_dot_matrix(point, &matrix);
+            assert!((xs[i] - expected.x).abs() < 1e-4 && (scalar_xs[i] - expected.x).abs() < 1e-4);
+            assert!((ys[i] - expected.y).abs() < 1e-4 && (scalar_ys[i] - expected.y).abs() < 1e-4);
+            assert!((zs[i] - expected.z).abs() < 1e-4 && (scalar_zs[i] - expected.z).abs() < 1e-4);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_single_thread() {
+        let matrix = RotationMatrixBuilder::new().in_axis(RotationAxis::X).with_theta(1.3).build();
+        let xs: Vec<f32> = (0..20_000).map(|i| i as f32 * 0.01).collect();
+        let ys: Vec<f32> = xs.iter().map(|x| x.sin()).collect();
+        let zs: Vec<f32> = xs.iter().map(|x| x.cos()).collect();
+        let (mut par_xs, mut par_ys, mut par_zs) = (xs.clone(), ys.clone(), zs.clone());
+        let (mut xs, mut ys, mut zs) = (xs, ys, zs);
+
+        batch_dot_matrix(&mut xs, &mut ys, &mut zs, &matrix);
+        super::par_batch_dot_matrix(&mut par_xs, &mut par_ys, &mut par_zs, &matrix);
+        assert_eq!((xs, ys, zs), (par_xs, par_ys, par_zs));
+    }
+
+    #[test]
+    fn zero_homogeneous_coordinate() {
+        let (mut xs, mut ys, mut zs) = (vec![1.0; 9], vec![1.0; 9], vec![1.0; 9]);
+        batch_dot_matrix(&mut xs, &mut ys, &mut zs, &Matrix4::default());
+        assert!(xs.iter().chain(&ys).chain(&zs).all(|axis| *axis == 0.0));
+    }
+}
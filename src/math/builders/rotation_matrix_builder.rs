@@ -13,11 +13,7 @@ pub struct RotationMatrixBuilder {
 impl RotationMatrixBuilder {
     /// Returns an instance of a builder
     pub fn new() -> Self {
-        Self {
-            speed: DEFAULT_SPEED,
-            theta: DEFAULT_THETA,
-            axis: RotationAxis::X,
-        }
+        Self::default()
     }
 
     pub fn in_axis(mut self, axis: RotationAxis) -> Self {
@@ -48,7 +44,6 @@ impl RotationMatrixBuilder {
 
 pub enum RotationAxis {
     X,
-    #[allow(dead_code)]
     Y,
     Z,
 }
@@ -67,12 +62,12 @@ impl RotationAxis {
                 matrix.matrix[3][3] = 1.0;
             }
             RotationAxis::Y => {
-                matrix.matrix[0][0] = 1.0;
+                matrix.matrix[0][0] = cos;
+                matrix.matrix[0][2] = -sin;
                 matrix.matrix[1][1] = 1.0;
+                matrix.matrix[2][0] = sin;
                 matrix.matrix[2][2] = cos;
-                matrix.matrix[2][3] = sin;
-                matrix.matrix[3][2] = -sin;
-                matrix.matrix[3][3] = cos;
+                matrix.matrix[3][3] = 1.0;
             }
             RotationAxis::Z => {
                 matrix.matrix[0][0] = cos;
@@ -85,3 +80,45 @@ impl RotationAxis {
         }
     }
 }
+
+impl Default for RotationMatrixBuilder {
+    fn default() -> Self {
+        Self {
+            speed: DEFAULT_SPEED,
+            theta: DEFAULT_THETA,
+            axis: RotationAxis::X,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::builders::{RotationAxis, RotationMatrixBuilder};
+    use crate::geometry::vector::Point3;
+    use crate::math::vector_dot_matrix;
+
+    fn assert_rotation(axis: RotationAxis, point: (f32, f32, f32), expected: (f32, f32, f32)) {
+        let matrix = RotationMatrixBuilder::new()
+            .in_axis(axis)
+            .with_theta(std::f32::consts::FRAC_PI_2)
+            .build();
+        let result = vector_dot_matrix(&Point3 { x: point.0, y: point.1, z: point.2 }, &matrix);
+        assert!((result.x - expected.0).abs() < 0.0001);
+        assert!((result.y - expected.1).abs() < 0.0001);
+        assert!((result.z - expected.2).abs() < 0.0001);
+    }
+
+    #[test]
+    fn rotation_keeps_axis() {
+        assert_rotation(RotationAxis::X, (1.0, 0.0, 0.0), (1.0, 0.0, 0.0));
+        assert_rotation(RotationAxis::Y, (0.0, 1.0, 0.0), (0.0, 1.0, 0.0));
+        assert_rotation(RotationAxis::Z, (0.0, 0.0, 1.0), (0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn quarter_rotation() {
+        assert_rotation(RotationAxis::X, (0.0, 1.0, 0.0), (0.0, 0.0, 1.0));
+        assert_rotation(RotationAxis::Y, (0.0, 0.0, 1.0), (1.0, 0.0, 0.0));
+        assert_rotation(RotationAxis::Z, (1.0, 0.0, 0.0), (0.0, 1.0, 0.0));
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::math::Matrix4;
+
+/// Axes of a batch of points, transformed in place
+pub(crate) type Axes<'a, T> = (&'a mut [T], &'a mut [T], &'a mut [T]);
+
+/// Floating-point precision of the geometry, `f32` or `f64`. The renderer works in `f32`, so the
+/// geometry of other precisions is converted once it's relative to the camera.
+pub trait Float:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Send
+    + Sync
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// Difference between one and the next value
+    const EPSILON: Self;
+    const INFINITY: Self;
+
+    /// Returns the closest value to the `f32`
+    fn from_f32(value: f32) -> Self;
+
+    /// Returns the closest `f32` to the value
+    fn to_f32(self) -> f32;
+
+    /// Returns the closest value to the `f64`
+    fn from_f64(value: f64) -> Self;
+
+    /// Returns the value as a `f64`, without losing precision
+    fn to_f64(self) -> f64;
+
+    /// Returns the square root of the value
+    fn sqrt(self) -> Self;
+
+    /// Returns the absolute value
+    fn abs(self) -> Self;
+
+    /// Returns the greatest integer not above the value
+    fn floor(self) -> Self;
+
+    /// Returns the lowest of both values, ignoring NaN
+    fn min(self, other: Self) -> Self;
+
+    /// Returns the greatest of both values, ignoring NaN
+    fn max(self, other: Self) -> Self;
+
+    /// Checks if the value is NaN
+    fn is_nan(self) -> bool;
+
+    /// Transforms the points of a batch filling whole SIMD lanes and returns the remaining ones,
+    /// for [batch_dot_matrix](crate::math::batch_dot_matrix). Only `f32` has lanes, with the
+    /// `simd` feature, so by default all the points remain.
+    #[doc(hidden)]
+    fn dot_matrix_lanes<'a>(xs: &'a mut [Self], ys: &'a mut [Self], zs: &'a mut [Self], _matrix: &Matrix4<Self>) -> Axes<'a, Self> {
+        (xs, ys, zs)
+    }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f32::EPSILON;
+    const INFINITY: Self = f32::INFINITY;
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn floor(self) -> Self {
+        f32::floor(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f32::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f32::max(self, other)
+    }
+
+    fn is_nan(self) -> bool {
+        f32::is_nan(self)
+    }
+
+    #[cfg(feature = "simd")]
+    fn dot_matrix_lanes<'a>(xs: &'a mut [Self], ys: &'a mut [Self], zs: &'a mut [Self], matrix: &Matrix4<Self>) -> Axes<'a, Self> {
+        crate::math::batch_transform::simd::batch_dot_matrix(xs, ys, zs, matrix)
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f64::EPSILON;
+    const INFINITY: Self = f64::INFINITY;
+
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn floor(self) -> Self {
+        f64::floor(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f64::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f64::max(self, other)
+    }
+
+    fn is_nan(self) -> bool {
+        f64::is_nan(self)
+    }
+}
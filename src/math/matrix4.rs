@@ -1,32 +1,123 @@
-use std::ops::Index;
+use std::ops::{Index, Mul};
+use crate::math::Float;
 
 const SIZE: usize = 4;
-type Matrix = [[f32; SIZE]; SIZE];
+type Matrix<T> = [[T; SIZE]; SIZE];
 
-/// Dimension four matrix to use with the vector's display calculation
-#[derive(Debug)]
-pub struct Matrix4 {
-    pub(crate) matrix: Matrix,
+/// Dimension four matrix to use with the vector's display calculation, with `f32` or `f64`
+/// precision
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix4<T: Float = f32> {
+    pub(crate) matrix: Matrix<T>,
 }
 
-impl Matrix4 {
-	pub fn new(matrix: Matrix) -> Self {
+impl<T: Float> Matrix4<T> {
+	pub fn new(matrix: Matrix<T>) -> Self {
 		Self {	matrix	}
 	}
+
+	/// Returns the identity matrix
+	pub fn identity() -> Self {
+		let mut matrix = [[T::ZERO; SIZE]; SIZE];
+		for (i, row) in matrix.iter_mut().enumerate() {
+			row[i] = T::ONE;
+		}
+		Self { matrix }
+	}
+
+	/// Returns the matrix translating the vectors by the given distances
+	///
+	/// # Arguments
+	/// * `x`, `y`, `z` - Distance to move in each axis
+	///
+	pub fn translation(x: T, y: T, z: T) -> Self {
+		let mut matrix = Self::identity();
+		matrix.matrix[3][0] = x;
+		matrix.matrix[3][1] = y;
+		matrix.matrix[3][2] = z;
+		matrix
+	}
+
+	/// Returns the matrix scaling the vectors by the given factors
+	///
+	/// # Arguments
+	/// * `x`, `y`, `z` - Factor to multiply each axis
+	///
+	pub fn scaling(x: T, y: T, z: T) -> Self {
+		let mut matrix = Self::identity();
+		matrix.matrix[0][0] = x;
+		matrix.matrix[1][1] = y;
+		matrix.matrix[2][2] = z;
+		matrix
+	}
+
+	/// Returns the matrix converted to another precision
+	pub fn cast<U: Float>(&self) -> Matrix4<U> {
+		Matrix4 { matrix: self.matrix.map(|row| row.map(|cell| U::from_f64(cell.to_f64()))) }
+	}
 }
 
-impl Default for Matrix4 {
+impl<T: Float> Default for Matrix4<T> {
     fn default() -> Self {
         Self {
-            matrix: [[0.0; SIZE]; SIZE],
+            matrix: [[T::ZERO; SIZE]; SIZE],
         }
     }
 }
 
-impl Index<usize> for Matrix4 {
-    type Output = [f32];
+impl<T: Float> Index<usize> for Matrix4<T> {
+    type Output = [T];
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.matrix[index]
     }
 }
+
+// Matrix multiplication. Vectors are multiplied as rows, so `a * b` applies `a` first and `b` after
+impl<'a, T: Float> Mul<&'a Matrix4<T>> for &'a Matrix4<T> {
+	type Output = Matrix4<T>;
+
+	fn mul(self, rhs: &'a Matrix4<T>) -> Self::Output {
+		let mut matrix = [[T::ZERO; SIZE]; SIZE];
+		for (i, row) in matrix.iter_mut().enumerate() {
+			for (j, cell) in row.iter_mut().enumerate() {
+				*cell = (0..SIZE).fold(T::ZERO, |sum, k| sum + self.matrix[i][k] * rhs.matrix[k][j]);
+			}
+		}
+		Matrix4 { matrix }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::geometry::vector::Point3;
+	use crate::math::{vector_dot_matrix, Matrix4};
+
+	#[test]
+	fn identity_multiplication() {
+		let matrix = Matrix4::translation(1.0, 2.0, 3.0);
+		assert_eq!(&matrix * &Matrix4::identity(), matrix);
+		assert_eq!(&Matrix4::identity() * &matrix, matrix);
+	}
+
+	#[test]
+	fn chained_translation() {
+		let matrix = &Matrix4::translation(1.0, 2.0, 3.0) * &Matrix4::translation(-2.0, 0.0, 1.0);
+		let point = Point3 { x: 1.0, y: 1.0, z: 1.0 };
+		assert_eq!(Point3 { x: 0.0, y: 3.0, z: 5.0 }, vector_dot_matrix(&point, &matrix));
+	}
+
+	#[test]
+	fn scaling_then_translation() {
+		let matrix = &Matrix4::scaling(2.0, 3.0, -1.0) * &Matrix4::translation(1.0, 0.0, 0.0);
+		let point = Point3 { x: 1.0, y: 1.0, z: 1.0 };
+		assert_eq!(Point3 { x: 3.0, y: 3.0, z: -1.0 }, vector_dot_matrix(&point, &matrix));
+	}
+
+	#[test]
+	fn precision_cast() {
+		let matrix: Matrix4<f64> = Matrix4::translation(0.1, 2.0, 3.0);
+		assert_eq!(matrix.cast::<f32>(), Matrix4::translation(0.1_f32, 2.0, 3.0));
+		assert_eq!(matrix.cast::<f64>(), matrix);
+	}
+}
@@ -1,6 +1,12 @@
+pub use batch_transform::{batch_dot_matrix, batch_dot_matrix_scalar};
+#[cfg(feature = "parallel")]
+pub use batch_transform::par_batch_dot_matrix;
+pub use float::Float;
 pub use matrix4::Matrix4;
 pub use vector_matrix_multiplication::vector_dot_matrix;
 
+mod batch_transform;
 pub mod builders;
+mod float;
 pub mod matrix4;
 mod vector_matrix_multiplication;
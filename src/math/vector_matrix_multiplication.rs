@@ -1,44 +1,33 @@
-use crate::math::Matrix4;
+use crate::geometry::vector::{Point3, Point4};
+use crate::math::{Float, Matrix4};
 
-/// Multiplies the vector with the given matrix using the provided Z value
+/// Multiplies the point with the given matrix as a row, dividing the result by its homogeneous
+/// coordinate
 ///
 /// # Arguments
-/// * `vector` - Vector to multiply
+/// * `vector` - Point to multiply
 /// * `matrix` - Matrix to multiply
 ///
-pub fn vector_dot_matrix(vector: Vector, matrix: &Matrix4) -> Vector {
-    let x =
-        vector.0 * matrix[0][0] + vector.1 * matrix[1][0] + vector.2 * matrix[2][0] + matrix[3][0];
-    let y =
-        vector.0 * matrix[0][1] + vector.1 * matrix[1][1] + vector.2 * matrix[2][1] + matrix[3][1];
-    let z =
-        vector.0 * matrix[0][2] + vector.1 * matrix[1][2] + vector.2 * matrix[2][2] + matrix[3][2];
-    let mut w =
-        vector.0 * matrix[0][3] + vector.1 * matrix[1][3] + vector.2 * matrix[2][3] + matrix[3][3];
-
-	if w == 0.0 {
-		w = 0.000001;
-	}
-	
-	(x / w, y / w, z / w)
+pub fn vector_dot_matrix<T: Float>(vector: &Point3<T>, matrix: &Matrix4<T>) -> Point3<T> {
+    (&Point4::from(vector) * matrix).to_cartesian()
 }
 
-type Vector = (f32, f32, f32);
-
 #[cfg(test)]
 mod tests {
     use ferrux_projection_matrix::ProjectionMatrixBuilder;
+    use crate::geometry::vector::Point3;
     use crate::math::{vector_dot_matrix, Matrix4};
 
     #[test]
     fn empty_matrix_multiplication() {
         let matrix = Matrix4::default();
-        assert_eq!((0.0, 0.0, 0.0), vector_dot_matrix((1.0, 1.0, 1.0), &matrix));
+        let point = Point3 { x: 1.0, y: 1.0, z: 1.0 };
+        assert_eq!(Point3::default(), vector_dot_matrix(&point, &matrix));
     }
 
     #[test]
     fn vector_matrix_multiplication() {
-        let point = (1.0, 1.0, 1.0);
+        let point = Point3 { x: 1.0, y: 1.0, z: 1.0 };
         let matrix = ProjectionMatrixBuilder::new()
             .set_height(1)
             .set_width(1)
@@ -47,10 +36,10 @@ mod tests {
             .set_near(1.0)
             .build();
 
-        let result = vector_dot_matrix(point, &Matrix4::new(matrix));
-        let expected = (-1.0, -1.0, 7.0);
-        assert!((result.0 - expected.0).abs() < 0.0001);
-        assert!((result.1 - expected.1).abs() < 0.0001);
-        assert!((result.2 - expected.2).abs() < 0.0001);
+        let result = vector_dot_matrix(&point, &Matrix4::new(matrix));
+        let expected = Point3 { x: -1.0, y: -1.0, z: 7.0 };
+        assert!((result.x - expected.x).abs() < 0.0001);
+        assert!((result.y - expected.y).abs() < 0.0001);
+        assert!((result.z - expected.z).abs() < 0.0001);
     }
 }
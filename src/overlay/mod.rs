@@ -0,0 +1,9 @@
+pub use overlay_canvas::{OverlayCanvas, ScreenPoint};
+pub use overlay_drawable::OverlayDrawable;
+pub use overlay_layer::{Overlay, OverlayId};
+
+mod font;
+mod overlay_canvas;
+mod overlay_drawable;
+mod overlay_layer;
+pub mod shapes;
@@ -0,0 +1,148 @@
+use crate::overlay::font;
+use crate::render::{Framebuffer, RasterVertex, Rasterizer};
+
+/// Point of the screen in pixels, with the origin in the upper-left corner
+pub type ScreenPoint = (f32, f32);
+
+/// Screen-space canvas to draw the overlay on. It works with pixel coordinates and draws over
+/// everything in the framebuffer, ignoring the depth of the scene.
+pub struct OverlayCanvas<'a> {
+    rasterizer: Rasterizer<'a>,
+}
+
+impl<'a> OverlayCanvas<'a> {
+    /// Returns a canvas drawing over the given framebuffer
+    ///
+    /// # Arguments
+    /// * `target` - Framebuffer to draw on
+    ///
+    pub fn new(target: &'a mut dyn Framebuffer) -> Self {
+        Self { rasterizer: Rasterizer::new(target, None) }
+    }
+
+    /// Returns the width of the screen in pixels
+    pub fn width(&self) -> u32 {
+        self.rasterizer.width()
+    }
+
+    /// Returns the height of the screen in pixels
+    pub fn height(&self) -> u32 {
+        self.rasterizer.height()
+    }
+
+    /// Draws a single pixel
+    pub fn draw_point(&mut self, (x, y): ScreenPoint, color: &[u8]) {
+        self.rasterizer.set_pixel(x.floor() as i64, y.floor() as i64, 0.0, to_rgba(color));
+    }
+
+    /// Draws a line between the two points
+    pub fn draw_line(&mut self, start: ScreenPoint, end: ScreenPoint, color: &[u8]) {
+        self.rasterizer.draw_line((start.0, start.1, 0.0), (end.0, end.1, 0.0), to_rgba(color));
+    }
+
+    /// Draws the outline of a rectangle
+    ///
+    /// # Arguments
+    /// * `origin` - Upper-left corner of the rectangle
+    /// * `size` - Width and height of the rectangle
+    /// * `color` - Color of the lines
+    ///
+    pub fn draw_rectangle(&mut self, origin: ScreenPoint, size: (f32, f32), color: &[u8]) {
+        let (left, top) = origin;
+        let (right, bottom) = (left + size.0, top + size.1);
+        self.draw_line((left, top), (right, top), color);
+        self.draw_line((right, top), (right, bottom), color);
+        self.draw_line((right, bottom), (left, bottom), color);
+        self.draw_line((left, bottom), (left, top), color);
+    }
+
+    /// Draws a filled triangle
+    pub fn fill_triangle(&mut self, a: ScreenPoint, b: ScreenPoint, c: ScreenPoint, color: &[u8]) {
+        let vertices = [a, b, c].map(|(x, y)| RasterVertex { position: (x, y, 0.0), varying: () });
+        let color = to_rgba(color);
+        self.rasterizer.fill_triangle(&vertices, |_| Some(color));
+    }
+
+    /// Draws a filled rectangle
+    ///
+    /// # Arguments
+    /// * `origin` - Upper-left corner of the rectangle
+    /// * `size` - Width and height of the rectangle
+    /// * `color` - Color of the rectangle
+    ///
+    pub fn fill_rectangle(&mut self, origin: ScreenPoint, size: (f32, f32), color: &[u8]) {
+        let (left, top) = origin;
+        let (right, bottom) = (left + size.0, top + size.1);
+        self.fill_triangle((left, top), (right, top), (right, bottom), color);
+        self.fill_triangle((left, top), (right, bottom), (left, bottom), color);
+    }
+
+    /// Writes the text with the built-in bitmap font
+    ///
+    /// # Arguments
+    /// * `origin` - Upper-left corner of the first character
+    /// * `text` - Text to write. Unsupported characters are written as `?`
+    /// * `scale` - Size in pixels of each dot of the font
+    /// * `color` - Color of the text
+    ///
+    pub fn draw_text(&mut self, origin: ScreenPoint, text: &str, scale: u32, color: &[u8]) {
+        let scale = scale.max(1);
+        let mut x = origin.0;
+        for character in text.chars() {
+            let glyph = font::glyph(character);
+            for (row, bits) in glyph.iter().enumerate() {
+                for column in 0..font::GLYPH_WIDTH {
+                    if bits & (1 << (font::GLYPH_WIDTH - 1 - column)) != 0 {
+                        let dot_x = x + (column * scale) as f32;
+                        let dot_y = origin.1 + (row as u32 * scale) as f32;
+                        self.draw_dot((dot_x, dot_y), scale, color);
+                    }
+                }
+            }
+            x += (font::GLYPH_ADVANCE * scale) as f32;
+        }
+    }
+
+    /// Draws a square of `size` pixels starting in the given point
+    fn draw_dot(&mut self, point: ScreenPoint, size: u32, color: &[u8]) {
+        for dx in 0..size {
+            for dy in 0..size {
+                self.draw_point((point.0 + dx as f32, point.1 + dy as f32), color);
+            }
+        }
+    }
+}
+
+/// Reads the first four channels of the color, using an opaque alpha if it's missing
+fn to_rgba(color: &[u8]) -> [u8; 4] {
+    let mut rgba = [0, 0, 0, u8::MAX];
+    for (channel, value) in rgba.iter_mut().zip(color) {
+        *channel = *value;
+    }
+    rgba
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OverlayCanvas;
+    use crate::render::MemoryFramebuffer;
+
+    const COLOR: [u8; 4] = [10, 20, 30, 255];
+
+    #[test]
+    fn fill_rectangle_covers_pixels() {
+        let mut buffer = MemoryFramebuffer::new(8, 8);
+        OverlayCanvas::new(&mut buffer).fill_rectangle((2.0, 2.0), (4.0, 3.0), &COLOR);
+        let filled = buffer.pixels().iter().filter(|&&pixel| pixel == COLOR).count();
+        assert_eq!(filled, 12);
+        assert_eq!(buffer.pixel(2, 2), Some(COLOR));
+        assert_eq!(buffer.pixel(6, 2), Some([0; 4]));
+    }
+
+    #[test]
+    fn color_without_alpha() {
+        let mut buffer = MemoryFramebuffer::new(2, 2);
+        OverlayCanvas::new(&mut buffer).draw_point((1.0, 1.0), &[1, 2, 3]);
+        assert_eq!(buffer.pixel(1, 1), Some([1, 2, 3, 255]));
+    }
+}
@@ -0,0 +1,10 @@
+use crate::overlay::OverlayCanvas;
+
+/// Entities than can be drawn in the two-dimensional overlay, on top of the 3D scene
+pub trait OverlayDrawable {
+    /// Draws the entity in the given [OverlayCanvas]
+    ///
+    /// # Arguments
+    /// * `canvas` - Screen-space canvas to draw the entity on
+    fn draw(&self, canvas: &mut OverlayCanvas);
+}
@@ -0,0 +1,95 @@
+use crate::overlay::{OverlayCanvas, OverlayDrawable};
+use crate::slots::{SlotId, Slots};
+
+/// Identifier of an element added to the [Overlay]. It's no longer valid once the overlay is
+/// cleared.
+pub type OverlayId = SlotId;
+
+/// Two-dimensional layer drawn after the 3D scene. It holds the screen-space elements like
+/// selection boxes, crosshairs or status bars.
+#[derive(Default)]
+pub struct Overlay {
+    elements: Slots<Box<dyn OverlayDrawable>>,
+}
+
+impl Overlay {
+    /// Returns a new empty overlay
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an element to the overlay and returns its identifier. Elements are drawn in the same
+    /// order they are added.
+    pub fn add(&mut self, element: Box<dyn OverlayDrawable>) -> OverlayId {
+        self.elements.add(element)
+    }
+
+    /// Replaces the element with the given identifier, keeping its drawing order
+    pub fn replace(&mut self, id: OverlayId, element: Box<dyn OverlayDrawable>) {
+        self.elements.replace(id, element);
+    }
+
+    /// Removes the element with the given identifier
+    pub fn remove(&mut self, id: OverlayId) {
+        self.elements.remove(id);
+    }
+
+    /// Removes all the elements of the overlay
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// Draws all the elements in the given canvas
+    ///
+    /// # Arguments
+    /// * `canvas` - [OverlayCanvas] to draw the elements on
+    pub(crate) fn draw(&self, canvas: &mut OverlayCanvas) {
+        for element in self.elements.iter() {
+            element.draw(canvas);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Overlay;
+    use crate::overlay::shapes::Crosshair;
+    use crate::overlay::OverlayCanvas;
+    use crate::render::MemoryFramebuffer;
+
+    fn crosshair(color: [u8; 4]) -> Box<Crosshair> {
+        Box::new(Crosshair { center: (1.0, 1.0), radius: 1.0, color })
+    }
+
+    /// Returns the color of the center of the overlay drawn in a small frame
+    fn center(overlay: &Overlay) -> Option<[u8; 4]> {
+        let mut frame = MemoryFramebuffer::new(3, 3);
+        overlay.draw(&mut OverlayCanvas::new(&mut frame));
+        frame.pixel(1, 1)
+    }
+
+    #[test]
+    fn removed_ids() {
+        let mut overlay = Overlay::new();
+        let removed = overlay.add(crosshair([255, 0, 0, 255]));
+        overlay.remove(removed);
+        overlay.replace(removed, crosshair([0, 0, 255, 255]));
+        assert_eq!(center(&overlay), Some([0; 4]));
+    }
+
+    #[test]
+    fn stale_ids_after_clear() {
+        let mut overlay = Overlay::new();
+        let stale = overlay.add(crosshair([255, 0, 0, 255]));
+        overlay.clear();
+        let id = overlay.add(crosshair([0, 255, 0, 255]));
+        assert_ne!(stale, id);
+
+        overlay.replace(stale, crosshair([0, 0, 255, 255]));
+        overlay.remove(stale);
+        assert_eq!(center(&overlay), Some([0, 255, 0, 255]));
+
+        overlay.remove(id);
+        assert_eq!(center(&overlay), Some([0; 4]));
+    }
+}
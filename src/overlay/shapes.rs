@@ -0,0 +1,180 @@
+use crate::overlay::{OverlayCanvas, OverlayDrawable, ScreenPoint};
+
+/// Straight line between two points of the screen
+pub struct Line {
+    pub start: ScreenPoint,
+    pub end: ScreenPoint,
+    pub color: [u8; 4],
+}
+
+impl OverlayDrawable for Line {
+    fn draw(&self, canvas: &mut OverlayCanvas) {
+        canvas.draw_line(self.start, self.end, &self.color);
+    }
+}
+
+/// Axis-aligned rectangle, drawn as its outline or filled
+pub struct Rectangle {
+    /// Upper-left corner of the rectangle
+    pub origin: ScreenPoint,
+    /// Width and height of the rectangle
+    pub size: (f32, f32),
+    pub color: [u8; 4],
+    pub filled: bool,
+}
+
+impl OverlayDrawable for Rectangle {
+    fn draw(&self, canvas: &mut OverlayCanvas) {
+        if self.filled {
+            canvas.fill_rectangle(self.origin, self.size, &self.color);
+        } else {
+            canvas.draw_rectangle(self.origin, self.size, &self.color);
+        }
+    }
+}
+
+/// Convex polygon, drawn as its outline or filled
+pub struct Polygon {
+    /// Vertices of the polygon in order
+    pub vertices: Vec<ScreenPoint>,
+    pub color: [u8; 4],
+    pub filled: bool,
+}
+
+impl OverlayDrawable for Polygon {
+    fn draw(&self, canvas: &mut OverlayCanvas) {
+        if self.vertices.len() < 2 {
+            return;
+        }
+        if self.filled {
+            let first = self.vertices[0];
+            for pair in self.vertices[1..].windows(2) {
+                canvas.fill_triangle(first, pair[0], pair[1], &self.color);
+            }
+        } else {
+            for pair in self.vertices.windows(2) {
+                canvas.draw_line(pair[0], pair[1], &self.color);
+            }
+            canvas.draw_line(self.vertices[self.vertices.len() - 1], self.vertices[0], &self.color);
+        }
+    }
+}
+
+/// Cross centered in a point of the screen
+pub struct Crosshair {
+    pub center: ScreenPoint,
+    /// Length of each arm of the cross
+    pub radius: f32,
+    pub color: [u8; 4],
+}
+
+impl OverlayDrawable for Crosshair {
+    fn draw(&self, canvas: &mut OverlayCanvas) {
+        let (x, y) = self.center;
+        canvas.draw_line((x - self.radius, y), (x + self.radius, y), &self.color);
+        canvas.draw_line((x, y - self.radius), (x, y + self.radius), &self.color);
+    }
+}
+
+/// Image drawn pixel by pixel. Pixels with an alpha of zero are skipped.
+pub struct Sprite {
+    /// Upper-left corner of the sprite
+    pub origin: ScreenPoint,
+    width: u32,
+    height: u32,
+    /// RGBA pixels of the image, row by row
+    pixels: Vec<u8>,
+}
+
+impl Sprite {
+    /// Returns a sprite with the given image
+    ///
+    /// # Arguments
+    /// * `origin` - Upper-left corner of the sprite
+    /// * `width` - Number of pixels of each row
+    /// * `height` - Number of rows
+    /// * `pixels` - RGBA pixels of the image, row by row
+    ///
+    /// # Panics
+    /// If the number of pixels doesn't match the size
+    ///
+    pub fn new(origin: ScreenPoint, width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        assert_eq!(pixels.len(), (width * height * 4) as usize, "The pixels don't match the size");
+        Self { origin, width, height, pixels }
+    }
+
+    /// Returns the number of pixels of each row
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the RGBA pixels of the image, row by row
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+}
+
+impl OverlayDrawable for Sprite {
+    fn draw(&self, canvas: &mut OverlayCanvas) {
+        if self.width == 0 {
+            return;
+        }
+        for (i, pixel) in self.pixels.chunks_exact(4).enumerate() {
+            if pixel[3] == 0 {
+                continue;
+            }
+            let x = self.origin.0 + (i as u32 % self.width) as f32;
+            let y = self.origin.1 + (i as u32 / self.width) as f32;
+            canvas.draw_point((x, y), pixel);
+        }
+    }
+}
+
+/// Text written with the built-in bitmap font
+pub struct Text {
+    /// Upper-left corner of the first character
+    pub origin: ScreenPoint,
+    pub content: String,
+    /// Size in pixels of each dot of the font
+    pub scale: u32,
+    pub color: [u8; 4],
+}
+
+impl OverlayDrawable for Text {
+    fn draw(&self, canvas: &mut OverlayCanvas) {
+        canvas.draw_text(self.origin, &self.content, self.scale, &self.color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sprite;
+    use crate::overlay::{OverlayCanvas, OverlayDrawable};
+    use crate::render::MemoryFramebuffer;
+
+    #[test]
+    fn sprite() {
+        let pixels = [[255, 0, 0, 255], [0, 0, 0, 0], [0, 255, 0, 255], [0, 0, 255, 255]].concat();
+        let sprite = Sprite::new((1.0, 1.0), 2, 2, pixels);
+        let mut frame = MemoryFramebuffer::new(4, 4);
+        sprite.draw(&mut OverlayCanvas::new(&mut frame));
+        assert_eq!(frame.pixel(1, 1), Some([255, 0, 0, 255]));
+        assert_eq!(frame.pixel(2, 1), Some([0, 0, 0, 0]));
+        assert_eq!(frame.pixel(1, 2), Some([0, 255, 0, 255]));
+        assert_eq!(frame.pixel(2, 2), Some([0, 0, 255, 255]));
+
+        // Nothing to draw without width
+        Sprite::new((0.0, 0.0), 0, 3, Vec::new()).draw(&mut OverlayCanvas::new(&mut frame));
+    }
+
+    #[test]
+    #[should_panic]
+    fn sprite_with_missing_pixels() {
+        Sprite::new((0.0, 0.0), 2, 2, vec![255; 12]);
+    }
+}
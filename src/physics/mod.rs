@@ -0,0 +1,3 @@
+pub use rigid_body::{resolve_collision, RigidBody};
+
+mod rigid_body;
@@ -0,0 +1,203 @@
+use crate::collision::Contact;
+use crate::geometry::vector::ops::Dot;
+use crate::geometry::vector::Point3;
+
+/// Body moving an actor with simple physics in the fixed step of the
+/// [Environment](crate::environment::Environment)
+///
+/// # Properties
+/// * `velocity` - Units moved per second
+/// * `acceleration` - Own acceleration of the body, added to the gravity
+/// * `mass` - Mass of the body, sharing the impulses of the collisions
+/// * `drag` - Fraction of the velocity lost per second
+/// * `gravity_scale` - Factor of the gravity pulling the body, zero to float
+/// * `restitution` - Fraction of the speed kept when bouncing off other actors, one for a fully
+///   elastic collision. Bodies without one go through the others.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct RigidBody {
+    pub velocity: Point3,
+    pub acceleration: Point3,
+    pub mass: f32,
+    pub drag: f32,
+    pub gravity_scale: f32,
+    pub restitution: Option<f32>,
+}
+
+impl RigidBody {
+    /// Returns a body at rest with a mass of one, pulled by the gravity and without collision
+    /// response
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specifies the initial velocity
+    pub fn with_velocity(mut self, velocity: Point3) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Specifies the own acceleration of the body
+    pub fn with_acceleration(mut self, acceleration: Point3) -> Self {
+        self.acceleration = acceleration;
+        self
+    }
+
+    /// Specifies the mass of the body
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Specifies the fraction of the velocity lost per second
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Specifies the factor of the gravity pulling the body
+    pub fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    /// Makes the body bounce off the other actors keeping the given fraction of its speed
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = Some(restitution);
+        self
+    }
+
+    /// Advances the body the given time and returns the distance it moved. The velocity is
+    /// updated before the position (semi-implicit Euler) to keep the motion stable.
+    ///
+    /// # Arguments
+    /// * `gravity` - Acceleration of the gravity in the environment
+    /// * `step` - Seconds to advance
+    ///
+    pub fn integrate(&mut self, gravity: &Point3, step: f32) -> Point3 {
+        let acceleration = &self.acceleration + gravity * self.gravity_scale;
+        self.velocity += acceleration * step;
+        self.velocity *= (1.0 - self.drag * step).max(0.0);
+        &self.velocity * step
+    }
+
+    /// Returns the inverse of the mass, zero for the bodies not reacting to collisions
+    fn inverse_mass(&self) -> f32 {
+        match self.restitution {
+            Some(_) if self.mass > 0.0 => 1.0 / self.mass,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        Self {
+            velocity: Point3::default(),
+            acceleration: Point3::default(),
+            mass: 1.0,
+            drag: 0.0,
+            gravity_scale: 1.0,
+            restitution: None,
+        }
+    }
+}
+
+/// Bounces the bodies of two colliding actors apart and returns the distances to move each actor
+/// to separate them, or [None] if neither reacts to the collision. Actors without a body are
+/// treated as immovable.
+///
+/// # Arguments
+/// * `a` - Body of the actor of the contact, if any
+/// * `b` - Body of the other actor of the contact, if any
+/// * `contact` - Contact between both actors, seen by the first one
+///
+pub fn resolve_collision(a: Option<&mut RigidBody>, b: Option<&mut RigidBody>, contact: &Contact) -> Option<(Point3, Point3)> {
+    let inverse_mass = |body: &Option<&mut RigidBody>| body.as_ref().map_or(0.0, |body| body.inverse_mass());
+    let (a_inverse, b_inverse) = (inverse_mass(&a), inverse_mass(&b));
+    let total = a_inverse + b_inverse;
+    if total == 0.0 {
+        return None;
+    }
+
+    let velocity = |body: &Option<&mut RigidBody>| body.as_ref().map_or(Point3::default(), |body| body.velocity.clone());
+    let approach = (&(&velocity(&b) - &velocity(&a))).dot(&contact.normal);
+    // Only the bodies getting closer bounce, the separating ones just move apart
+    if approach < 0.0 {
+        let restitution = [&a, &b].into_iter()
+            .filter_map(|body| body.as_ref().and_then(|body| body.restitution))
+            .fold(0.0, f32::max);
+        let impulse = -(1.0 + restitution) * approach / total;
+        if let Some(body) = a {
+            body.velocity -= &contact.normal * (impulse * a_inverse);
+        }
+        if let Some(body) = b {
+            body.velocity += &contact.normal * (impulse * b_inverse);
+        }
+    }
+
+    Some((
+        &contact.normal * (-contact.depth * a_inverse / total),
+        &contact.normal * (contact.depth * b_inverse / total),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_collision, RigidBody};
+    use crate::collision::Contact;
+    use crate::geometry::vector::Point3;
+
+    fn contact(depth: f32) -> Contact {
+        Contact {
+            actor: 0,
+            other: 1,
+            point: Point3::default(),
+            normal: Point3 { x: 1.0, y: 0.0, z: 0.0 },
+            depth,
+        }
+    }
+
+    #[test]
+    fn integration() {
+        let gravity = Point3 { x: 0.0, y: -10.0, z: 0.0 };
+        let mut body = RigidBody::new().with_velocity(Point3 { x: 2.0, y: 0.0, z: 0.0 });
+        let offset = body.integrate(&gravity, 0.5);
+        assert_eq!(body.velocity, Point3 { x: 2.0, y: -5.0, z: 0.0 });
+        assert_eq!(offset, Point3 { x: 1.0, y: -2.5, z: 0.0 });
+
+        let mut floating = RigidBody::new()
+            .with_gravity_scale(0.0)
+            .with_acceleration(Point3 { x: 0.0, y: 0.0, z: 4.0 })
+            .with_drag(1.0);
+        floating.integrate(&gravity, 0.5);
+        assert_eq!(floating.velocity, Point3 { x: 0.0, y: 0.0, z: 1.0 });
+    }
+
+    #[test]
+    fn elastic_collision() {
+        let mut a = RigidBody::new().with_restitution(1.0).with_velocity(Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        let mut b = RigidBody::new().with_restitution(1.0).with_velocity(Point3 { x: -3.0, y: 0.0, z: 0.0 });
+        let (a_offset, b_offset) = resolve_collision(Some(&mut a), Some(&mut b), &contact(0.5)).unwrap();
+        // Equal masses swap their velocities
+        assert_eq!(a.velocity, Point3 { x: -3.0, y: 0.0, z: 0.0 });
+        assert_eq!(b.velocity, Point3 { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(a_offset, Point3 { x: -0.25, y: 0.0, z: 0.0 });
+        assert_eq!(b_offset, Point3 { x: 0.25, y: 0.0, z: 0.0 });
+    }
+
+    #[test]
+    fn static_collision() {
+        // Half the speed is kept bouncing off a wall without a body
+        let mut ball = RigidBody::new().with_restitution(0.5).with_velocity(Point3 { x: 2.0, y: 1.0, z: 0.0 });
+        let (offset, wall) = resolve_collision(Some(&mut ball), None, &contact(0.5)).unwrap();
+        assert_eq!(ball.velocity, Point3 { x: -1.0, y: 1.0, z: 0.0 });
+        assert_eq!(offset, Point3 { x: -0.5, y: 0.0, z: 0.0 });
+        assert_eq!(wall, Point3::default());
+
+        // Bodies without restitution don't react
+        let mut ghost = RigidBody::new().with_velocity(Point3 { x: 2.0, y: 0.0, z: 0.0 });
+        assert!(resolve_collision(Some(&mut ghost), None, &contact(0.5)).is_none());
+        assert_eq!(ghost.velocity, Point3 { x: 2.0, y: 0.0, z: 0.0 });
+    }
+}
@@ -0,0 +1,143 @@
+use crate::lighting::Color;
+use crate::post_process::{FrameContext, PostProcess};
+use crate::render::{Framebuffer, MemoryFramebuffer};
+
+/// Way the fog thickens with the distance to the camera
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FogMode {
+    /// Grows evenly from the start distance until covering everything at the view limit
+    #[default]
+    Linear,
+    /// Grows quickly past the start distance and slowly after, `1 - e^(-density * d)`
+    Exponential,
+    /// Stays clear longer past the start distance and grows quickly after, `1 - e^(-(density * d)²)`
+    ExponentialSquared,
+}
+
+/// Fog blending the drawn surfaces with a color according to their distance to the camera, so
+/// the far objects fade before reaching the view limit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fog {
+    pub mode: FogMode,
+    pub color: Color,
+    /// Distance to the camera where the fog begins
+    pub start: f32,
+    /// Thickness of the exponential fogs, ignored by the linear one
+    pub density: f32,
+    /// Covers the pixels without surfaces too, hiding the background
+    pub background: bool,
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Self { mode: FogMode::default(), color: Color::new(0.5, 0.5, 0.5), start: 0.0, density: 0.05, background: false }
+    }
+}
+
+impl Fog {
+    /// Returns the amount of fog covering a surface, from zero to one
+    ///
+    /// # Arguments
+    /// * `distance` - Distance from the camera to the surface
+    /// * `limit` - View limit of the camera
+    ///
+    pub fn factor(&self, distance: f32, limit: f32) -> f32 {
+        let distance = (distance - self.start).max(0.0);
+        let factor = match self.mode {
+            FogMode::Linear => distance / (limit - self.start).max(f32::EPSILON),
+            FogMode::Exponential => 1.0 - (-self.density * distance).exp(),
+            FogMode::ExponentialSquared => 1.0 - (-(self.density * distance).powi(2)).exp(),
+        };
+        factor.clamp(0.0, 1.0)
+    }
+}
+
+impl PostProcess for Fog {
+    fn apply(&self, frame: &mut MemoryFramebuffer, context: &FrameContext) {
+        let width = frame.width();
+        let limit = context.camera.far();
+        let factors: Vec<f32> = (0..frame.pixels().len() as u32)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if self.background || context.has_surface(x, y, frame) {
+                    self.factor(context.distance(x, y, frame), limit)
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        for (pixel, fog) in frame.pixels_mut().iter_mut().zip(factors) {
+            // The empty pixels stay transparent, keeping the background of the target, unless
+            // the fog covers it
+            if pixel[3] == 0 && !self.background {
+                continue;
+            }
+            let color = Color::from_rgb([pixel[0], pixel[1], pixel[2]]);
+            let [r, g, b, alpha] = (color * (1.0 - fog) + self.color * fog).to_rgba();
+            *pixel = [r, g, b, if pixel[3] == 0 { alpha } else { pixel[3] }];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fog, FogMode};
+    use crate::engine::{EngineCamera, EngineConfig};
+    use crate::lighting::Color;
+    use crate::post_process::{FrameContext, PostProcess};
+    use crate::render::{DepthBuffer, MemoryFramebuffer};
+
+    #[test]
+    fn linear() {
+        let fog = Fog { start: 10.0, ..Fog::default() };
+        assert_eq!(fog.factor(5.0, 30.0), 0.0);
+        assert_eq!(fog.factor(20.0, 30.0), 0.5);
+        assert_eq!(fog.factor(30.0, 30.0), 1.0);
+        assert_eq!(fog.factor(50.0, 30.0), 1.0);
+    }
+
+    #[test]
+    fn exponential() {
+        let exponential = Fog { mode: FogMode::Exponential, start: 10.0, density: 0.1, ..Fog::default() };
+        let squared = Fog { mode: FogMode::ExponentialSquared, ..exponential };
+        assert_eq!(exponential.factor(10.0, 30.0), 0.0);
+        assert!((exponential.factor(20.0, 30.0) - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+        assert!(squared.factor(15.0, 30.0) < exponential.factor(15.0, 30.0));
+        assert!(squared.factor(40.0, 30.0) > exponential.factor(40.0, 30.0));
+        assert!(exponential.factor(1000.0, 30.0) <= 1.0);
+    }
+
+    #[test]
+    fn blend_by_depth() {
+        let config = EngineConfig::default().with_view_limit(20.0);
+        let camera = EngineCamera::new(&config);
+        let mut frame = MemoryFramebuffer::new(4, 4);
+        frame.clear([0, 0, 0, 255]);
+        let mut depth = DepthBuffer::new(4, 4);
+        depth.clear(camera.far());
+        depth.set(0, 0, 1.0 / 4.0);
+        depth.set(1, 0, 1.0 / 12.0);
+
+        let fog = Fog { color: Color::WHITE, start: 4.0, background: true, ..Fog::default() };
+        fog.apply(&mut frame, &FrameContext { depth: &depth, camera: &camera });
+        assert_eq!(frame.pixel(0, 0), Some([0, 0, 0, 255]));
+        assert_eq!(frame.pixel(1, 0), Some([128, 128, 128, 255]));
+        assert_eq!(frame.pixel(2, 0), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn clear_background() {
+        let config = EngineConfig::default().with_view_limit(20.0);
+        let camera = EngineCamera::new(&config);
+        let mut frame = MemoryFramebuffer::new(2, 1);
+        frame.clear([0, 0, 0, 255]);
+        let mut depth = DepthBuffer::new(2, 1);
+        depth.clear(camera.far());
+        depth.set(0, 0, 1.0 / 20.0 + f32::EPSILON);
+
+        let fog = Fog { color: Color::WHITE, ..Fog::default() };
+        fog.apply(&mut frame, &FrameContext { depth: &depth, camera: &camera });
+        assert_eq!(frame.pixel(0, 0), Some([255, 255, 255, 255]));
+        assert_eq!(frame.pixel(1, 0), Some([0, 0, 0, 255]));
+    }
+}
@@ -0,0 +1,8 @@
+pub use fog::{Fog, FogMode};
+pub use post_process_chain::{PostProcessChain, PostProcessId};
+pub use post_process_pass::{FrameContext, PostProcess};
+
+mod fog;
+mod post_process_chain;
+mod post_process_pass;
+pub mod passes;
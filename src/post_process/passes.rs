@@ -0,0 +1,194 @@
+use crate::lighting::Color;
+use crate::post_process::{FrameContext, PostProcess};
+use crate::render::{Framebuffer, MemoryFramebuffer};
+
+const DEFAULT_GAMMA: f32 = 2.2;
+
+/// Converts the linear colors of the frame to the gamma space of the screen
+pub struct GammaCorrection {
+    pub gamma: f32,
+}
+
+impl Default for GammaCorrection {
+    fn default() -> Self {
+        Self { gamma: DEFAULT_GAMMA }
+    }
+}
+
+impl PostProcess for GammaCorrection {
+    fn apply(&self, frame: &mut MemoryFramebuffer, _: &FrameContext) {
+        let exponent = 1.0 / self.gamma;
+        map_colors(frame, |_, _, color| {
+            Color::new(color.r.powf(exponent), color.g.powf(exponent), color.b.powf(exponent))
+        });
+    }
+}
+
+/// Compresses the brightness of the frame, keeping the details of the brightest areas
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToneMapping {
+    /// Reinhard operator, `c / (1 + c)`
+    Reinhard { exposure: f32 },
+    /// Filmic curve approximating the ACES standard
+    Aces { exposure: f32 },
+}
+
+impl PostProcess for ToneMapping {
+    fn apply(&self, frame: &mut MemoryFramebuffer, _: &FrameContext) {
+        let curve = |c: f32| match *self {
+            Self::Reinhard { exposure } => {
+                let c = c * exposure;
+                c / (1.0 + c)
+            }
+            Self::Aces { exposure } => {
+                let c = c * exposure;
+                (c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14)
+            }
+        };
+        map_colors(frame, |_, _, color| Color::new(curve(color.r), curve(color.g), curve(color.b)));
+    }
+}
+
+/// Darkens the borders of the frame
+pub struct Vignette {
+    /// Darkness of the corners, from zero to one
+    pub strength: f32,
+    /// Distance from the center where the darkening starts, relative to the distance to a corner
+    pub radius: f32,
+}
+
+impl PostProcess for Vignette {
+    fn apply(&self, frame: &mut MemoryFramebuffer, _: &FrameContext) {
+        let center = (frame.width() as f32 / 2.0, frame.height() as f32 / 2.0);
+        let corner = (center.0 * center.0 + center.1 * center.1).sqrt().max(f32::EPSILON);
+        map_colors(frame, |x, y, color| {
+            let (dx, dy) = (x as f32 + 0.5 - center.0, y as f32 + 0.5 - center.1);
+            let distance = (dx * dx + dy * dy).sqrt() / corner;
+            let edge = ((distance - self.radius) / (1.0 - self.radius).max(f32::EPSILON)).clamp(0.0, 1.0);
+            color * (1.0 - self.strength * edge * edge)
+        });
+    }
+}
+
+/// Adjusts the colors of the frame. The default values leave them unchanged.
+pub struct ColorGrading {
+    /// Value added to all the channels
+    pub brightness: f32,
+    /// Scale of the distance of the channels to the middle grey
+    pub contrast: f32,
+    /// Scale of the distance of the channels to the grey of the same luma
+    pub saturation: f32,
+    /// Color multiplying all the pixels
+    pub tint: Color,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self { brightness: 0.0, contrast: 1.0, saturation: 1.0, tint: Color::WHITE }
+    }
+}
+
+impl PostProcess for ColorGrading {
+    fn apply(&self, frame: &mut MemoryFramebuffer, _: &FrameContext) {
+        map_colors(frame, |_, _, color| {
+            let grey = luma(color);
+            let saturate = |c: f32| grey + (c - grey) * self.saturation;
+            let adjust = |c: f32| (saturate(c) - 0.5) * self.contrast + 0.5 + self.brightness;
+            Color::new(adjust(color.r), adjust(color.g), adjust(color.b)) * self.tint
+        });
+    }
+}
+
+/// Turns the frame into shades of grey
+pub struct Grayscale;
+
+impl PostProcess for Grayscale {
+    fn apply(&self, frame: &mut MemoryFramebuffer, _: &FrameContext) {
+        map_colors(frame, |_, _, color| {
+            let grey = luma(color);
+            Color::new(grey, grey, grey)
+        });
+    }
+}
+
+/// Replaces the color of every drawn pixel, keeping its alpha
+fn map_colors(frame: &mut MemoryFramebuffer, mut map: impl FnMut(u32, u32, Color) -> Color) {
+    let width = frame.width();
+    for (i, pixel) in frame.pixels_mut().iter_mut().enumerate() {
+        if pixel[3] == 0 {
+            continue;
+        }
+        let i = i as u32;
+        let color = map(i % width, i / width, Color::from_rgb([pixel[0], pixel[1], pixel[2]]));
+        let [r, g, b, _] = color.to_rgba();
+        *pixel = [r, g, b, pixel[3]];
+    }
+}
+
+/// Perceived brightness of a color
+fn luma(color: Color) -> f32 {
+    color.r * 0.299 + color.g * 0.587 + color.b * 0.114
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorGrading, GammaCorrection, Grayscale, ToneMapping, Vignette};
+    use crate::engine::{EngineCamera, EngineConfig};
+    use crate::post_process::{FrameContext, PostProcess};
+    use crate::render::{DepthBuffer, Framebuffer, MemoryFramebuffer};
+
+    fn apply(pass: &dyn PostProcess, frame: &mut MemoryFramebuffer, depth: &DepthBuffer) {
+        let camera = EngineCamera::new(&EngineConfig::default());
+        pass.apply(frame, &FrameContext { depth, camera: &camera });
+    }
+
+    fn frame(color: [u8; 4]) -> MemoryFramebuffer {
+        let mut frame = MemoryFramebuffer::new(4, 4);
+        frame.clear(color);
+        frame
+    }
+
+    #[test]
+    fn gamma() {
+        let mut frame = frame([64, 0, 255, 255]);
+        apply(&GammaCorrection::default(), &mut frame, &DepthBuffer::new(4, 4));
+        assert_eq!(frame.pixel(0, 0), Some([136, 0, 255, 255]));
+    }
+
+    #[test]
+    fn tone_mapping() {
+        let mut frame = frame([255, 0, 0, 255]);
+        apply(&ToneMapping::Reinhard { exposure: 1.0 }, &mut frame, &DepthBuffer::new(4, 4));
+        assert_eq!(frame.pixel(0, 0), Some([128, 0, 0, 255]));
+    }
+
+    #[test]
+    fn vignette() {
+        let mut frame = frame([200, 200, 200, 255]);
+        apply(&Vignette { strength: 1.0, radius: 0.5 }, &mut frame, &DepthBuffer::new(4, 4));
+        assert_eq!(frame.pixel(1, 1), Some([200, 200, 200, 255]));
+        assert!(frame.pixel(0, 0).unwrap()[0] < 200);
+    }
+
+    #[test]
+    fn color_grading() {
+        let mut frame = frame([100, 150, 200, 255]);
+        let mut expected = frame.clone();
+        apply(&ColorGrading::default(), &mut frame, &DepthBuffer::new(4, 4));
+        assert_eq!(frame, expected);
+
+        let desaturate = ColorGrading { saturation: 0.0, ..ColorGrading::default() };
+        apply(&desaturate, &mut frame, &DepthBuffer::new(4, 4));
+        apply(&Grayscale, &mut expected, &DepthBuffer::new(4, 4));
+        assert_eq!(frame, expected);
+    }
+
+    #[test]
+    fn grayscale_keeps_empty_pixels() {
+        let mut frame = frame([0; 4]);
+        frame.set_pixel(0, 0, [255, 0, 0, 255]);
+        apply(&Grayscale, &mut frame, &DepthBuffer::new(4, 4));
+        assert_eq!(frame.pixel(0, 0), Some([76, 76, 76, 255]));
+        assert_eq!(frame.pixel(1, 0), Some([0; 4]));
+    }
+}
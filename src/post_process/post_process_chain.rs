@@ -0,0 +1,56 @@
+use crate::post_process::{FrameContext, PostProcess};
+use crate::render::MemoryFramebuffer;
+use crate::slots::{SlotId, Slots};
+
+/// Identifier of a pass added to the [PostProcessChain]. It's no longer valid once the chain is
+/// cleared.
+pub type PostProcessId = SlotId;
+
+/// Ordered list of the post-process passes applied to each frame
+#[derive(Default)]
+pub struct PostProcessChain {
+    passes: Slots<Box<dyn PostProcess>>,
+}
+
+impl PostProcessChain {
+    /// Returns a new empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pass at the end of the chain and returns its identifier
+    pub fn add(&mut self, pass: Box<dyn PostProcess>) -> PostProcessId {
+        self.passes.add(pass)
+    }
+
+    /// Replaces the pass with the given identifier, keeping its position in the chain
+    pub fn replace(&mut self, id: PostProcessId, pass: Box<dyn PostProcess>) {
+        self.passes.replace(id, pass);
+    }
+
+    /// Removes the pass with the given identifier
+    pub fn remove(&mut self, id: PostProcessId) {
+        self.passes.remove(id);
+    }
+
+    /// Removes all the passes of the chain
+    pub fn clear(&mut self) {
+        self.passes.clear();
+    }
+
+    /// Checks if the chain has no passes to apply
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Applies all the passes to the frame, in order
+    ///
+    /// # Arguments
+    /// * `frame` - Frame to process
+    /// * `context` - Depth and camera of the frame
+    pub(crate) fn apply(&self, frame: &mut MemoryFramebuffer, context: &FrameContext) {
+        for pass in self.passes.iter() {
+            pass.apply(frame, context);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::engine::EngineCamera;
+use crate::render::{DepthBuffer, Framebuffer, MemoryFramebuffer};
+
+/// Effects applied to the whole frame once the environment is drawn, before the overlay
+pub trait PostProcess {
+    /// Modifies the pixels of the frame
+    ///
+    /// # Arguments
+    /// * `frame` - Frame with the drawn environment
+    /// * `context` - Depth and camera of the frame
+    fn apply(&self, frame: &mut MemoryFramebuffer, context: &FrameContext);
+}
+
+/// State of the rendered frame available to the post-process passes
+pub struct FrameContext<'a> {
+    /// Depth of the closest fragment of each pixel, maybe at a greater resolution than the frame
+    pub depth: &'a DepthBuffer,
+    /// Camera that rendered the frame
+    pub camera: &'a EngineCamera,
+}
+
+impl<'a> FrameContext<'a> {
+    /// Returns the distance from the camera to the surface drawn in a pixel of the frame. Pixels
+    /// without surfaces are at the view limit of the camera.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Coordinates of the pixel
+    /// * `frame` - Frame the pixel belongs to
+    ///
+    pub fn distance(&self, x: u32, y: u32, frame: &MemoryFramebuffer) -> f32 {
+        self.depth(x, y, frame)
+            .filter(|depth| *depth > 0.0)
+            .map_or(self.camera.far(), |depth| (1.0 / depth).min(self.camera.far()))
+    }
+
+    /// Checks if a surface was drawn in a pixel of the frame, or it only has the background
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Coordinates of the pixel
+    /// * `frame` - Frame the pixel belongs to
+    ///
+    pub fn has_surface(&self, x: u32, y: u32, frame: &MemoryFramebuffer) -> bool {
+        self.depth(x, y, frame).is_some_and(|depth| depth > 1.0 / self.camera.far())
+    }
+
+    /// Returns the depth of the pixel of the frame, from the buffer even if it's bigger
+    fn depth(&self, x: u32, y: u32, frame: &MemoryFramebuffer) -> Option<f32> {
+        let scale_x = self.depth.width() / frame.width().max(1);
+        let scale_y = self.depth.height() / frame.height().max(1);
+        self.depth.depth(x * scale_x.max(1), y * scale_y.max(1))
+    }
+}
@@ -0,0 +1,184 @@
+use crate::render::{Framebuffer, MemoryFramebuffer};
+
+/// Minimum contrast between neighbour pixels to consider them an edge
+const FXAA_EDGE_THRESHOLD: f32 = 1.0 / 16.0;
+/// Fraction of the average luma used to reduce the length of the blur direction
+const FXAA_REDUCE_MUL: f32 = 1.0 / 8.0;
+/// Minimum reduction of the blur direction, for dark areas
+const FXAA_REDUCE_MIN: f32 = 1.0 / 128.0;
+/// Maximum length in pixels of the blur along an edge
+const FXAA_SPAN_MAX: f32 = 8.0;
+
+/// Techniques to smooth the edges of the triangles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AntiAliasing {
+    /// The edges are drawn as they are rasterized
+    #[default]
+    None,
+    /// The frame is rendered at `factor` times the resolution in each axis and then scaled
+    /// down averaging the samples of each pixel
+    Supersampling { factor: u32 },
+    /// Fast approximate anti-aliasing, blurring the frame along the edges found by the contrast
+    /// of the pixels
+    Fxaa,
+}
+
+/// Scales down the frame averaging each square of `factor` by `factor` pixels into the target.
+/// Empty pixels count as black, so the edges over the background get blended with it.
+///
+/// # Arguments
+/// * `source` - Frame rendered at `factor` times the resolution of the target
+/// * `factor` - Number of samples of each pixel in each axis
+/// * `target` - Framebuffer to write the scaled frame
+///
+pub(crate) fn downsample(source: &MemoryFramebuffer, factor: u32, target: &mut dyn Framebuffer) {
+    let samples = factor * factor;
+    for y in 0..target.height() {
+        for x in 0..target.width() {
+            let mut sum = [0u32; 4];
+            for sy in 0..factor {
+                for sx in 0..factor {
+                    let pixel = source.pixel(x * factor + sx, y * factor + sy).unwrap_or_default();
+                    for (total, channel) in sum.iter_mut().zip(pixel) {
+                        *total += channel as u32;
+                    }
+                }
+            }
+            if sum[3] > 0 {
+                let [r, g, b, _] = sum.map(|total| ((total + samples / 2) / samples) as u8);
+                target.set_pixel(x, y, [r, g, b, u8::MAX]);
+            }
+        }
+    }
+}
+
+/// Applies fast approximate anti-aliasing to the frame, writing the result into the target
+///
+/// # Arguments
+/// * `source` - Frame to smooth
+/// * `target` - Framebuffer of the same size to write the smoothed frame
+///
+pub(crate) fn fxaa(source: &MemoryFramebuffer, target: &mut dyn Framebuffer) {
+    let (width, height) = (source.width(), source.height());
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = source.pixel(x, y).unwrap_or_default();
+            let color = smooth(source, x as f32 + 0.5, y as f32 + 0.5).unwrap_or([
+                pixel[0] as f32,
+                pixel[1] as f32,
+                pixel[2] as f32,
+            ]);
+            let alpha = if color == [0.0; 3] { pixel[3] } else { u8::MAX };
+            if alpha > 0 {
+                target.set_pixel(x, y, [color[0] as u8, color[1] as u8, color[2] as u8, alpha]);
+            }
+        }
+    }
+}
+
+/// Returns the smoothed color of the pixel centered in the given point, or [None] if it's not
+/// on an edge
+fn smooth(source: &MemoryFramebuffer, x: f32, y: f32) -> Option<[f32; 3]> {
+    let luma_at = |dx: f32, dy: f32| luma(sample(source, x + dx, y + dy));
+    let (nw, ne, sw, se) = (luma_at(-1.0, -1.0), luma_at(1.0, -1.0), luma_at(-1.0, 1.0), luma_at(1.0, 1.0));
+    let center = luma_at(0.0, 0.0);
+    let min = center.min(nw).min(ne).min(sw).min(se);
+    let max = center.max(nw).max(ne).max(sw).max(se);
+    if max - min < FXAA_EDGE_THRESHOLD * u8::MAX as f32 {
+        return None;
+    }
+
+    // Direction along the edge, perpendicular to the gradient
+    let direction = (-((nw + ne) - (sw + se)), (nw + sw) - (ne + se));
+    let reduce = ((nw + ne + sw + se) * 0.25 * FXAA_REDUCE_MUL).max(FXAA_REDUCE_MIN * u8::MAX as f32);
+    let scale = 1.0 / (direction.0.abs().min(direction.1.abs()) + reduce);
+    let direction = (
+        (direction.0 * scale).clamp(-FXAA_SPAN_MAX, FXAA_SPAN_MAX),
+        (direction.1 * scale).clamp(-FXAA_SPAN_MAX, FXAA_SPAN_MAX),
+    );
+
+    let along = |t: f32| sample(source, x + direction.0 * t, y + direction.1 * t);
+    let inner = average(along(1.0 / 3.0 - 0.5), along(2.0 / 3.0 - 0.5));
+    let outer = average(average(along(-0.5), along(0.5)), inner);
+    let outer_luma = luma(outer);
+    if outer_luma < min || outer_luma > max {
+        Some(inner)
+    } else {
+        Some(outer)
+    }
+}
+
+/// Returns the bilinear interpolation of the colors of the pixels around a point
+fn sample(source: &MemoryFramebuffer, x: f32, y: f32) -> [f32; 3] {
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (left, top) = (x.floor(), y.floor());
+    let (dx, dy) = (x - left, y - top);
+    let max_x = source.width() as i64 - 1;
+    let max_y = source.height() as i64 - 1;
+    let pixel = |px: i64, py: i64| {
+        let p = source.pixel(px.clamp(0, max_x) as u32, py.clamp(0, max_y) as u32).unwrap_or_default();
+        [p[0] as f32, p[1] as f32, p[2] as f32]
+    };
+    let (left, top) = (left as i64, top as i64);
+    let mix = |a: [f32; 3], b: [f32; 3], t: f32| [0, 1, 2].map(|i| a[i] * (1.0 - t) + b[i] * t);
+    let upper = mix(pixel(left, top), pixel(left + 1, top), dx);
+    let lower = mix(pixel(left, top + 1), pixel(left + 1, top + 1), dx);
+    mix(upper, lower, dy)
+}
+
+fn average(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [0, 1, 2].map(|i| (a[i] + b[i]) * 0.5)
+}
+
+/// Perceived brightness of a color
+fn luma(color: [f32; 3]) -> f32 {
+    color[0] * 0.299 + color[1] * 0.587 + color[2] * 0.114
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downsample, fxaa};
+    use crate::render::{Framebuffer, MemoryFramebuffer};
+
+    const WHITE: [u8; 4] = [255; 4];
+
+    #[test]
+    fn downsample_averages() {
+        let mut source = MemoryFramebuffer::new(4, 2);
+        source.set_pixel(0, 0, WHITE);
+        source.set_pixel(2, 0, WHITE);
+        source.set_pixel(3, 0, WHITE);
+        source.set_pixel(2, 1, WHITE);
+        source.set_pixel(3, 1, WHITE);
+        let mut target = MemoryFramebuffer::new(2, 1);
+        downsample(&source, 2, &mut target);
+        assert_eq!(target.pixel(0, 0), Some([64, 64, 64, 255]));
+        assert_eq!(target.pixel(1, 0), Some(WHITE));
+    }
+
+    #[test]
+    fn fxaa_keeps_flat_areas() {
+        let mut source = MemoryFramebuffer::new(4, 4);
+        source.clear([100, 150, 200, 255]);
+        let mut target = MemoryFramebuffer::new(4, 4);
+        fxaa(&source, &mut target);
+        assert_eq!(source, target);
+    }
+
+    #[test]
+    fn fxaa_smooths_edges() {
+        // Staircase of a diagonal edge
+        let mut source = MemoryFramebuffer::new(8, 8);
+        for y in 0..8 {
+            for x in 0..=y {
+                source.set_pixel(x, y, WHITE);
+            }
+        }
+        let mut target = MemoryFramebuffer::new(8, 8);
+        fxaa(&source, &mut target);
+        let blended = target.pixels().iter().filter(|pixel| pixel[0] > 0 && pixel[0] < 255).count();
+        assert!(blended > 0);
+        assert_eq!(target.pixel(0, 7), Some(WHITE));
+        assert_eq!(target.pixel(7, 0), Some([0; 4]));
+    }
+}
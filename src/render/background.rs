@@ -0,0 +1,123 @@
+use crate::engine::EngineCamera;
+use crate::geometry::vector::Point3;
+use crate::lighting::Color;
+use crate::render::Framebuffer;
+use crate::texture::Cubemap;
+use std::sync::Arc;
+
+/// Content drawn behind the environment, where no triangle covers the screen
+#[derive(Clone, Debug, PartialEq)]
+pub enum Background {
+    /// Single color for the whole screen
+    Color(Color),
+    /// Blend from the color of the bottom of the screen to the one of the top
+    Gradient { top: Color, bottom: Color },
+    /// Cubemap around the camera, following its rotation
+    Skybox(Arc<Cubemap>),
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Self::Color(Color::BLACK)
+    }
+}
+
+impl Background {
+    /// Fills the whole target with the background
+    ///
+    /// # Arguments
+    /// * `target` - Framebuffer to draw the background
+    /// * `camera` - Camera rendering the scene
+    ///
+    pub(crate) fn draw(&self, target: &mut dyn Framebuffer, camera: &EngineCamera) {
+        let (width, height) = (target.width(), target.height());
+        match self {
+            Self::Color(color) => fill(target, |_, _| *color),
+            // The rows of the framebuffer go from the top of the screen to the bottom
+            Self::Gradient { top, bottom } => fill(target, |_, y| {
+                let t = (y as f32 + 0.5) / height as f32;
+                *top * (1.0 - t) + *bottom * t
+            }),
+            Self::Skybox(cubemap) => {
+                // The rays are linear across the screen, only the ones of three corners are needed
+                let (screen_width, screen_height) = camera.screen_size();
+                let origin = camera.ray_direction(0.0, 0.0);
+                let right = &camera.ray_direction(screen_width as f32, 0.0) - &origin;
+                let up = &camera.ray_direction(0.0, screen_height as f32) - &origin;
+                fill(target, |x, y| {
+                    let (dx, dy) = ((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32);
+                    cubemap.sample(&Point3 {
+                        x: origin.x + right.x * dx + up.x * dy,
+                        y: origin.y + right.y * dx + up.y * dy,
+                        z: origin.z + right.z * dx + up.z * dy,
+                    })
+                })
+            }
+        }
+    }
+}
+
+/// Sets every pixel of the target to the color returned for its coordinates
+fn fill(target: &mut dyn Framebuffer, color: impl Fn(u32, u32) -> Color) {
+    for y in 0..target.height() {
+        for x in 0..target.width() {
+            target.set_pixel(x, y, color(x, y).to_rgba());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Background;
+    use crate::engine::{EngineCamera, EngineConfig};
+    use crate::lighting::Color;
+    use crate::render::MemoryFramebuffer;
+    use crate::texture::{Cubemap, Texture};
+    use std::sync::Arc;
+
+    fn camera() -> EngineCamera {
+        EngineCamera::new(&EngineConfig::default().with_width(8).with_height(8))
+    }
+
+    #[test]
+    fn clear_color() {
+        let mut frame = MemoryFramebuffer::new(8, 8);
+        Background::Color(Color::new(1.0, 0.0, 0.0)).draw(&mut frame, &camera());
+        assert!(frame.pixels().iter().all(|pixel| *pixel == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn gradient() {
+        let mut frame = MemoryFramebuffer::new(8, 8);
+        let background = Background::Gradient { top: Color::WHITE, bottom: Color::BLACK };
+        background.draw(&mut frame, &camera());
+        let top = frame.pixel(0, 0).unwrap();
+        let bottom = frame.pixel(0, 7).unwrap();
+        assert!(bottom[0] < 32);
+        assert!(top[0] > 224);
+        assert_eq!(frame.pixel(0, 3), frame.pixel(7, 3));
+    }
+
+    #[test]
+    fn skybox_follows_rotation() {
+        let colors = [
+            Color::new(1.0, 0.0, 0.0),
+            Color::new(0.0, 1.0, 0.0),
+            Color::new(0.0, 0.0, 1.0),
+            Color::new(1.0, 1.0, 0.0),
+            Color::new(0.0, 1.0, 1.0),
+            Color::new(1.0, 0.0, 1.0),
+        ];
+        let cubemap = Cubemap::new(colors.map(|color| Texture::new(1, 1, vec![color])));
+        let background = Background::Skybox(Arc::new(cubemap));
+        let mut camera = camera();
+        let mut frame = MemoryFramebuffer::new(8, 8);
+
+        background.draw(&mut frame, &camera);
+        assert_eq!(frame.pixel(4, 4), Some(colors[4].to_rgba()));
+
+        camera.set_rotation(std::f32::consts::PI, 0.0);
+        background.draw(&mut frame, &camera);
+        assert_eq!(frame.pixel(4, 4), Some(colors[5].to_rgba()));
+    }
+}
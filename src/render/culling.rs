@@ -0,0 +1,102 @@
+use crate::geometry::vector::ops::{Cross, Dot};
+use crate::geometry::vector::Point3;
+use crate::geometry::Winding;
+
+/// Faces to discard when drawing a mesh
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CullMode {
+    /// Discards the faces looking away from the camera
+    #[default]
+    Back,
+    /// Discards the faces looking to the camera
+    Front,
+    /// Keeps all the faces
+    None,
+}
+
+/// Pipeline stage discarding the faces of a triangle according to their facing in view space
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Culling {
+    mode: CullMode,
+    winding: Winding,
+}
+
+impl Culling {
+    /// Returns the culling stage for the given configuration, or [None] if no face is discarded
+    pub fn new(mode: CullMode, winding: Winding) -> Option<Self> {
+        match mode {
+            CullMode::None => None,
+            mode => Some(Self { mode, winding }),
+        }
+    }
+
+    /// Checks if the triangle must be discarded
+    ///
+    /// # Arguments
+    /// * `view` - Vertices of the triangle in view space, with the camera in the origin
+    ///
+    pub fn culls(&self, view: &[Point3; 3]) -> bool {
+        let normal = (&(&view[1] - &view[0])).cross(&(&view[2] - &view[0]));
+        let facing = normal.dot(&view[0]);
+        let front = match self.winding {
+            Winding::Clockwise => facing < 0.0,
+            Winding::CounterClockwise => facing > 0.0,
+        };
+        match self.mode {
+            CullMode::Back => !front,
+            CullMode::Front => front,
+            CullMode::None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CullMode, Culling};
+    use crate::geometry::vector::Point3;
+    use crate::geometry::Winding;
+
+    /// Triangle in front of the camera with its vertices in clockwise order as seen from it
+    fn clockwise_triangle() -> [Point3; 3] {
+        [
+            Point3 { x: 0.0, y: 0.0, z: 5.0 },
+            Point3 { x: 0.0, y: 1.0, z: 5.0 },
+            Point3 { x: 1.0, y: 0.0, z: 5.0 },
+        ]
+    }
+
+    fn counter_clockwise_triangle() -> [Point3; 3] {
+        let [a, b, c] = clockwise_triangle();
+        [a, c, b]
+    }
+
+    #[test]
+    fn no_culling() {
+        assert_eq!(Culling::new(CullMode::None, Winding::Clockwise), None);
+    }
+
+    #[test]
+    fn back_culling() {
+        let culling = Culling::new(CullMode::Back, Winding::Clockwise).unwrap();
+        assert!(!culling.culls(&clockwise_triangle()));
+        assert!(culling.culls(&counter_clockwise_triangle()));
+
+        let culling = Culling::new(CullMode::Back, Winding::CounterClockwise).unwrap();
+        assert!(culling.culls(&clockwise_triangle()));
+        assert!(!culling.culls(&counter_clockwise_triangle()));
+    }
+
+    #[test]
+    fn front_culling() {
+        let culling = Culling::new(CullMode::Front, Winding::Clockwise).unwrap();
+        assert!(culling.culls(&clockwise_triangle()));
+        assert!(!culling.culls(&counter_clockwise_triangle()));
+    }
+
+    #[test]
+    fn culling_independent_of_position() {
+        let culling = Culling::new(CullMode::Back, Winding::Clockwise).unwrap();
+        let moved = clockwise_triangle().map(|p| Point3 { x: p.x + 50.0, y: p.y - 20.0, z: p.z });
+        assert!(!culling.culls(&moved));
+    }
+}
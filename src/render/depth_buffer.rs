@@ -0,0 +1,129 @@
+/// Depth of the closest fragment drawn in each pixel. Depths are the inverse of the distance to
+/// the camera, so they can be interpolated linearly in screen space and the greater is the closer.
+#[derive(Clone, Debug, Default)]
+pub struct DepthBuffer {
+    width: u32,
+    height: u32,
+    depths: Vec<f32>,
+    /// Coordinates of the upper-left pixel, only moved for the tiles of a bigger buffer
+    origin: (u32, u32),
+}
+
+impl DepthBuffer {
+    /// Returns a new buffer of the given size, cleared to the infinity
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, depths: vec![0.0; (width * height) as usize], origin: (0, 0) }
+    }
+
+    /// Returns the width in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Clears the buffer, so only the fragments closer than the given distance pass the test
+    pub fn clear(&mut self, far: f32) {
+        self.depths.fill(1.0 / far);
+    }
+
+    /// Sets all the pixels to the given depth
+    pub fn fill(&mut self, depth: f32) {
+        self.depths.fill(depth);
+    }
+
+    /// Changes the size of the buffer if it's different, clearing it
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if self.width != width || self.height != height {
+            *self = Self::new(width, height);
+        }
+    }
+
+    /// Returns the depth stored in a pixel
+    pub fn depth(&self, x: u32, y: u32) -> Option<f32> {
+        self.index(x, y).map(|i| self.depths[i])
+    }
+
+    /// Checks if a fragment is visible in the pixel, not behind the one already drawn
+    pub fn test(&self, x: u32, y: u32, depth: f32) -> bool {
+        self.index(x, y).is_some_and(|i| depth >= self.depths[i])
+    }
+
+    /// Stores the depth of the fragment drawn in the pixel
+    pub fn set(&mut self, x: u32, y: u32, depth: f32) {
+        if let Some(i) = self.index(x, y) {
+            self.depths[i] = depth;
+        }
+    }
+
+    /// Returns a copy of a region of the buffer, keeping the coordinates of its pixels
+    ///
+    /// # Arguments
+    /// * `clip` - Upper-left corner and lower-right one, excluded, of the region
+    ///
+    #[cfg(feature = "parallel")]
+    pub(crate) fn tile(&self, clip: [u32; 4]) -> Self {
+        let [min_x, min_y, max_x, max_y] = clip;
+        let mut tile = Self::new(max_x - min_x, max_y - min_y);
+        tile.origin = (min_x, min_y);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                tile.set(x, y, self.depth(x, y).unwrap_or_default());
+            }
+        }
+        tile
+    }
+
+    /// Writes back the depths of a tile taken from this buffer
+    #[cfg(feature = "parallel")]
+    pub(crate) fn copy_tile(&mut self, tile: &DepthBuffer) {
+        let (min_x, min_y) = tile.origin;
+        for y in min_y..min_y + tile.height {
+            for x in min_x..min_x + tile.width {
+                if let Some(depth) = tile.depth(x, y) {
+                    self.set(x, y, depth);
+                }
+            }
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        let (x, y) = (x.checked_sub(self.origin.0)?, y.checked_sub(self.origin.1)?);
+        (x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DepthBuffer;
+
+    #[test]
+    fn closer_fragments_pass() {
+        let mut buffer = DepthBuffer::new(2, 2);
+        buffer.clear(100.0);
+        assert!(!buffer.test(0, 0, 1.0 / 200.0));
+        assert!(buffer.test(0, 0, 1.0 / 50.0));
+        buffer.set(0, 0, 1.0 / 50.0);
+        assert!(!buffer.test(0, 0, 1.0 / 60.0));
+        assert!(buffer.test(0, 0, 1.0 / 40.0));
+        assert!(buffer.test(1, 1, 1.0 / 60.0));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn tiles() {
+        let mut buffer = DepthBuffer::new(4, 4);
+        buffer.set(2, 3, 0.5);
+        let mut tile = buffer.tile([2, 2, 4, 4]);
+        assert_eq!(tile.depth(2, 3), Some(0.5));
+        assert_eq!(tile.depth(0, 0), None);
+        assert!(!tile.test(1, 1, 1.0));
+        tile.set(3, 2, 0.25);
+        buffer.copy_tile(&tile);
+        assert_eq!(buffer.depth(3, 2), Some(0.25));
+        assert_eq!(buffer.depth(2, 3), Some(0.5));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::render::draw_strategy::{DrawStrategy, Fill, HiddenLine, Layered, SmoothFill, Wireframe};
+use crate::lighting::Material;
+use crate::render::Shading;
+
+const WIREFRAME_COLOR: [u8; 4] = [u8::MAX, u8::MAX, u8::MAX, u8::MAX];
+const OVERLAID_WIREFRAME_COLOR: [u8; 4] = [0, u8::MAX, 0, u8::MAX];
+
+/// Available ways to draw the meshes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DrawMode {
+    /// Filled faces, skipping the ones hidden from the camera
+    #[default]
+    Filled,
+    /// Edges of all the faces, even the hidden ones
+    Wireframe,
+    /// Edges of the faces visible from the camera, hiding the parts behind other faces
+    HiddenLine,
+    /// Filled faces with their edges drawn on top, skipping the hidden ones
+    WireframeOverFilled,
+    /// Filled faces, including the hidden ones
+    NoCulling,
+}
+
+impl DrawMode {
+    /// Returns the mode following this one, to cycle through all of them
+    pub fn next(self) -> Self {
+        match self {
+            Self::Filled => Self::Wireframe,
+            Self::Wireframe => Self::HiddenLine,
+            Self::HiddenLine => Self::WireframeOverFilled,
+            Self::WireframeOverFilled => Self::NoCulling,
+            Self::NoCulling => Self::Filled,
+        }
+    }
+
+    /// Checks if the mode discards the faces according to the culling mode
+    pub(crate) fn culls(self) -> bool {
+        !matches!(self, Self::Wireframe | Self::NoCulling)
+    }
+
+    /// Builds the drawing strategy of the mode. This is meant to be done when the mode is set,
+    /// so the drawing of every triangle doesn't need to check it.
+    ///
+    /// # Arguments
+    /// * `shading` - Way to light the filled faces
+    /// * `material` - Material of the filled faces
+    ///
+    pub(crate) fn strategy(self, shading: Shading, material: Material) -> Box<dyn DrawStrategy> {
+        let fill = || -> Box<dyn DrawStrategy> {
+            match shading {
+                Shading::Flat => Box::new(Fill(material.clone())),
+                Shading::Smooth => Box::new(SmoothFill(material.clone())),
+            }
+        };
+        match self {
+            Self::Filled | Self::NoCulling => fill(),
+            Self::Wireframe => Box::new(Wireframe(WIREFRAME_COLOR)),
+            Self::HiddenLine => Box::new(HiddenLine(WIREFRAME_COLOR)),
+            Self::WireframeOverFilled => Box::new(Layered(vec![
+                fill(),
+                Box::new(Wireframe(OVERLAID_WIREFRAME_COLOR)),
+            ])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DrawMode;
+
+    #[test]
+    fn next_cycles_all_modes() {
+        let mut mode = DrawMode::default();
+        let mut visited = vec![mode];
+        loop {
+            mode = mode.next();
+            if mode == DrawMode::default() {
+                break;
+            }
+            assert!(!visited.contains(&mode));
+            visited.push(mode);
+        }
+        assert_eq!(visited.len(), 5);
+    }
+}
@@ -0,0 +1,15 @@
+use crate::geometry::Winding;
+use crate::render::{CullMode, DrawMode, Shading};
+
+/// Options to draw a mesh
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawOptions {
+    /// Way to draw the faces
+    pub mode: DrawMode,
+    /// Faces to discard
+    pub cull_mode: CullMode,
+    /// Vertex order of the front faces
+    pub winding: Winding,
+    /// Way to light the faces
+    pub shading: Shading,
+}
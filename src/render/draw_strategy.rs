@@ -0,0 +1,185 @@
+use crate::geometry::vector::Point3;
+use crate::geometry::TextureCoordinate;
+use crate::lighting::{Color, Material};
+use crate::render::pipeline::ViewTriangle;
+use crate::render::rasterizer::{Interpolate, RasterVertex};
+use crate::render::{Rasterizer, RenderContext};
+use crate::texture::Texture;
+
+/// Logic to draw a single triangle of a mesh. It's shared with the threads drawing the tiles of
+/// the frame in parallel.
+pub(crate) trait DrawStrategy: Send + Sync {
+    /// Draws the triangle with the rasterizer
+    ///
+    /// # Arguments
+    /// * `rasterizer` - Rasterizer to draw the triangle
+    /// * `triangle` - Triangle to draw, already in view and screen space
+    /// * `context` - State of the scene
+    ///
+    fn draw(&self, rasterizer: &mut Rasterizer, triangle: &ViewTriangle, context: &RenderContext);
+}
+
+/// Fills the triangle with the light reflected by its center
+pub(crate) struct Fill(pub Material);
+
+impl DrawStrategy for Fill {
+    fn draw(&self, rasterizer: &mut Rasterizer, triangle: &ViewTriangle, context: &RenderContext) {
+        let [a, b, c] = &triangle.world;
+        let center = Point3 {
+            x: (a.x + b.x + c.x) / 3.0,
+            y: (a.y + b.y + c.y) / 3.0,
+            z: (a.z + b.z + c.z) / 3.0,
+        };
+        let reflection = Reflection::new(&center, &triangle.normal, &self.0, context);
+        fill(rasterizer, triangle, &self.0, context, [reflection; 3]);
+    }
+}
+
+/// Fills the triangle interpolating the light reflected by its vertices
+pub(crate) struct SmoothFill(pub Material);
+
+impl DrawStrategy for SmoothFill {
+    fn draw(&self, rasterizer: &mut Rasterizer, triangle: &ViewTriangle, context: &RenderContext) {
+        let reflections = [0, 1, 2].map(|i| {
+            Reflection::new(&triangle.world[i], &triangle.vertex_normals[i], &self.0, context)
+        });
+        fill(rasterizer, triangle, &self.0, context, reflections);
+    }
+}
+
+/// Draws the edges of the triangle with the given color
+pub(crate) struct Wireframe(pub [u8; 4]);
+
+impl DrawStrategy for Wireframe {
+    fn draw(&self, rasterizer: &mut Rasterizer, triangle: &ViewTriangle, _: &RenderContext) {
+        let [a, b, c] = triangle.screen;
+        rasterizer.draw_line(a, b, self.0);
+        rasterizer.draw_line(b, c, self.0);
+        rasterizer.draw_line(c, a, self.0);
+    }
+}
+
+/// Hides what's behind the triangle and draws its edges with the given color, only where they
+/// aren't behind other faces
+pub(crate) struct HiddenLine(pub [u8; 4]);
+
+impl DrawStrategy for HiddenLine {
+    fn draw(&self, rasterizer: &mut Rasterizer, triangle: &ViewTriangle, _: &RenderContext) {
+        let [a, b, c] = triangle.screen;
+        rasterizer.fill_depth(&triangle.screen);
+        rasterizer.draw_hidden_line(a, b, self.0);
+        rasterizer.draw_hidden_line(b, c, self.0);
+        rasterizer.draw_hidden_line(c, a, self.0);
+    }
+}
+
+/// Draws the triangle with each one of the strategies, in order
+pub(crate) struct Layered(pub Vec<Box<dyn DrawStrategy>>);
+
+impl DrawStrategy for Layered {
+    fn draw(&self, rasterizer: &mut Rasterizer, triangle: &ViewTriangle, context: &RenderContext) {
+        for strategy in &self.0 {
+            strategy.draw(rasterizer, triangle, context);
+        }
+    }
+}
+
+/// Light reflected by a point of a surface. The part coming from the light of the shadow map is
+/// kept apart to dim it in the shadowed pixels.
+#[derive(Clone, Copy)]
+struct Reflection {
+    total: Color,
+    shadowed: Color,
+}
+
+impl Reflection {
+    fn new(position: &Point3, normal: &Point3, material: &Material, context: &RenderContext) -> Self {
+        let eye = &context.camera.position();
+        let total = context.lighting.shade(position, normal, eye, material);
+        let shadowed = context.shadow.map_or(Color::BLACK, |shadow| {
+            context.lighting.shade_light(shadow.light(), position, normal, eye, material)
+        });
+        Self { total, shadowed }
+    }
+}
+
+/// Attributes interpolated across a filled face. They are divided by the distance to the camera,
+/// so their linear interpolation in screen space is perspective-correct once multiplied back.
+struct Surface {
+    light: Color,
+    shadowed: Color,
+    coordinate: TextureCoordinate,
+    position: Point3,
+}
+
+impl Interpolate for Surface {
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self {
+        Surface {
+            light: Color::interpolate(values.map(|v| &v.light), weights),
+            shadowed: Color::interpolate(values.map(|v| &v.shadowed), weights),
+            coordinate: TextureCoordinate::interpolate(values.map(|v| &v.coordinate), weights),
+            position: Point3::interpolate(values.map(|v| &v.position), weights),
+        }
+    }
+}
+
+/// Fills the triangle with the light reflected by each vertex, dimmed in the shadows and
+/// modulated by the diffuse map of the material if the triangle has texture coordinates
+///
+/// # Arguments
+/// * `rasterizer` - Rasterizer to draw the triangle
+/// * `triangle` - Triangle to draw
+/// * `material` - Material of the triangle
+/// * `context` - State of the scene
+/// * `reflections` - Light reflected by each vertex
+///
+fn fill(
+    rasterizer: &mut Rasterizer,
+    triangle: &ViewTriangle,
+    material: &Material,
+    context: &RenderContext,
+    reflections: [Reflection; 3],
+) {
+    let texture = material.diffuse_map.as_deref().filter(|_| triangle.texture_coordinates.is_some());
+    let vertices = [0, 1, 2].map(|i| {
+        let w = triangle.screen[i].2;
+        let world = &triangle.world[i];
+        RasterVertex {
+            position: triangle.screen[i],
+            varying: Surface {
+                light: reflections[i].total * w,
+                shadowed: reflections[i].shadowed * w,
+                coordinate: triangle.texture_coordinates.map_or_else(Default::default, |c| c[i] * w),
+                position: Point3 { x: world.x * w, y: world.y * w, z: world.z * w },
+            },
+        }
+    });
+
+    rasterizer.fill_triangle(&vertices, |fragment| {
+        let distance = 1.0 / fragment.depth;
+        let surface = &fragment.varying;
+        let mut color = surface.light * distance;
+        if let Some(shadow) = context.shadow {
+            let position = &surface.position;
+            let position = Point3 { x: position.x * distance, y: position.y * distance, z: position.z * distance };
+            let hidden = 1.0 - shadow.visibility(&position);
+            color = color + surface.shadowed * (-hidden * distance);
+        }
+        if let Some(texture) = texture {
+            color = color * sample(texture, material, surface.coordinate, fragment.depth);
+        }
+        Some(color.to_rgba())
+    });
+}
+
+/// Samples the texture in the interpolated coordinate of a fragment
+///
+/// # Arguments
+/// * `texture` - Texture to sample
+/// * `material` - Material with the sampling options
+/// * `coordinate` - Interpolated texture coordinate divided by the distance
+/// * `depth` - Interpolated inverse of the distance of the fragment
+///
+fn sample(texture: &Texture, material: &Material, coordinate: TextureCoordinate, depth: f32) -> Color {
+    texture.sample(&(coordinate * (1.0 / depth)), &material.sampler)
+}
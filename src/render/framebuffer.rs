@@ -0,0 +1,172 @@
+use crate::FerruxViewport;
+
+/// Target of the pixels drawn by the rasterizer
+pub trait Framebuffer {
+    /// Returns the width in pixels
+    fn width(&self) -> u32;
+
+    /// Returns the height in pixels
+    fn height(&self) -> u32;
+
+    /// Sets the color of a pixel. Coordinates out of the buffer are ignored.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Coordinates of the pixel, with the origin in the upper-left corner
+    /// * `color` - RGBA color of the pixel
+    ///
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]);
+}
+
+/// Framebuffer storing the pixels in memory, to render without a window or to process the frame
+/// before displaying it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryFramebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl MemoryFramebuffer {
+    /// Returns a new framebuffer of the given size with all the pixels transparent black
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![[0; 4]; (width * height) as usize] }
+    }
+
+    /// Returns the color of a pixel, or [None] if the coordinates are out of the buffer
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        self.index(x, y).map(|i| self.pixels[i])
+    }
+
+    /// Returns all the pixels, row by row
+    pub fn pixels(&self) -> &[[u8; 4]] {
+        &self.pixels
+    }
+
+    /// Returns all the pixels, row by row, to modify them
+    pub fn pixels_mut(&mut self) -> &mut [[u8; 4]] {
+        &mut self.pixels
+    }
+
+    /// Writes the pixels into another framebuffer of the same size, skipping the transparent ones
+    pub fn copy_to(&self, target: &mut dyn Framebuffer) {
+        for (i, pixel) in self.pixels.iter().enumerate() {
+            if pixel[3] > 0 {
+                let i = i as u32;
+                target.set_pixel(i % self.width, i / self.width, *pixel);
+            }
+        }
+    }
+
+    /// Sets all the pixels to the given color
+    pub fn clear(&mut self, color: [u8; 4]) {
+        self.pixels.fill(color);
+    }
+
+    /// Changes the size of the buffer, clearing it
+    pub fn resize(&mut self, width: u32, height: u32) {
+        *self = Self::new(width, height);
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+    }
+}
+
+impl Framebuffer for MemoryFramebuffer {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = color;
+        }
+    }
+}
+
+/// Framebuffer drawing the pixels in the viewport of the window
+pub(crate) struct WindowFramebuffer<'a> {
+    viewport: &'a mut FerruxViewport,
+    width: u32,
+    height: u32,
+}
+
+impl<'a> WindowFramebuffer<'a> {
+    /// Returns a framebuffer over the viewport
+    ///
+    /// # Arguments
+    /// * `viewport` - Viewport to draw on
+    /// * `width` - Width of the window in pixels
+    /// * `height` - Height of the window in pixels
+    ///
+    pub fn new(viewport: &'a mut FerruxViewport, width: u32, height: u32) -> Self {
+        Self { viewport, width, height }
+    }
+}
+
+impl<'a> Framebuffer for WindowFramebuffer<'a> {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        if x < self.width && y < self.height {
+            // The depth is already tested by the rasterizer, every pixel is drawn in the front
+            let position = (
+                2.0 * (x as f32 + 0.5) / self.width as f32 - 1.0,
+                2.0 * (y as f32 + 0.5) / self.height as f32 - 1.0,
+                0.0,
+            );
+            self.viewport.draw_point(position, &color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Framebuffer, MemoryFramebuffer};
+
+    #[test]
+    fn set_and_get_pixel() {
+        let mut buffer = MemoryFramebuffer::new(4, 2);
+        buffer.set_pixel(3, 1, [1, 2, 3, 4]);
+        assert_eq!(buffer.pixel(3, 1), Some([1, 2, 3, 4]));
+        assert_eq!(buffer.pixels()[7], [1, 2, 3, 4]);
+        assert_eq!(buffer.pixel(0, 0), Some([0; 4]));
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let mut buffer = MemoryFramebuffer::new(4, 2);
+        buffer.set_pixel(4, 0, [1; 4]);
+        buffer.set_pixel(0, 2, [1; 4]);
+        assert!(buffer.pixels().iter().all(|pixel| *pixel == [0; 4]));
+        assert_eq!(buffer.pixel(4, 0), None);
+    }
+
+    #[test]
+    fn copy_opaque_pixels() {
+        let mut source = MemoryFramebuffer::new(2, 2);
+        source.set_pixel(1, 0, [1, 2, 3, 255]);
+        let mut target = MemoryFramebuffer::new(2, 2);
+        target.clear([9; 4]);
+        source.copy_to(&mut target);
+        assert_eq!(target.pixel(1, 0), Some([1, 2, 3, 255]));
+        assert_eq!(target.pixel(0, 0), Some([9; 4]));
+    }
+
+    #[test]
+    fn clear() {
+        let mut buffer = MemoryFramebuffer::new(2, 2);
+        buffer.clear([9; 4]);
+        assert!(buffer.pixels().iter().all(|pixel| *pixel == [9; 4]));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::engine::EngineCamera;
+use crate::geometry::vector::Point3;
+use crate::geometry::{Aabb, BoundingSphere, Plane};
+use crate::math::Matrix4;
+
+/// Volume of the world seen by the camera, limited by the borders of the screen and the near and
+/// far planes
+pub struct Frustum {
+    /// Limits of the frustum in view space, with the normals pointing inside
+    planes: [Plane; 6],
+    /// Precise view matrix of the camera, the one the pipeline applies to the meshes
+    view_matrix: Matrix4<f64>,
+    offset: f64,
+}
+
+impl Frustum {
+    /// Returns the frustum of the camera in its current position
+    pub fn new(camera: &EngineCamera) -> Self {
+        // A point is on the screen if |x| <= z * slope_x and |y| <= z * slope_y
+        let projection = camera.projection_matrix();
+        let slope_x = (projection[2][3] / projection[0][0]).abs();
+        let slope_y = (projection[2][3] / projection[1][1]).abs();
+        let origin = Point3::default();
+        let side = |x: f32, y: f32, slope: f32| Plane::new(&origin, &Point3 { x, y, z: slope });
+        Self {
+            planes: [
+                side(1.0, 0.0, slope_x),
+                side(-1.0, 0.0, slope_x),
+                side(0.0, 1.0, slope_y),
+                side(0.0, -1.0, slope_y),
+                Plane { normal: Point3 { x: 0.0, y: 0.0, z: 1.0 }, distance: -camera.near() },
+                Plane { normal: Point3 { x: 0.0, y: 0.0, z: -1.0 }, distance: camera.far() },
+            ],
+            view_matrix: camera.precise_view_matrix().clone(),
+            offset: f64::from(camera.offset()),
+        }
+    }
+
+    /// Checks if any part of the sphere, in world space, may be inside the frustum
+    pub fn contains_sphere(&self, sphere: &BoundingSphere) -> bool {
+        let center = self.to_view(&sphere.center);
+        self.planes.iter().all(|plane| plane.signed_distance(&center) >= -sphere.radius)
+    }
+
+    /// Checks if any part of the box, in world space, may be inside the frustum. Boxes near the
+    /// corners of the frustum can be reported as inside while being outside.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let corners = aabb.corners().map(|corner| self.to_view(&corner));
+        self.planes.iter()
+            .all(|plane| corners.iter().any(|corner| plane.signed_distance(corner) >= 0.0))
+    }
+
+    /// Moves a point from world space to the view space of the camera. It's narrowed to `f32`
+    /// once relative to the camera, like the points of the meshes drawn.
+    fn to_view(&self, point: &Point3) -> Point3 {
+        point.cast::<f64>().apply_offset(self.offset).transform(&self.view_matrix).cast()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Frustum;
+    use crate::engine::{EngineCamera, EngineConfig};
+    use crate::geometry::vector::Point3;
+    use crate::geometry::{Aabb, BoundingSphere};
+
+    /// Default camera with a fov of 90 degrees, 3 units in front of the origin
+    fn camera() -> EngineCamera {
+        let config = EngineConfig::default().with_width(100).with_height(100).with_view_limit(50.0);
+        EngineCamera::new(&config)
+    }
+
+    fn frustum() -> Frustum {
+        Frustum::new(&camera())
+    }
+
+    fn sphere(x: f32, y: f32, z: f32, radius: f32) -> BoundingSphere {
+        BoundingSphere { center: Point3 { x, y, z }, radius }
+    }
+
+    #[test]
+    fn spheres() {
+        let frustum = frustum();
+        assert!(frustum.contains_sphere(&sphere(0.0, 0.0, 0.0, 1.0)));
+        assert!(!frustum.contains_sphere(&sphere(0.0, 0.0, -10.0, 1.0)));
+        assert!(!frustum.contains_sphere(&sphere(0.0, 0.0, 60.0, 1.0)));
+        assert!(!frustum.contains_sphere(&sphere(10.0, 0.0, 0.0, 1.0)));
+        assert!(!frustum.contains_sphere(&sphere(0.0, -10.0, 0.0, 1.0)));
+        // Partially inside
+        assert!(frustum.contains_sphere(&sphere(3.5, 0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn boxes() {
+        let frustum = frustum();
+        let aabb = |min: (f32, f32, f32), max: (f32, f32, f32)| Aabb {
+            min: Point3 { x: min.0, y: min.1, z: min.2 },
+            max: Point3 { x: max.0, y: max.1, z: max.2 },
+        };
+        assert!(frustum.intersects_aabb(&aabb((-1.0, -1.0, -1.0), (1.0, 1.0, 1.0))));
+        assert!(frustum.intersects_aabb(&aabb((-100.0, -1.0, -1.0), (100.0, 1.0, 1.0))));
+        assert!(!frustum.intersects_aabb(&aabb((-1.0, -1.0, -10.0), (1.0, 1.0, -5.0))));
+        assert!(!frustum.intersects_aabb(&aabb((8.0, -1.0, -1.0), (9.0, 1.0, 1.0))));
+    }
+
+    #[test]
+    fn far_from_origin() {
+        // Half a unit away from the closest position in f32, the spheres near the sides stay
+        // where the pipeline draws them
+        let mut camera = camera();
+        camera.set_position(Point3 { x: 10_000_000.5, y: 0.0, z: 0.0 });
+        let frustum = Frustum::new(&camera);
+        assert!(frustum.contains_sphere(&sphere(10_000_004.0, 0.0, 0.0, 0.5)));
+        assert!(!frustum.contains_sphere(&sphere(10_000_005.0, 0.0, 0.0, 0.5)));
+    }
+}
@@ -0,0 +1,34 @@
+pub use anti_aliasing::AntiAliasing;
+pub use background::Background;
+pub use culling::CullMode;
+pub use depth_buffer::DepthBuffer;
+pub use draw_mode::DrawMode;
+pub use draw_options::DrawOptions;
+pub use framebuffer::{Framebuffer, MemoryFramebuffer};
+pub use frustum::Frustum;
+pub use rasterizer::{Fragment, Interpolate, RasterVertex, Rasterizer, ScreenPosition};
+pub use render_context::RenderContext;
+pub use renderer::Renderer;
+pub use shadow_map::ShadowMap;
+pub use shading::Shading;
+pub(crate) use draw_strategy::DrawStrategy;
+pub(crate) use framebuffer::WindowFramebuffer;
+pub(crate) use pipeline::RenderPipeline;
+
+mod anti_aliasing;
+mod background;
+mod culling;
+mod depth_buffer;
+mod draw_mode;
+mod draw_options;
+mod draw_strategy;
+mod framebuffer;
+mod frustum;
+mod pipeline;
+mod rasterizer;
+mod render_context;
+mod renderer;
+mod shading;
+mod shadow_map;
+#[cfg(feature = "parallel")]
+mod tiles;
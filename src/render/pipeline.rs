@@ -0,0 +1,100 @@
+use crate::geometry::vector::Point3;
+use crate::geometry::{Mesh, Projectable, TextureCoordinate, Winding};
+use crate::math::{Float, Matrix4};
+use crate::render::culling::{CullMode, Culling};
+use crate::render::rasterizer::ScreenPosition;
+use crate::lighting::Material;
+use crate::render::{DrawOptions, DrawStrategy, Rasterizer, RenderContext};
+use std::sync::Arc;
+
+/// Triangle of a mesh ready to be drawn. Meshes of any precision are narrowed to `f32` after the
+/// camera-relative transform, so the drawing strategies only handle this one.
+pub(crate) struct ViewTriangle {
+    /// Vertices in world space
+    pub world: [Point3; 3],
+    /// Vertices in screen space, ready to rasterize
+    pub screen: [ScreenPosition; 3],
+    /// Outwards normal in world space
+    pub normal: Point3,
+    /// Outwards normals of the vertices in world space
+    pub vertex_normals: [Point3; 3],
+    /// Texture coordinates of the vertices, if the mesh has them
+    pub texture_coordinates: Option<[TextureCoordinate; 3]>,
+}
+
+/// Stages to draw the triangles of a mesh: transformation to view space, culling, projection to
+/// the screen and drawing.
+/// It's built when the drawing options change, so the stages don't need to check them.
+pub(crate) struct RenderPipeline {
+    culling: Option<Culling>,
+    winding: Winding,
+    strategy: Arc<dyn DrawStrategy>,
+}
+
+impl RenderPipeline {
+    /// Builds the pipeline for the given options. Some drawing modes ignore the culling mode and
+    /// draw all the faces.
+    ///
+    /// # Arguments
+    /// * `options` - Options to draw the mesh
+    /// * `material` - Material of the mesh
+    ///
+    pub fn new(options: &DrawOptions, material: Material) -> Self {
+        let cull_mode = if options.mode.culls() { options.cull_mode } else { CullMode::None };
+        Self {
+            culling: Culling::new(cull_mode, options.winding),
+            winding: options.winding,
+            strategy: options.mode.strategy(options.shading, material).into(),
+        }
+    }
+
+    /// Draws the triangles of the mesh with the rasterizer, or leaves them in its bins if the
+    /// rasterization is deferred. Triangles crossing the near plane are discarded.
+    ///
+    /// The offset and the view matrix are applied in the precision of the mesh, and the points
+    /// are narrowed to `f32` only once they're relative to the camera.
+    ///
+    /// # Arguments
+    /// * `rasterizer` - Rasterizer to draw the triangles
+    /// * `mesh` - Mesh to draw
+    /// * `context` - State of the scene
+    ///
+    pub fn draw<T: Float>(&self, rasterizer: &mut Rasterizer, mesh: &Mesh<T>, context: &RenderContext) {
+        let camera = context.camera;
+        let offset = T::from_f32(camera.offset());
+        let view_matrix: Matrix4<T> = camera.precise_view_matrix().cast();
+        let triangles = mesh.triangles().zip(mesh.vertex_normals());
+        for (i, (triangle, normals)) in triangles.enumerate() {
+            let world = [&triangle.0, &triangle.1, &triangle.2].map(|point| point.clone().apply_offset(offset));
+            let view: [Point3; 3] = world.each_ref().map(|point| point.transform(&view_matrix).cast());
+            if view.iter().any(|point| point.z < camera.near()) {
+                continue;
+            }
+            if let Some(culling) = &self.culling {
+                if culling.culls(&view) {
+                    continue;
+                }
+            }
+            let screen = view.each_ref().map(|point| to_screen(point, rasterizer, context));
+            let triangle = ViewTriangle {
+                world: world.map(|point| point.cast()),
+                screen,
+                normal: self.winding.orient(triangle.normal()).cast(),
+                vertex_normals: normals.map(|normal| normal.cast()),
+                texture_coordinates: mesh.texture_coordinates.get(i).copied(),
+            };
+            rasterizer.submit(&self.strategy, triangle, context);
+        }
+    }
+}
+
+/// Projects a point in view space to the pixels of the rasterizer target, keeping the inverse of
+/// its distance as depth
+fn to_screen(point: &Point3, rasterizer: &Rasterizer, context: &RenderContext) -> ScreenPosition {
+    let projected = point.get_projection(context.camera.projection_matrix(), 0.0);
+    (
+        (projected.x + 1.0) * 0.5 * rasterizer.width() as f32,
+        (projected.y + 1.0) * 0.5 * rasterizer.height() as f32,
+        1.0 / point.z,
+    )
+}
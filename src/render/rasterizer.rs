@@ -0,0 +1,483 @@
+use crate::geometry::vector::Point3;
+use crate::geometry::TextureCoordinate;
+use crate::lighting::Color;
+use crate::render::pipeline::ViewTriangle;
+#[cfg(feature = "parallel")]
+use crate::render::tiles::{draw_tiles, DeferredTriangle};
+use crate::render::{DepthBuffer, DrawStrategy, Framebuffer, RenderContext};
+use std::sync::Arc;
+
+/// Position in screen space: the pixel coordinates, with the origin in the upper-left corner, and
+/// the depth as the inverse of the distance to the camera
+pub type ScreenPosition = (f32, f32, f32);
+
+/// Fraction of its depth a hidden line can be behind the closest surface and still be drawn, so
+/// the edges aren't hidden by the faces they belong to
+const LINE_DEPTH_BIAS: f32 = 0.01;
+
+/// Attributes that can be interpolated across a triangle
+pub trait Interpolate: Sized {
+    /// Returns the weighted sum of the values of the three vertices
+    ///
+    /// # Arguments
+    /// * `values` - Values of the vertices
+    /// * `weights` - Barycentric weights of the vertices, adding up to one
+    ///
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self;
+}
+
+impl Interpolate for () {
+    fn interpolate(_: [&Self; 3], _: [f32; 3]) -> Self {}
+}
+
+impl Interpolate for f32 {
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self {
+        values[0] * weights[0] + values[1] * weights[1] + values[2] * weights[2]
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self {
+        *values[0] * weights[0] + *values[1] * weights[1] + *values[2] * weights[2]
+    }
+}
+
+impl Interpolate for Point3 {
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self {
+        Point3 {
+            x: f32::interpolate(values.map(|v| &v.x), weights),
+            y: f32::interpolate(values.map(|v| &v.y), weights),
+            z: f32::interpolate(values.map(|v| &v.z), weights),
+        }
+    }
+}
+
+impl Interpolate for TextureCoordinate {
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self {
+        *values[0] * weights[0] + *values[1] * weights[1] + *values[2] * weights[2]
+    }
+}
+
+impl<A: Interpolate, B: Interpolate> Interpolate for (A, B) {
+    fn interpolate(values: [&Self; 3], weights: [f32; 3]) -> Self {
+        (
+            A::interpolate(values.map(|v| &v.0), weights),
+            B::interpolate(values.map(|v| &v.1), weights),
+        )
+    }
+}
+
+/// Vertex of a triangle to rasterize
+pub struct RasterVertex<V> {
+    /// Position of the vertex in screen space
+    pub position: ScreenPosition,
+    /// Attributes of the vertex to interpolate
+    pub varying: V,
+}
+
+/// Sample of a triangle in a pixel
+pub struct Fragment<V> {
+    pub x: u32,
+    pub y: u32,
+    /// Inverse of the distance to the camera
+    pub depth: f32,
+    /// Attributes interpolated in the pixel
+    pub varying: V,
+}
+
+/// Software rasterizer writing the pixels in a [Framebuffer]. If it has a [DepthBuffer] only the
+/// closest fragments are drawn.
+pub struct Rasterizer<'a> {
+    target: &'a mut dyn Framebuffer,
+    depth: Option<&'a mut DepthBuffer>,
+    /// Pixels that can be drawn, from the upper-left corner to the lower-right one excluded
+    clip: [u32; 4],
+    /// Lines waiting to be tested against the depth of the whole frame
+    hidden_lines: Vec<(ScreenPosition, ScreenPosition, [u8; 4])>,
+    /// Triangles waiting to be drawn in tiles, if the rasterization is deferred
+    #[cfg(feature = "parallel")]
+    deferred: Option<Vec<DeferredTriangle>>,
+}
+
+impl<'a> Rasterizer<'a> {
+    /// Returns a rasterizer over the target
+    ///
+    /// # Arguments
+    /// * `target` - Framebuffer to write the pixels
+    /// * `depth` - Depth buffer to test the fragments, if any
+    ///
+    pub fn new(target: &'a mut dyn Framebuffer, depth: Option<&'a mut DepthBuffer>) -> Self {
+        let clip = [0, 0, target.width(), target.height()];
+        Self::clipped(target, depth, clip)
+    }
+
+    /// Returns a rasterizer only drawing the pixels of a region of the target
+    ///
+    /// # Arguments
+    /// * `target` - Framebuffer to write the pixels
+    /// * `depth` - Depth buffer to test the fragments, if any
+    /// * `clip` - Upper-left corner and lower-right one, excluded, of the region to draw
+    ///
+    pub(crate) fn clipped(target: &'a mut dyn Framebuffer, depth: Option<&'a mut DepthBuffer>, clip: [u32; 4]) -> Self {
+        Self {
+            target,
+            depth,
+            clip,
+            hidden_lines: Vec::new(),
+            #[cfg(feature = "parallel")]
+            deferred: None,
+        }
+    }
+
+    /// Keeps the triangles submitted by the pipeline to draw them at once in tiles, in parallel,
+    /// when the rasterizer is flushed
+    #[cfg(feature = "parallel")]
+    pub(crate) fn deferred(mut self) -> Self {
+        self.deferred = Some(Vec::new());
+        self
+    }
+
+    /// Returns the width of the target in pixels
+    pub fn width(&self) -> u32 {
+        self.target.width()
+    }
+
+    /// Returns the height of the target in pixels
+    pub fn height(&self) -> u32 {
+        self.target.height()
+    }
+
+    /// Draws a triangle of the pipeline with its strategy, or keeps it to draw it in the tiles it
+    /// overlaps if the rasterization is deferred
+    ///
+    /// # Arguments
+    /// * `strategy` - Logic to draw the triangle
+    /// * `triangle` - Triangle to draw, already in view and screen space
+    /// * `context` - State of the scene
+    ///
+    pub(crate) fn submit(&mut self, strategy: &Arc<dyn DrawStrategy>, triangle: ViewTriangle, context: &RenderContext) {
+        #[cfg(feature = "parallel")]
+        if let Some(deferred) = &mut self.deferred {
+            deferred.push(DeferredTriangle { strategy: strategy.clone(), triangle });
+            return;
+        }
+        strategy.draw(self, &triangle, context);
+    }
+
+    /// Draws all the deferred triangles, splitting the target in tiles drawn in parallel. The
+    /// result is the same as drawing the triangles one by one as they were submitted.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn flush(&mut self, context: &RenderContext) {
+        if let Some(deferred) = &mut self.deferred {
+            let triangles = std::mem::take(deferred);
+            draw_tiles(&mut *self.target, self.depth.as_deref_mut(), &triangles, context);
+        }
+    }
+
+    /// Draws the lines waiting for the depth of the whole frame, once all the faces are drawn
+    pub(crate) fn draw_hidden_lines(&mut self) {
+        for (start, end, color) in std::mem::take(&mut self.hidden_lines) {
+            self.line(start, end, color, true);
+        }
+    }
+
+    /// Draws a single pixel if it passes the depth test
+    pub fn set_pixel(&mut self, x: i64, y: i64, depth: f32, color: [u8; 4]) {
+        if x < 0 || y < 0 {
+            return;
+        }
+        let (x, y) = (x as u32, y as u32);
+        if !self.in_clip(x, y) {
+            return;
+        }
+        if let Some(buffer) = &mut self.depth {
+            if !buffer.test(x, y, depth) {
+                return;
+            }
+            buffer.set(x, y, depth);
+        }
+        self.target.set_pixel(x, y, color);
+    }
+
+    /// Fills a triangle, sampling the center of each pixel. Pixels exactly on an edge are only
+    /// drawn if it's a top or left edge, so triangles sharing an edge don't draw it twice.
+    ///
+    /// # Arguments
+    /// * `vertices` - Vertices of the triangle, in any order
+    /// * `shader` - Returns the color of each fragment, or [None] to discard it
+    ///
+    pub fn fill_triangle<V, S>(&mut self, vertices: &[RasterVertex<V>; 3], shader: S)
+    where
+        V: Interpolate,
+        S: FnMut(&Fragment<V>) -> Option<[u8; 4]>,
+    {
+        self.fill(vertices, shader, true);
+    }
+
+    /// Writes the depth of a triangle without drawing its pixels, to hide what's behind it
+    ///
+    /// # Arguments
+    /// * `positions` - Vertices of the triangle in screen space, in any order
+    ///
+    pub fn fill_depth(&mut self, positions: &[ScreenPosition; 3]) {
+        let vertices = positions.map(|position| RasterVertex { position, varying: () });
+        self.fill(&vertices, |_| Some([0; 4]), false);
+    }
+
+    /// Fills a triangle writing the depth of the fragments and, if requested, their colors
+    fn fill<V, S>(&mut self, vertices: &[RasterVertex<V>; 3], mut shader: S, write_color: bool)
+    where
+        V: Interpolate,
+        S: FnMut(&Fragment<V>) -> Option<[u8; 4]>,
+    {
+        let [clip_min_x, clip_min_y, clip_max_x, clip_max_y] = self.clip;
+        if clip_min_x >= clip_max_x || clip_min_y >= clip_max_y {
+            return;
+        }
+
+        let mut area = edge(&vertices[0].position, &vertices[1].position, &vertices[2].position);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        // Sort the vertices to have a positive area
+        let [a, b, c] = if area > 0.0 {
+            [&vertices[0], &vertices[1], &vertices[2]]
+        } else {
+            area = -area;
+            [&vertices[0], &vertices[2], &vertices[1]]
+        };
+        let (pa, pb, pc) = (&a.position, &b.position, &c.position);
+
+        let min_x = pa.0.min(pb.0).min(pc.0).floor().max(clip_min_x as f32) as u32;
+        let min_y = pa.1.min(pb.1).min(pc.1).floor().max(clip_min_y as f32) as u32;
+        let max_x = pa.0.max(pb.0).max(pc.0).ceil().min((clip_max_x - 1) as f32) as u32;
+        let max_y = pa.1.max(pb.1).max(pc.1).ceil().min((clip_max_y - 1) as f32) as u32;
+        if pa.0.max(pb.0).max(pc.0) < clip_min_x as f32 || pa.1.max(pb.1).max(pc.1) < clip_min_y as f32 {
+            return;
+        }
+
+        let top_left = [is_top_left(pb, pc), is_top_left(pc, pa), is_top_left(pa, pb)];
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let pixel = (x as f32 + 0.5, y as f32 + 0.5, 0.0);
+                let edges = [edge(pb, pc, &pixel), edge(pc, pa, &pixel), edge(pa, pb, &pixel)];
+                let inside = edges.iter()
+                    .zip(&top_left)
+                    .all(|(&w, &top_left)| w > 0.0 || (w == 0.0 && top_left));
+                if !inside {
+                    continue;
+                }
+
+                let weights = edges.map(|w| w / area);
+                let depth = f32::interpolate([&pa.2, &pb.2, &pc.2], weights);
+                if let Some(buffer) = &self.depth {
+                    if !buffer.test(x, y, depth) {
+                        continue;
+                    }
+                }
+
+                let varying = V::interpolate([&a.varying, &b.varying, &c.varying], weights);
+                if let Some(color) = shader(&Fragment { x, y, depth, varying }) {
+                    if let Some(buffer) = &mut self.depth {
+                        buffer.set(x, y, depth);
+                    }
+                    if write_color {
+                        self.target.set_pixel(x, y, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws a line between two points, interpolating the depth
+    pub fn draw_line(&mut self, start: ScreenPosition, end: ScreenPosition, color: [u8; 4]) {
+        self.line(start, end, color, false);
+    }
+
+    /// Draws a line between two points with the rest of hidden lines, only where it isn't behind the
+    /// depth written by then. It can be over the faces it belongs to, within a small bias. Without
+    /// a depth buffer the whole line is drawn.
+    pub(crate) fn draw_hidden_line(&mut self, start: ScreenPosition, end: ScreenPosition, color: [u8; 4]) {
+        self.hidden_lines.push((start, end, color));
+    }
+
+    /// Draws a line, writing its depth or only testing it with a bias
+    fn line(&mut self, start: ScreenPosition, end: ScreenPosition, color: [u8; 4], hidden: bool) {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let steps = dx.abs().max(dy.abs()).ceil();
+        if !steps.is_finite() {
+            return;
+        }
+        let steps = steps.max(1.0) as u32;
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let x = (start.0 + dx * t).floor() as i64;
+            let y = (start.1 + dy * t).floor() as i64;
+            let depth = start.2 + (end.2 - start.2) * t;
+            if !hidden {
+                self.set_pixel(x, y, depth, color);
+            } else if x >= 0 && y >= 0 && self.in_clip(x as u32, y as u32) {
+                let (x, y) = (x as u32, y as u32);
+                if self.depth.as_ref().is_none_or(|buffer| buffer.test(x, y, depth * (1.0 + LINE_DEPTH_BIAS))) {
+                    self.target.set_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Checks if the pixel is in the region the rasterizer can draw
+    fn in_clip(&self, x: u32, y: u32) -> bool {
+        let [min_x, min_y, max_x, max_y] = self.clip;
+        (min_x..max_x).contains(&x) && (min_y..max_y).contains(&y)
+    }
+}
+
+/// Edge function of the point `p` against the line from `a` to `b`. Its sign tells the side of
+/// the line and its value is twice the area of the triangle formed by the three points.
+fn edge(a: &ScreenPosition, b: &ScreenPosition, p: &ScreenPosition) -> f32 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+/// Checks if the edge from `a` to `b` of a triangle with positive area is a top or left edge
+fn is_top_left(a: &ScreenPosition, b: &ScreenPosition) -> bool {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edge, Interpolate, RasterVertex, Rasterizer};
+    use crate::lighting::Color;
+    use crate::render::{DepthBuffer, Framebuffer, MemoryFramebuffer};
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    fn vertex(x: f32, y: f32, depth: f32) -> RasterVertex<()> {
+        RasterVertex { position: (x, y, depth), varying: () }
+    }
+
+    fn count(buffer: &MemoryFramebuffer, color: [u8; 4]) -> usize {
+        buffer.pixels().iter().filter(|&&pixel| pixel == color).count()
+    }
+
+    #[test]
+    fn edge_sign() {
+        assert!(edge(&(0.0, 0.0, 0.0), &(1.0, 0.0, 0.0), &(0.5, 1.0, 0.0)) > 0.0);
+        assert!(edge(&(0.0, 0.0, 0.0), &(1.0, 0.0, 0.0), &(0.5, -1.0, 0.0)) < 0.0);
+        assert_eq!(edge(&(0.0, 0.0, 0.0), &(2.0, 0.0, 0.0), &(0.0, 2.0, 0.0)), 4.0);
+    }
+
+    #[test]
+    fn interpolation() {
+        let values = [&0.0, &1.0, &2.0];
+        assert_eq!(f32::interpolate(values, [0.25, 0.25, 0.5]), 1.25);
+        let colors = [&Color::WHITE, &Color::BLACK, &Color::BLACK];
+        assert_eq!(Color::interpolate(colors, [0.5, 0.5, 0.0]), Color::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn fill_both_orientations() {
+        let mut buffer = MemoryFramebuffer::new(4, 4);
+        let triangle = [vertex(0.0, 0.0, 1.0), vertex(4.0, 0.0, 1.0), vertex(0.0, 4.0, 1.0)];
+        Rasterizer::new(&mut buffer, None).fill_triangle(&triangle, |_| Some(RED));
+        let filled = count(&buffer, RED);
+        assert!(filled > 0);
+
+        let mut reversed_buffer = MemoryFramebuffer::new(4, 4);
+        let reversed = [vertex(0.0, 0.0, 1.0), vertex(0.0, 4.0, 1.0), vertex(4.0, 0.0, 1.0)];
+        Rasterizer::new(&mut reversed_buffer, None).fill_triangle(&reversed, |_| Some(RED));
+        assert_eq!(buffer, reversed_buffer);
+    }
+
+    #[test]
+    fn top_left_rule_shared_edge() {
+        // Two triangles splitting a square along the diagonal, passing through pixel centers
+        let mut buffer = MemoryFramebuffer::new(4, 4);
+        let mut drawn = 0;
+        {
+            let mut rasterizer = Rasterizer::new(&mut buffer, None);
+            let first = [vertex(0.5, 0.5, 1.0), vertex(3.5, 0.5, 1.0), vertex(3.5, 3.5, 1.0)];
+            let second = [vertex(0.5, 0.5, 1.0), vertex(3.5, 3.5, 1.0), vertex(0.5, 3.5, 1.0)];
+            rasterizer.fill_triangle(&first, |_| { drawn += 1; Some(RED) });
+            rasterizer.fill_triangle(&second, |_| { drawn += 1; Some(BLUE) });
+        }
+        // Every pixel of the square is drawn exactly once
+        assert_eq!(drawn, count(&buffer, RED) + count(&buffer, BLUE));
+        assert_eq!(drawn, 9);
+    }
+
+    #[test]
+    fn depth_test() {
+        let mut buffer = MemoryFramebuffer::new(4, 4);
+        let mut depth = DepthBuffer::new(4, 4);
+        depth.clear(100.0);
+        {
+            let mut rasterizer = Rasterizer::new(&mut buffer, Some(&mut depth));
+            let near = [vertex(0.0, 0.0, 0.5), vertex(4.0, 0.0, 0.5), vertex(0.0, 4.0, 0.5)];
+            let far = [vertex(0.0, 0.0, 0.1), vertex(4.0, 0.0, 0.1), vertex(0.0, 4.0, 0.1)];
+            rasterizer.fill_triangle(&near, |_| Some(RED));
+            rasterizer.fill_triangle(&far, |_| Some(BLUE));
+        }
+        assert!(count(&buffer, RED) > 0);
+        assert_eq!(count(&buffer, BLUE), 0);
+    }
+
+    #[test]
+    fn discarded_fragments() {
+        let mut buffer = MemoryFramebuffer::new(4, 4);
+        let triangle = [vertex(0.0, 0.0, 1.0), vertex(4.0, 0.0, 1.0), vertex(0.0, 4.0, 1.0)];
+        Rasterizer::new(&mut buffer, None).fill_triangle(&triangle, |fragment| {
+            (fragment.x == 0).then_some(RED)
+        });
+        assert!(buffer.pixels().iter().enumerate().all(|(i, &pixel)| pixel != RED || i % 4 == 0));
+    }
+
+    #[test]
+    fn varying_interpolation() {
+        let mut buffer = MemoryFramebuffer::new(3, 1);
+        let triangle = [
+            RasterVertex { position: (0.0, -1.0, 1.0), varying: 0.0 },
+            RasterVertex { position: (3.0, -1.0, 1.0), varying: 3.0 },
+            RasterVertex { position: (0.0, 2.0, 1.0), varying: 0.0 },
+        ];
+        let mut samples = Vec::new();
+        Rasterizer::new(&mut buffer, None).fill_triangle(&triangle, |fragment| {
+            samples.push((fragment.x, fragment.varying));
+            Some(RED)
+        });
+        assert_eq!(samples, vec![(0, 0.5)]);
+    }
+
+    #[test]
+    fn line() {
+        let mut buffer = MemoryFramebuffer::new(4, 4);
+        Rasterizer::new(&mut buffer, None).draw_line((0.5, 0.5, 1.0), (3.5, 3.5, 1.0), RED);
+        for i in 0..4 {
+            assert_eq!(buffer.pixel(i, i), Some(RED));
+        }
+        assert_eq!(count(&buffer, RED), 4);
+        assert_eq!(buffer.width(), 4);
+    }
+
+    #[test]
+    fn hidden_lines() {
+        let mut buffer = MemoryFramebuffer::new(4, 4);
+        let mut depth = DepthBuffer::new(4, 4);
+        depth.clear(100.0);
+        {
+            let mut rasterizer = Rasterizer::new(&mut buffer, Some(&mut depth));
+            // Behind the face drawn later, on its edge and in front of it
+            rasterizer.draw_hidden_line((0.5, 1.5, 0.1), (3.5, 1.5, 0.1), RED);
+            rasterizer.draw_hidden_line((0.0, 0.5, 0.5), (3.9, 0.5, 0.5), BLUE);
+            rasterizer.draw_hidden_line((0.5, 3.5, 0.1), (3.5, 3.5, 0.1), BLUE);
+            rasterizer.fill_depth(&[(0.0, 0.0, 0.5), (4.0, 0.0, 0.5), (4.0, 3.0, 0.5)]);
+            rasterizer.fill_depth(&[(0.0, 0.0, 0.5), (4.0, 3.0, 0.5), (0.0, 3.0, 0.5)]);
+            rasterizer.draw_hidden_lines();
+        }
+        assert_eq!(count(&buffer, RED), 0);
+        assert_eq!(count(&buffer, BLUE), 8);
+        assert_eq!(depth.depth(0, 3), Some(0.01));
+    }
+}
@@ -0,0 +1,13 @@
+use crate::engine::EngineCamera;
+use crate::lighting::Lighting;
+use crate::render::ShadowMap;
+
+/// State of the scene shared by all the entities drawn in a frame
+pub struct RenderContext<'a> {
+    /// Camera rendering the scene
+    pub camera: &'a EngineCamera,
+    /// Light sources of the scene
+    pub lighting: &'a Lighting,
+    /// Depth of the scene from the directional light, if the shadows are enabled
+    pub shadow: Option<&'a ShadowMap>,
+}
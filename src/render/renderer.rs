@@ -0,0 +1,409 @@
+use crate::engine::EngineCamera;
+use crate::environment::Environment;
+use crate::post_process::{Fog, FrameContext, PostProcess, PostProcessChain};
+use crate::render::anti_aliasing::{downsample, fxaa};
+use crate::render::{AntiAliasing, Background, DepthBuffer, Framebuffer, MemoryFramebuffer, Rasterizer};
+
+/// Renders the environment into any [Framebuffer], like the window or a buffer in memory. It
+/// keeps the depth buffer and the intermediate frame between frames to avoid allocating them
+/// every time.
+#[derive(Default)]
+pub struct Renderer {
+    depth: DepthBuffer,
+    /// Resolution and bias of the shadow map, if the shadows are enabled
+    shadows: Option<(u32, f32)>,
+    anti_aliasing: AntiAliasing,
+    /// Content drawn behind the environment, if any. Otherwise, the empty pixels are untouched.
+    background: Option<Background>,
+    fog: Option<Fog>,
+    /// Frame rendered before the anti-aliasing
+    frame: MemoryFramebuffer,
+    post_processing: PostProcessChain,
+    /// Frame to apply the post-processing before copying it to the target
+    output: MemoryFramebuffer,
+}
+
+impl Renderer {
+    /// Returns a new renderer, without shadows, anti-aliasing, background nor fog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables the shadows of the directional light
+    ///
+    /// # Arguments
+    /// * `resolution` - Width and height in texels of the shadow map
+    /// * `bias` - Distance to tolerate between a point and the closest caster, to avoid self-shadowing
+    ///
+    pub fn with_shadows(mut self, resolution: u32, bias: f32) -> Self {
+        self.shadows = Some((resolution, bias));
+        self
+    }
+
+    /// Sets the technique to smooth the edges of the triangles
+    pub fn with_anti_aliasing(mut self, anti_aliasing: AntiAliasing) -> Self {
+        self.anti_aliasing = anti_aliasing;
+        self
+    }
+
+    /// Sets the content drawn behind the environment
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// Sets the fog blending the far surfaces, applied before the rest of the post-processing
+    pub fn with_fog(mut self, fog: Fog) -> Self {
+        self.fog = Some(fog);
+        self
+    }
+
+    /// Returns the passes applied to each frame once the environment is drawn
+    pub fn post_processing(&mut self) -> &mut PostProcessChain {
+        &mut self.post_processing
+    }
+
+    /// Draws the environment as seen by the camera in the target
+    ///
+    /// # Arguments
+    /// * `target` - Framebuffer to draw the frame
+    /// * `environment` - Environment to draw
+    /// * `camera` - Camera rendering the scene
+    ///
+    pub fn render(&mut self, target: &mut dyn Framebuffer, environment: &Environment, camera: &EngineCamera) {
+        if self.fog.is_none() && self.post_processing.is_empty() {
+            return self.resolve(target, environment, camera);
+        }
+        let mut output = take_frame(&mut self.output, target.width(), target.height());
+        self.resolve(&mut output, environment, camera);
+        let context = FrameContext { depth: &self.depth, camera };
+        if let Some(fog) = &self.fog {
+            fog.apply(&mut output, &context);
+        }
+        self.post_processing.apply(&mut output, &context);
+        output.copy_to(target);
+        self.output = output;
+    }
+
+    /// Draws the environment in the target, smoothing the edges with the anti-aliasing
+    fn resolve(&mut self, target: &mut dyn Framebuffer, environment: &Environment, camera: &EngineCamera) {
+        let (width, height) = (target.width(), target.height());
+        match self.anti_aliasing {
+            AntiAliasing::None => self.draw(target, environment, camera),
+            AntiAliasing::Supersampling { factor } => {
+                let factor = factor.max(1);
+                let mut frame = take_frame(&mut self.frame, width * factor, height * factor);
+                self.draw(&mut frame, environment, camera);
+                downsample(&frame, factor, target);
+                self.frame = frame;
+            }
+            AntiAliasing::Fxaa => {
+                let mut frame = take_frame(&mut self.frame, width, height);
+                self.draw(&mut frame, environment, camera);
+                fxaa(&frame, target);
+                self.frame = frame;
+            }
+        }
+    }
+
+    /// Draws the environment directly in the target
+    fn draw(&mut self, target: &mut dyn Framebuffer, environment: &Environment, camera: &EngineCamera) {
+        let shadow = self.shadows
+            .and_then(|(resolution, bias)| environment.shadow_map(camera, resolution, bias));
+
+        if let Some(background) = &self.background {
+            background.draw(target, camera);
+        }
+        self.depth.resize(target.width(), target.height());
+        self.depth.clear(camera.far());
+        #[cfg(not(feature = "parallel"))]
+        let mut rasterizer = Rasterizer::new(target, Some(&mut self.depth));
+        #[cfg(feature = "parallel")]
+        let mut rasterizer = Rasterizer::new(target, Some(&mut self.depth)).deferred();
+        environment.draw(&mut rasterizer, camera, shadow.as_ref());
+    }
+}
+
+/// Takes an intermediate frame of the renderer with the given size, cleared
+fn take_frame(frame: &mut MemoryFramebuffer, width: u32, height: u32) -> MemoryFramebuffer {
+    let mut frame = std::mem::take(frame);
+    if frame.width() != width || frame.height() != height {
+        frame.resize(width, height);
+    } else {
+        frame.clear([0; 4]);
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Renderer;
+    use crate::actors::mesh_actor::MeshActor;
+    use crate::engine::{EngineCamera, EngineConfig};
+    use crate::environment::Environment;
+    use crate::geometry::vector::Point3;
+    use crate::geometry::Mesh;
+    use crate::lighting::{Color, Material};
+    use crate::math::Float;
+    use crate::post_process::passes::Grayscale;
+    use crate::post_process::{Fog, FogMode};
+    use crate::render::{AntiAliasing, Background, DrawMode, MemoryFramebuffer};
+    use crate::texture::Texture;
+    use std::sync::Arc;
+
+    const TRIANGLE: &str = "v -1.0 -1.0 0.0\nv 0.0 1.0 0.0\nv 1.0 -1.0 0.0\nf 1 2 3\n";
+    const TEXTURED_TRIANGLE: &str = "v -1.0 -1.0 0.0\nv 0.0 1.0 0.0\nv 1.0 -1.0 0.0\n\
+        vt 0.0 0.0\nvt 0.5 1.0\nvt 1.0 0.0\nf 1/1 2/2 3/3\n";
+
+    /// Environment rendered without a window
+    struct Scene {
+        environment: Environment,
+        camera: EngineCamera,
+    }
+
+    impl Scene {
+        /// Returns a scene with the actors, seen from the camera of the configuration
+        fn new<T: Float>(config: EngineConfig, actors: impl IntoIterator<Item = MeshActor<T>>) -> Self {
+            let mut environment = Environment::empty(&config);
+            for actor in actors {
+                environment.add_actor(Box::new(actor));
+            }
+            Self { environment, camera: EngineCamera::new(&config) }
+        }
+
+        /// Renders the environment in a new framebuffer of the size of the screen
+        fn render(&self, mut renderer: Renderer) -> MemoryFramebuffer {
+            let (width, height) = self.camera.screen_size();
+            let mut buffer = MemoryFramebuffer::new(width, height);
+            renderer.render(&mut buffer, &self.environment, &self.camera);
+            buffer
+        }
+    }
+
+    /// Returns a scene of 64x64 pixels with the actors, seen from the origin
+    fn scene<T: Float>(config: EngineConfig, actors: impl IntoIterator<Item = MeshActor<T>>) -> Scene {
+        Scene::new(config.with_width(64).with_height(64), actors)
+    }
+
+    /// Returns an actor with the mesh of the OBJ content, drawing both sides of its faces
+    fn actor(content: &str) -> MeshActor {
+        MeshActor::new(Mesh::try_from(content.to_string()).unwrap()).with_draw_mode(DrawMode::NoCulling)
+    }
+
+    /// Returns a material with a plain red texture
+    fn red_texture() -> Material {
+        let texture = Arc::new(Texture::new(1, 1, vec![Color::new(1.0, 0.0, 0.0)]));
+        Material::default().with_diffuse_map(texture)
+    }
+
+    #[test]
+    fn headless_render() {
+        let buffer = scene(EngineConfig::default(), [actor(TRIANGLE)]).render(Renderer::new());
+
+        let center = buffer.pixel(32, 32).unwrap();
+        assert_ne!(center, [0; 4]);
+        assert_eq!(buffer.pixel(0, 0), Some([0; 4]));
+    }
+
+    #[test]
+    fn textured_render() {
+        let actors = [actor(TEXTURED_TRIANGLE).with_material(red_texture())];
+        let buffer = scene(EngineConfig::default(), actors).render(Renderer::new());
+
+        let [r, g, b, _] = buffer.pixel(32, 32).unwrap();
+        assert!(r > 0);
+        assert_eq!((g, b), (0, 0));
+    }
+
+    #[test]
+    fn shadowed_render() {
+        // Small triangle in front of a bigger one, lit from a side of the camera
+        let config = EngineConfig::default().with_light_direction(Point3 { x: 1.0, y: 0.0, z: -1.0 });
+        let background = "v -3.0 -3.0 0.0\nv 0.0 3.0 0.0\nv 3.0 -3.0 0.0\nf 1 2 3\n";
+        let occluder = "v -0.2 -0.2 -1.0\nv 0.0 0.2 -1.0\nv 0.2 -0.2 -1.0\nf 1 2 3\n";
+        let scene = scene(config, [actor(background), actor(occluder)]);
+
+        let lit = scene.render(Renderer::new());
+        let shadowed = scene.render(Renderer::new().with_shadows(256, 0.01));
+
+        // The occluder is lit in both frames, only part of the background gets darker
+        assert_eq!(lit.pixel(32, 32), shadowed.pixel(32, 32));
+        let pixels = lit.pixels().iter().zip(shadowed.pixels());
+        let darker = pixels.clone().filter(|(lit, shadowed)| lit[0] > shadowed[0]).count();
+        assert!(darker > 0);
+        assert!(darker < 64 * 64 / 4);
+        assert!(pixels.clone().all(|(lit, shadowed)| lit[0] >= shadowed[0]));
+        assert!(pixels.filter(|(lit, _)| lit[0] > 0).all(|(_, shadowed)| shadowed[0] > 0));
+    }
+
+    /// Renders the wireframe of a triangle with half-unit vertices moved to the position, seen
+    /// from there
+    fn render_triangle_at<T: Float>(position: Point3<f64>) -> MemoryFramebuffer {
+        let mut mesh = Mesh::<T>::parse("v -0.5 -1.0 0.0\nv 0.0 1.0 0.0\nv 0.5 -1.0 0.0\nf 1 2 3\n").unwrap();
+        mesh.translate(&position.cast());
+        let mut scene = scene(EngineConfig::default(), [MeshActor::new(mesh).with_draw_mode(DrawMode::Wireframe)]);
+        scene.camera.set_position(position);
+        scene.render(Renderer::new())
+    }
+
+    #[test]
+    fn far_from_origin_render() {
+        let origin = render_triangle_at::<f64>(Point3::default());
+        assert!(origin.pixels().iter().any(|pixel| pixel[0] > 0));
+
+        // Ten million units away, f64 keeps the triangle in place while f32 rounds its vertices to
+        // whole units
+        let far = Point3 { x: 10_000_000.5, y: 0.0, z: 0.0 };
+        assert_eq!(render_triangle_at::<f64>(far.clone()).pixels(), origin.pixels());
+        assert_ne!(render_triangle_at::<f32>(far).pixels(), origin.pixels());
+    }
+
+    #[test]
+    fn anti_aliased_render() {
+        let scene = scene(EngineConfig::default(), [actor(TRIANGLE)]);
+        let aliased = scene.render(Renderer::new());
+        let shades = |buffer: &MemoryFramebuffer| {
+            let mut shades: Vec<u8> = buffer.pixels().iter().map(|pixel| pixel[0]).collect();
+            shades.sort_unstable();
+            shades.dedup();
+            shades.len()
+        };
+        assert_eq!(shades(&aliased), 2);
+
+        for anti_aliasing in [AntiAliasing::Supersampling { factor: 4 }, AntiAliasing::Fxaa] {
+            let smooth = scene.render(Renderer::new().with_anti_aliasing(anti_aliasing));
+            assert!(shades(&smooth) > 2, "{:?}", anti_aliasing);
+            assert_eq!(smooth.pixel(32, 32), aliased.pixel(32, 32));
+        }
+    }
+
+    #[test]
+    fn post_processed_render() {
+        let actors = [actor(TEXTURED_TRIANGLE).with_material(red_texture())];
+        let mut renderer = Renderer::new();
+        renderer.post_processing().add(Box::new(Grayscale));
+        let buffer = scene(EngineConfig::default(), actors).render(renderer);
+
+        let [r, g, b, _] = buffer.pixel(32, 32).unwrap();
+        assert!(r > 0);
+        assert_eq!((r, r), (g, b));
+        assert_eq!(buffer.pixel(0, 0), Some([0; 4]));
+    }
+
+    #[test]
+    fn fogged_render() {
+        let scene = scene(EngineConfig::default().with_view_limit(10.0), [actor(TRIANGLE)]);
+        let clear = scene.render(Renderer::new());
+        let fog = Fog {
+            mode: FogMode::Exponential,
+            color: Color::new(0.0, 0.0, 1.0),
+            start: 1.0,
+            density: 0.5,
+            background: true,
+        };
+        let fogged = scene.render(Renderer::new().with_fog(fog));
+
+        // The triangle fades into the fog and the background is covered by it
+        let [r, _, b, _] = fogged.pixel(32, 32).unwrap();
+        let [clear_r, _, clear_b, _] = clear.pixel(32, 32).unwrap();
+        assert!(r < clear_r);
+        assert!(b >= clear_b);
+        let [r, g, b, a] = fogged.pixel(0, 0).unwrap();
+        assert_eq!((r, g, a), (0, 0, 255));
+        assert!(b > 200);
+    }
+
+    #[test]
+    fn background_render() {
+        let scene = scene(EngineConfig::default(), [actor(TRIANGLE)]);
+        let plain = scene.render(Renderer::new());
+        let background = Background::Color(Color::new(0.0, 1.0, 0.0));
+        let buffer = scene.render(Renderer::new().with_background(background.clone()));
+
+        assert_eq!(buffer.pixel(32, 32), plain.pixel(32, 32));
+        assert_eq!(buffer.pixel(0, 0), Some([0, 255, 0, 255]));
+
+        // The fog only covers the surfaces unless told otherwise
+        let fog = Fog { mode: FogMode::Exponential, color: Color::new(0.0, 0.0, 1.0), start: 1.0, ..Fog::default() };
+        let fogged = scene.render(Renderer::new().with_background(background).with_fog(fog));
+        assert_ne!(fogged.pixel(32, 32), plain.pixel(32, 32));
+        assert_eq!(fogged.pixel(0, 0), Some([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn fogged_empty_render() {
+        // The window keeps its background unless the fog covers it
+        let scene = scene::<f32>(EngineConfig::default(), []);
+        let render = |fog: Fog| {
+            let mut buffer = MemoryFramebuffer::new(64, 64);
+            buffer.clear([0, 255, 0, 255]);
+            Renderer::new().with_fog(fog).render(&mut buffer, &scene.environment, &scene.camera);
+            buffer
+        };
+        let fog = Fog { color: Color::new(0.0, 0.0, 1.0), ..Fog::default() };
+        assert!(render(fog).pixels().iter().all(|pixel| *pixel == [0, 255, 0, 255]));
+        let covered = render(Fog { background: true, ..fog });
+        assert!(covered.pixels().iter().all(|pixel| *pixel == [0, 0, 255, 255]));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_render_matches_sequential() {
+        use crate::render::{DepthBuffer, Rasterizer, Shading};
+
+        // Overlapping meshes bigger than a tile, in a frame not divisible in whole tiles
+        let behind = "v -3.0 -2.0 1.0\nv 0.5 2.5 1.0\nv 2.5 -2.0 1.0\nf 1 2 3\n";
+        let crossing = "v -2.0 0.5 -1.0\nv 1.0 1.5 2.0\nv 2.0 -1.5 0.5\nf 1 2 3\n";
+        let actors = [
+            actor(behind).with_draw_mode(DrawMode::WireframeOverFilled),
+            actor(crossing).with_shading(Shading::Smooth),
+            actor(TEXTURED_TRIANGLE).with_material(red_texture()),
+        ];
+        let Scene { environment, camera } = Scene::new(EngineConfig::default().with_width(100).with_height(70), actors);
+
+        let mut renderer = Renderer::new();
+        let mut parallel = MemoryFramebuffer::new(100, 70);
+        renderer.render(&mut parallel, &environment, &camera);
+
+        let mut sequential = MemoryFramebuffer::new(100, 70);
+        let mut depth = DepthBuffer::new(100, 70);
+        depth.clear(camera.far());
+        environment.draw(&mut Rasterizer::new(&mut sequential, Some(&mut depth)), &camera, None);
+
+        assert_eq!(parallel, sequential);
+        assert!(parallel.pixels().iter().any(|pixel| *pixel != [0; 4]));
+        for (x, y) in (0..100).flat_map(|x| (0..70).map(move |y| (x, y))) {
+            assert_eq!(renderer.depth.depth(x, y), depth.depth(x, y));
+        }
+    }
+
+    #[test]
+    fn hidden_line_render() {
+        use crate::geometry::primitives::cube;
+
+        // Small cube right behind a bigger one, that only adds lines if they aren't hidden
+        let render = |hidden: bool| {
+            let mut actors = vec![MeshActor::new(cube(2.0)).with_draw_mode(DrawMode::HiddenLine)];
+            if hidden {
+                let mut behind = cube(0.5);
+                behind.translate(&Point3 { x: 0.0, y: 0.0, z: 3.0 });
+                actors.push(MeshActor::new(behind).with_draw_mode(DrawMode::HiddenLine));
+            }
+            scene(EngineConfig::default(), actors).render(Renderer::new())
+        };
+
+        let front = render(false);
+        assert!(front.pixels().iter().any(|pixel| *pixel != [0; 4]));
+        assert_eq!(render(true), front);
+    }
+
+    #[test]
+    fn culled_render() {
+        let behind = "v -1.0 -1.0 -10.0\nv 0.0 1.0 -10.0\nv 1.0 -1.0 -10.0\nf 1 2 3\n";
+        let scene = scene(EngineConfig::default(), [actor(TRIANGLE), actor(behind)]);
+
+        let buffer = scene.render(Renderer::new());
+        assert_eq!(scene.environment.culled_actors(), 1);
+        assert_ne!(buffer.pixel(32, 32), Some([0; 4]));
+    }
+}
@@ -0,0 +1,9 @@
+/// Ways to light the faces of a mesh
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Shading {
+    /// Same intensity for the whole face, using its normal
+    #[default]
+    Flat,
+    /// Intensity calculated for each vertex with its normal and interpolated across the face
+    Smooth,
+}
@@ -0,0 +1,183 @@
+use crate::geometry::vector::ops::{Cross, Dot, Module, Normalizable};
+use crate::geometry::vector::Point3;
+use crate::lighting::{Light, Lighting};
+use crate::render::rasterizer::RasterVertex;
+use crate::render::{DepthBuffer, Framebuffer, Rasterizer};
+
+/// Minimum extent of the area covered by the map, to avoid dividing by zero with flat scenes
+const MIN_EXTENT: f32 = 0.001;
+
+/// Depth of the scene as seen from the first directional light, used to find the points hidden
+/// from it. The light is orthographic, so the map covers the bounds of the shadow casters.
+pub struct ShadowMap {
+    /// Index of the light casting the shadows in the lighting
+    light: usize,
+    /// Axes of the light space: right, up and forward, towards the scene
+    axes: [Point3; 3],
+    /// Lower corner of the bounds of the casters in light space
+    min: Point3,
+    /// Size of the bounds of the casters in light space
+    extent: Point3,
+    resolution: u32,
+    bias: f32,
+    /// Negative distances to the closest caster, so the greater is the closer
+    depth: DepthBuffer,
+}
+
+impl ShadowMap {
+    /// Renders the depth of the casters from the first directional light of the lighting.
+    /// Returns [None] if there's no directional light or nothing casting shadows.
+    ///
+    /// # Arguments
+    /// * `lighting` - Lights of the scene
+    /// * `casters` - Triangles casting shadows, in world space
+    /// * `resolution` - Width and height of the map in texels
+    /// * `bias` - Distance to tolerate between a point and the closest caster, to avoid self-shadowing
+    ///
+    pub fn new(lighting: &Lighting, casters: &[[Point3; 3]], resolution: u32, bias: f32) -> Option<Self> {
+        let (light, direction) = lighting.lights().iter().enumerate().find_map(|(i, light)| match light {
+            Light::Directional { direction, .. } => Some((i, direction)),
+            _ => None,
+        })?;
+        if casters.is_empty() || resolution == 0 || direction.module() == 0.0 {
+            return None;
+        }
+
+        let axes = light_axes(direction);
+        let to_light_space = |point: &Point3| Point3 {
+            x: point.dot(&axes[0]),
+            y: point.dot(&axes[1]),
+            z: point.dot(&axes[2]),
+        };
+        let casters: Vec<[Point3; 3]> = casters.iter()
+            .map(|triangle| triangle.each_ref().map(to_light_space))
+            .collect();
+
+        let mut min = Point3 { x: f32::MAX, y: f32::MAX, z: f32::MAX };
+        let mut max = Point3 { x: f32::MIN, y: f32::MIN, z: f32::MIN };
+        for point in casters.iter().flatten() {
+            min = Point3 { x: min.x.min(point.x), y: min.y.min(point.y), z: min.z.min(point.z) };
+            max = Point3 { x: max.x.max(point.x), y: max.y.max(point.y), z: max.z.max(point.z) };
+        }
+        let extent = Point3 {
+            x: (max.x - min.x).max(MIN_EXTENT),
+            y: (max.y - min.y).max(MIN_EXTENT),
+            z: (max.z - min.z).max(MIN_EXTENT),
+        };
+
+        let mut depth = DepthBuffer::new(resolution, resolution);
+        depth.fill(f32::NEG_INFINITY);
+        let mut target = DepthOnly(resolution);
+        let mut rasterizer = Rasterizer::new(&mut target, Some(&mut depth));
+        for triangle in &casters {
+            let vertices = triangle.each_ref().map(|point| RasterVertex {
+                position: (
+                    (point.x - min.x) / extent.x * resolution as f32,
+                    (point.y - min.y) / extent.y * resolution as f32,
+                    min.z - point.z,
+                ),
+                varying: (),
+            });
+            rasterizer.fill_triangle(&vertices, |_| Some([0; 4]));
+        }
+
+        Some(Self { light, axes, min, extent, resolution, bias, depth })
+    }
+
+    /// Returns the index of the light casting the shadows
+    pub fn light(&self) -> usize {
+        self.light
+    }
+
+    /// Returns how much of the light reaches the point: one if it's lit and zero if it's in the
+    /// shadow. Points out of the map are lit.
+    ///
+    /// # Arguments
+    /// * `position` - Point in world space
+    ///
+    pub fn visibility(&self, position: &Point3) -> f32 {
+        let x = (position.dot(&self.axes[0]) - self.min.x) / self.extent.x * self.resolution as f32;
+        let y = (position.dot(&self.axes[1]) - self.min.y) / self.extent.y * self.resolution as f32;
+        let distance = position.dot(&self.axes[2]) - self.min.z;
+        if x < 0.0 || y < 0.0 {
+            return 1.0;
+        }
+        match self.depth.depth(x as u32, y as u32) {
+            Some(closest) if distance - self.bias > -closest => 0.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Returns the right, up and forward axes of a light pointing to the given direction. The
+/// direction points to the source, so the forward axis is the opposite.
+fn light_axes(direction: &Point3) -> [Point3; 3] {
+    let forward = Point3 { x: -direction.x, y: -direction.y, z: -direction.z }.normal();
+    let helper = if forward.y.abs() > 0.99 {
+        Point3 { x: 1.0, y: 0.0, z: 0.0 }
+    } else {
+        Point3 { x: 0.0, y: 1.0, z: 0.0 }
+    };
+    let right = helper.cross(&forward).normal();
+    let up = forward.cross(&right);
+    [right, up, forward]
+}
+
+/// Target of the depth pass, discarding the colors
+struct DepthOnly(u32);
+
+impl Framebuffer for DepthOnly {
+    fn width(&self) -> u32 {
+        self.0
+    }
+
+    fn height(&self) -> u32 {
+        self.0
+    }
+
+    fn set_pixel(&mut self, _: u32, _: u32, _: [u8; 4]) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShadowMap;
+    use crate::geometry::vector::Point3;
+    use crate::lighting::{Color, Light, Lighting};
+
+    const UP: Point3 = Point3 { x: 0.0, y: 1.0, z: 0.0 };
+
+    fn point(x: f32, y: f32, z: f32) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    fn sun() -> Lighting {
+        let mut lighting = Lighting::new();
+        lighting.add(Light::ambient(Color::WHITE, 0.1));
+        lighting.add(Light::directional(UP, Color::WHITE, 1.0));
+        lighting
+    }
+
+    #[test]
+    fn occluded_points() {
+        // Small roof over a big floor
+        let casters = [
+            [point(-0.5, 1.0, -0.5), point(0.5, 1.0, -0.5), point(0.0, 1.0, 0.5)],
+            [point(-4.0, 0.0, -4.0), point(4.0, 0.0, -4.0), point(0.0, 0.0, 4.0)],
+        ];
+        let map = ShadowMap::new(&sun(), &casters, 256, 0.01).unwrap();
+        assert_eq!(map.light(), 1);
+        assert_eq!(map.visibility(&point(0.0, 0.0, 0.0)), 0.0);
+        assert_eq!(map.visibility(&point(0.0, 1.0, 0.0)), 1.0);
+        assert_eq!(map.visibility(&point(2.0, 0.0, -2.0)), 1.0);
+        assert_eq!(map.visibility(&point(50.0, 0.0, 0.0)), 1.0);
+    }
+
+    #[test]
+    fn without_directional_light() {
+        let mut lighting = Lighting::new();
+        lighting.add(Light::ambient(Color::WHITE, 0.1));
+        let casters = [[point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0), point(0.0, 0.0, 1.0)]];
+        assert!(ShadowMap::new(&lighting, &casters, 16, 0.01).is_none());
+        assert!(ShadowMap::new(&sun(), &[], 16, 0.01).is_none());
+    }
+}
@@ -0,0 +1,178 @@
+use crate::render::pipeline::ViewTriangle;
+use crate::render::{DepthBuffer, DrawStrategy, Framebuffer, Rasterizer, RenderContext, ScreenPosition};
+use std::sync::{Arc, Mutex};
+
+/// Width and height in pixels of the tiles drawn in parallel
+const TILE_SIZE: u32 = 32;
+
+/// Triangle of the pipeline waiting to be drawn in the tiles it overlaps
+pub(crate) struct DeferredTriangle {
+    /// Logic to draw the triangle
+    pub strategy: Arc<dyn DrawStrategy>,
+    /// Triangle to draw, already in view and screen space
+    pub triangle: ViewTriangle,
+}
+
+/// Region of the frame drawn by a single thread
+struct Tile {
+    /// Upper-left corner and lower-right one, excluded, of the region
+    clip: [u32; 4],
+    /// Indices of the triangles overlapping the tile, in the order they were submitted
+    bin: Vec<usize>,
+    pixels: TilePixels,
+    depth: Option<DepthBuffer>,
+}
+
+impl Tile {
+    /// Draws the triangles of its bin, one after another, and then their hidden lines
+    fn draw(&mut self, triangles: &[DeferredTriangle], context: &RenderContext) {
+        let mut rasterizer = Rasterizer::clipped(&mut self.pixels, self.depth.as_mut(), self.clip);
+        for deferred in self.bin.iter().map(|&i| &triangles[i]) {
+            deferred.strategy.draw(&mut rasterizer, &deferred.triangle, context);
+        }
+        rasterizer.draw_hidden_lines();
+    }
+}
+
+/// Framebuffer keeping the pixels drawn in a tile. It has the size of the whole frame, so the
+/// triangles are rasterized exactly as in the frame.
+struct TilePixels {
+    width: u32,
+    height: u32,
+    clip: [u32; 4],
+    /// Pixels of the region, [None] until they are drawn
+    pixels: Vec<Option<[u8; 4]>>,
+}
+
+impl TilePixels {
+    fn new(width: u32, height: u32, clip: [u32; 4]) -> Self {
+        let [min_x, min_y, max_x, max_y] = clip;
+        Self { width, height, clip, pixels: vec![None; ((max_x - min_x) * (max_y - min_y)) as usize] }
+    }
+
+    /// Writes the drawn pixels in the frame
+    fn copy_to(&self, target: &mut dyn Framebuffer) {
+        let [min_x, min_y, max_x, _] = self.clip;
+        let columns = max_x - min_x;
+        for (i, pixel) in self.pixels.iter().enumerate() {
+            if let Some(color) = pixel {
+                let i = i as u32;
+                target.set_pixel(min_x + i % columns, min_y + i / columns, *color);
+            }
+        }
+    }
+}
+
+impl Framebuffer for TilePixels {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let [min_x, min_y, max_x, max_y] = self.clip;
+        if (min_x..max_x).contains(&x) && (min_y..max_y).contains(&y) {
+            self.pixels[((y - min_y) * (max_x - min_x) + x - min_x) as usize] = Some(color);
+        }
+    }
+}
+
+/// Draws the triangles splitting the target in tiles. Each triangle is added to the bins of the
+/// tiles it overlaps and the tiles are drawn in parallel, drawing their bins in order. The tiles
+/// are written back to the target one after another, so the frame is exactly the same as drawing
+/// the triangles one by one with any number of threads.
+///
+/// # Arguments
+/// * `target` - Framebuffer to write the pixels
+/// * `depth` - Depth buffer to test the fragments, if any
+/// * `triangles` - Triangles to draw, in the order they were submitted
+/// * `context` - State of the scene
+///
+pub(crate) fn draw_tiles(
+    target: &mut dyn Framebuffer,
+    mut depth: Option<&mut DepthBuffer>,
+    triangles: &[DeferredTriangle],
+    context: &RenderContext,
+) {
+    let (width, height) = (target.width(), target.height());
+    if width == 0 || height == 0 || triangles.is_empty() {
+        return;
+    }
+
+    let columns = width.div_ceil(TILE_SIZE);
+    let mut tiles: Vec<Tile> = (0..height.div_ceil(TILE_SIZE))
+        .flat_map(|row| (0..columns).map(move |column| (column, row)))
+        .map(|(column, row)| {
+            let (x, y) = (column * TILE_SIZE, row * TILE_SIZE);
+            let clip = [x, y, (x + TILE_SIZE).min(width), (y + TILE_SIZE).min(height)];
+            Tile {
+                clip,
+                bin: Vec::new(),
+                pixels: TilePixels::new(width, height, clip),
+                depth: depth.as_deref().map(|depth| depth.tile(clip)),
+            }
+        })
+        .collect();
+
+    for (i, deferred) in triangles.iter().enumerate() {
+        if let Some([min_x, min_y, max_x, max_y]) = bounds(&deferred.triangle.screen, width, height) {
+            for row in min_y / TILE_SIZE..=max_y / TILE_SIZE {
+                for column in min_x / TILE_SIZE..=max_x / TILE_SIZE {
+                    tiles[(row * columns + column) as usize].bin.push(i);
+                }
+            }
+        }
+    }
+
+    let workers = std::thread::available_parallelism().map_or(1, |workers| workers.get());
+    let queue = Mutex::new(tiles.iter_mut().filter(|tile| !tile.bin.is_empty()));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let tile = queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).next();
+                match tile {
+                    Some(tile) => tile.draw(triangles, context),
+                    None => break,
+                }
+            });
+        }
+    });
+
+    for tile in tiles.iter().filter(|tile| !tile.bin.is_empty()) {
+        tile.pixels.copy_to(target);
+        if let (Some(depth), Some(tile_depth)) = (depth.as_deref_mut(), &tile.depth) {
+            depth.copy_tile(tile_depth);
+        }
+    }
+}
+
+/// Returns the pixels of the frame that the triangle may cover, with the lower-right corner
+/// included, or [None] if it's out of the frame. It has a margin of a pixel for the rounding of
+/// the lines.
+fn bounds(screen: &[ScreenPosition; 3], width: u32, height: u32) -> Option<[u32; 4]> {
+    let [a, b, c] = screen;
+    let (max_x, max_y) = (a.0.max(b.0).max(c.0) + 1.0, a.1.max(b.1).max(c.1) + 1.0);
+    if max_x < 0.0 || max_y < 0.0 {
+        return None;
+    }
+    let min_x = (a.0.min(b.0).min(c.0) - 1.0).floor().max(0.0) as u32;
+    let min_y = (a.1.min(b.1).min(c.1) - 1.0).floor().max(0.0) as u32;
+    let max_x = max_x.ceil().min((width - 1) as f32) as u32;
+    let max_y = max_y.ceil().min((height - 1) as f32) as u32;
+    (min_x <= max_x && min_y <= max_y).then_some([min_x, min_y, max_x, max_y])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bounds;
+
+    #[test]
+    fn triangle_bounds() {
+        assert_eq!(bounds(&[(1.5, 2.5, 1.0), (10.2, 3.0, 1.0), (4.0, 40.0, 1.0)], 64, 32), Some([0, 1, 12, 31]));
+        assert_eq!(bounds(&[(-8.0, 2.0, 1.0), (-2.0, 3.0, 1.0), (-4.0, 4.0, 1.0)], 64, 32), None);
+        assert_eq!(bounds(&[(70.0, 2.0, 1.0), (80.0, 3.0, 1.0), (90.0, 4.0, 1.0)], 64, 32), None);
+    }
+}
@@ -0,0 +1,103 @@
+/// Identifier of a value added to a list of [Slots]. It's no longer valid once the list is
+/// cleared, even if a new value takes its position.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SlotId {
+    index: usize,
+    /// Number of times the list was cleared when the value was added
+    generation: u32,
+}
+
+/// Ordered list of values that can be replaced or removed by their identifier while the others
+/// keep their positions
+pub(crate) struct Slots<T> {
+    values: Vec<Option<T>>,
+    /// Number of times the list was cleared
+    generation: u32,
+}
+
+impl<T> Default for Slots<T> {
+    fn default() -> Self {
+        Self { values: Vec::new(), generation: 0 }
+    }
+}
+
+impl<T> Slots<T> {
+    /// Adds a value at the end of the list and returns its identifier
+    pub fn add(&mut self, value: T) -> SlotId {
+        self.values.push(Some(value));
+        SlotId { index: self.values.len() - 1, generation: self.generation }
+    }
+
+    /// Replaces the value with the given identifier, keeping its position. Removed values stay
+    /// removed.
+    pub fn replace(&mut self, id: SlotId, value: T) {
+        if let Some(Some(current)) = self.slot(id) {
+            *current = value;
+        }
+    }
+
+    /// Removes the value with the given identifier
+    pub fn remove(&mut self, id: SlotId) {
+        if let Some(slot) = self.slot(id) {
+            *slot = None;
+        }
+    }
+
+    /// Removes all the values and invalidates their identifiers
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Checks if the list has no values
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+
+    /// Returns the values in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter().flatten()
+    }
+
+    /// Returns the slot of the value with the given identifier, if it's still valid
+    fn slot(&mut self, id: SlotId) -> Option<&mut Option<T>> {
+        if id.generation != self.generation {
+            return None;
+        }
+        self.values.get_mut(id.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Slots;
+
+    #[test]
+    fn replace_and_remove() {
+        let mut slots = Slots::default();
+        let first = slots.add(1);
+        let second = slots.add(2);
+        slots.add(3);
+        slots.replace(first, 4);
+        slots.remove(second);
+        slots.replace(second, 5);
+        assert_eq!(slots.iter().collect::<Vec<_>>(), [&4, &3]);
+        assert!(!slots.is_empty());
+    }
+
+    #[test]
+    fn stale_ids_after_clear() {
+        let mut slots = Slots::default();
+        let stale = slots.add(1);
+        slots.clear();
+        assert!(slots.is_empty());
+        let id = slots.add(2);
+        assert_ne!(stale, id);
+
+        slots.replace(stale, 3);
+        slots.remove(stale);
+        assert_eq!(slots.iter().collect::<Vec<_>>(), [&2]);
+        slots.remove(id);
+        assert!(slots.is_empty());
+    }
+}
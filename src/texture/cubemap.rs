@@ -0,0 +1,109 @@
+use crate::geometry::vector::Point3;
+use crate::geometry::TextureCoordinate;
+use crate::lighting::Color;
+use crate::texture::{Sampler, Texture, TextureError, Wrap};
+use std::path::Path;
+
+/// Six textures covering the faces of a cube around the viewer, sampled by direction
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cubemap {
+    /// Faces looking to +X, -X, +Y, -Y, +Z and -Z
+    faces: [Texture; 6],
+    sampler: Sampler,
+}
+
+impl Cubemap {
+    /// Returns a cubemap with the given faces
+    ///
+    /// # Arguments
+    /// * `faces` - Textures of the faces looking to +X, -X, +Y, -Y, +Z and -Z
+    ///
+    pub fn new(faces: [Texture; 6]) -> Self {
+        let sampler = Sampler { wrap: Wrap::Clamp, ..Sampler::default() };
+        Self { faces, sampler }
+    }
+
+    /// Loads the faces of the cubemap from six images
+    ///
+    /// # Arguments
+    /// * `paths` - Paths of the images of the faces looking to +X, -X, +Y, -Y, +Z and -Z
+    ///
+    /// # Error
+    /// A [TextureError] if any image can't be read or decoded
+    ///
+    pub fn load(paths: [&Path; 6]) -> Result<Self, TextureError> {
+        let [right, left, top, bottom, front, back] = paths.map(Texture::load);
+        Ok(Self::new([right?, left?, top?, bottom?, front?, back?]))
+    }
+
+    /// Returns the color seen in the given direction
+    ///
+    /// # Arguments
+    /// * `direction` - Direction to look at, not necessarily normalized
+    ///
+    pub fn sample(&self, direction: &Point3) -> Color {
+        let Point3 { x, y, z } = *direction;
+        let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+        // Face and coordinates in the face, with the rows going down the image
+        let (face, s, t, major) = if ax >= ay && ax >= az {
+            if x > 0.0 { (0, -z, -y, ax) } else { (1, z, -y, ax) }
+        } else if ay >= az {
+            if y > 0.0 { (2, x, z, ay) } else { (3, x, -z, ay) }
+        } else if z > 0.0 {
+            (4, x, -y, az)
+        } else {
+            (5, -x, -y, az)
+        };
+        if major == 0.0 {
+            return Color::BLACK;
+        }
+        let coordinate = TextureCoordinate {
+            u: (s / major + 1.0) * 0.5,
+            v: 1.0 - (t / major + 1.0) * 0.5,
+        };
+        self.faces[face].sample(&coordinate, &self.sampler)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cubemap;
+    use crate::geometry::vector::Point3;
+    use crate::lighting::Color;
+    use crate::texture::Texture;
+
+    fn cubemap() -> Cubemap {
+        let colors = [
+            Color::new(1.0, 0.0, 0.0),
+            Color::new(0.0, 1.0, 0.0),
+            Color::new(0.0, 0.0, 1.0),
+            Color::new(1.0, 1.0, 0.0),
+            Color::new(0.0, 1.0, 1.0),
+            Color::new(1.0, 0.0, 1.0),
+        ];
+        Cubemap::new(colors.map(|color| Texture::new(1, 1, vec![color])))
+    }
+
+    #[test]
+    fn face_by_direction() {
+        let cubemap = cubemap();
+        let sample = |x, y, z| cubemap.sample(&Point3 { x, y, z });
+        assert_eq!(sample(2.0, 0.5, -1.0), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(sample(-2.0, 0.5, 1.0), Color::new(0.0, 1.0, 0.0));
+        assert_eq!(sample(0.1, 3.0, 0.2), Color::new(0.0, 0.0, 1.0));
+        assert_eq!(sample(0.1, -3.0, 0.2), Color::new(1.0, 1.0, 0.0));
+        assert_eq!(sample(0.0, 0.0, 1.0), Color::new(0.0, 1.0, 1.0));
+        assert_eq!(sample(0.5, -0.5, -1.0), Color::new(1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn coordinates_in_face() {
+        // Left half of the front face is black and the right half white
+        let front = Texture::new(2, 1, vec![Color::BLACK, Color::WHITE]);
+        let mut faces = cubemap().faces;
+        faces[4] = front;
+        let cubemap = Cubemap::new(faces);
+        assert_eq!(cubemap.sample(&Point3 { x: -0.9, y: 0.0, z: 1.0 }), Color::BLACK);
+        assert_eq!(cubemap.sample(&Point3 { x: 0.9, y: 0.0, z: 1.0 }), Color::WHITE);
+    }
+}
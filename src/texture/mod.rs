@@ -0,0 +1,176 @@
+pub use cubemap::Cubemap;
+pub use sampler::{Filter, Sampler, Wrap};
+pub use texture_error::TextureError;
+
+mod cubemap;
+mod sampler;
+mod texture_error;
+
+use crate::geometry::TextureCoordinate;
+use crate::lighting::Color;
+use image::ImageError;
+use log::{error, info};
+use std::path::Path;
+
+/// Image mapped over the faces of a mesh
+#[derive(Clone, Debug, PartialEq)]
+pub struct Texture {
+    width: u32,
+    height: u32,
+    /// Colors of the texels, row by row from the top
+    texels: Vec<Color>,
+}
+
+impl Texture {
+    /// Returns a texture with the given texels
+    ///
+    /// # Arguments
+    /// * `width` - Number of texels of each row
+    /// * `height` - Number of rows
+    /// * `texels` - Colors of the texels, row by row from the top
+    ///
+    /// # Panics
+    /// If the number of texels doesn't match the size or the size is zero
+    ///
+    pub fn new(width: u32, height: u32, texels: Vec<Color>) -> Self {
+        assert!(width > 0 && height > 0, "The texture must have at least one texel");
+        assert_eq!(texels.len(), (width * height) as usize, "The texels don't match the size");
+        Self { width, height, texels }
+    }
+
+    /// Loads the texture from a PNG, TGA or PPM image
+    ///
+    /// # Arguments
+    /// * `path` - Path of the image
+    ///
+    /// # Error
+    /// A [TextureError] if the image can't be read or decoded
+    ///
+    pub fn load(path: &Path) -> Result<Self, TextureError> {
+        info!("Loading texture {}", path.display());
+        let file = path.display().to_string();
+        let image = image::open(path).map_err(|e| {
+            error!("{}", e);
+            match e {
+                ImageError::IoError(_) => TextureError::BadFile(file.clone()),
+                ImageError::Unsupported(_) => TextureError::UnsupportedFormat(file.clone()),
+                _ => TextureError::InvalidImage(file.clone()),
+            }
+        })?;
+        let image = image.to_rgb8();
+        if image.width() == 0 || image.height() == 0 {
+            return Err(TextureError::InvalidImage(file));
+        }
+        let texels = image.pixels().map(|pixel| Color::from_rgb(pixel.0)).collect();
+        Ok(Self::new(image.width(), image.height(), texels))
+    }
+
+    /// Returns the number of texels of each row
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the color of the texel in the given column and row, wrapping them as requested
+    pub fn texel(&self, x: i64, y: i64, wrap: Wrap) -> Color {
+        let x = wrap.apply(x, self.width);
+        let y = wrap.apply(y, self.height);
+        self.texels[(y * self.width + x) as usize]
+    }
+
+    /// Returns the color of the texture in the given coordinates
+    ///
+    /// # Arguments
+    /// * `coordinate` - Point of the texture to read
+    /// * `sampler` - Filter and wrap mode to read the texture
+    ///
+    pub fn sample(&self, coordinate: &TextureCoordinate, sampler: &Sampler) -> Color {
+        // Texel space, with the rows from the top of the image
+        let x = coordinate.u * self.width as f32;
+        let y = (1.0 - coordinate.v) * self.height as f32;
+        match sampler.filter {
+            Filter::Nearest => self.texel(x.floor() as i64, y.floor() as i64, sampler.wrap),
+            Filter::Bilinear => {
+                let (x, y) = (x - 0.5, y - 0.5);
+                let (left, top) = (x.floor(), y.floor());
+                let (dx, dy) = (x - left, y - top);
+                let (left, top) = (left as i64, top as i64);
+                let texel = |x, y| self.texel(x, y, sampler.wrap);
+                let upper = texel(left, top) * (1.0 - dx) + texel(left + 1, top) * dx;
+                let lower = texel(left, top + 1) * (1.0 - dx) + texel(left + 1, top + 1) * dx;
+                upper * (1.0 - dy) + lower * dy
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Filter, Sampler, Texture, TextureError, Wrap};
+    use crate::geometry::TextureCoordinate;
+    use crate::lighting::Color;
+    use std::path::Path;
+
+    /// Two by two texture, black and white on the top row and red and blue on the bottom one
+    fn checker() -> Texture {
+        Texture::new(2, 2, vec![
+            Color::BLACK, Color::WHITE,
+            Color::new(1.0, 0.0, 0.0), Color::new(0.0, 0.0, 1.0),
+        ])
+    }
+
+    fn sampler(filter: Filter, wrap: Wrap) -> Sampler {
+        Sampler { filter, wrap }
+    }
+
+    #[test]
+    fn nearest() {
+        let texture = checker();
+        let sampler = sampler(Filter::Nearest, Wrap::Repeat);
+        assert_eq!(texture.sample(&TextureCoordinate { u: 0.25, v: 0.75 }, &sampler), Color::BLACK);
+        assert_eq!(texture.sample(&TextureCoordinate { u: 0.75, v: 0.75 }, &sampler), Color::WHITE);
+        assert_eq!(
+            texture.sample(&TextureCoordinate { u: 0.25, v: 0.25 }, &sampler),
+            Color::new(1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn bilinear() {
+        let texture = checker();
+        let center = TextureCoordinate { u: 0.5, v: 0.75 };
+        let color = texture.sample(&center, &sampler(Filter::Bilinear, Wrap::Clamp));
+        assert_eq!(color, Color::new(0.5, 0.5, 0.5));
+        let texel_center = TextureCoordinate { u: 0.75, v: 0.75 };
+        assert_eq!(texture.sample(&texel_center, &sampler(Filter::Bilinear, Wrap::Clamp)), Color::WHITE);
+    }
+
+    #[test]
+    fn wrap_modes() {
+        let texture = checker();
+        let outside = TextureCoordinate { u: 1.25, v: 0.75 };
+        assert_eq!(texture.sample(&outside, &sampler(Filter::Nearest, Wrap::Repeat)), Color::BLACK);
+        assert_eq!(texture.sample(&outside, &sampler(Filter::Nearest, Wrap::Clamp)), Color::WHITE);
+    }
+
+    #[test]
+    fn load_ppm() {
+        let path = std::env::temp_dir().join("ferrux_engine_texture_test.ppm");
+        std::fs::write(&path, b"P3\n2 1\n255\n255 0 0 0 0 255\n").unwrap();
+        let texture = Texture::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((texture.width(), texture.height()), (2, 1));
+        assert_eq!(texture.texel(0, 0, Wrap::Clamp), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(texture.texel(1, 0, Wrap::Clamp), Color::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn missing_file() {
+        let path = Path::new("missing_texture.png");
+        assert_eq!(Texture::load(path).unwrap_err(), TextureError::BadFile("missing_texture.png".to_string()));
+    }
+}
@@ -0,0 +1,53 @@
+/// Way to read a texture between the centers of its texels
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Filter {
+    /// Color of the closest texel
+    Nearest,
+    /// Weighted average of the four closest texels
+    #[default]
+    Bilinear,
+}
+
+/// Way to read a texture out of the zero to one range
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    /// The texture is tiled
+    #[default]
+    Repeat,
+    /// The texels of the edges are stretched
+    Clamp,
+}
+
+impl Wrap {
+    /// Returns the index of the texel to read in a row or column of the given size
+    pub(crate) fn apply(self, index: i64, size: u32) -> u32 {
+        match self {
+            Self::Repeat => index.rem_euclid(size as i64) as u32,
+            Self::Clamp => index.clamp(0, size as i64 - 1) as u32,
+        }
+    }
+}
+
+/// Options to sample a texture
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sampler {
+    pub filter: Filter,
+    pub wrap: Wrap,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Wrap;
+
+    #[test]
+    fn repeat() {
+        assert_eq!(Wrap::Repeat.apply(5, 4), 1);
+        assert_eq!(Wrap::Repeat.apply(-1, 4), 3);
+    }
+
+    #[test]
+    fn clamp() {
+        assert_eq!(Wrap::Clamp.apply(5, 4), 3);
+        assert_eq!(Wrap::Clamp.apply(-1, 4), 0);
+    }
+}
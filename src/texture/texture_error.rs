@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+/// Errors than can occur loading a texture
+#[derive(PartialEq)]
+pub enum TextureError {
+    /// The image file is missing or can't be read
+    BadFile(String),
+    /// The format of the image is not supported
+    UnsupportedFormat(String),
+    /// The content of the image is not valid
+    InvalidImage(String),
+}
+
+impl TextureError {
+    fn message(&self) -> String {
+        match self {
+            Self::BadFile(file) => format!("The image can't be read: {}", file),
+            Self::UnsupportedFormat(file) => format!("Unsupported image format: {}", file),
+            Self::InvalidImage(file) => format!("Invalid image: {}", file),
+        }
+    }
+}
+
+impl Error for TextureError {}
+
+impl Debug for TextureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Display for TextureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.message())
+    }
+}